@@ -0,0 +1,288 @@
+use clap::{Args, ValueEnum};
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use message_segment_calculator::{Error, SegmentationOptions, SegmentedMessage};
+
+use crate::summary::MessageSummary;
+use crate::{Failure, SegmentationArgs};
+
+/// Rows read and segmented at a time, so memory use doesn't grow with the
+/// size of the input
+const CHUNK_ROWS: usize = 4096;
+
+/// Columns appended to every row. Rows that can't be segmented only have an
+/// error. Inputs that already have one of them are rejected rather than
+/// overwritten.
+const RESULT_COLUMNS: [&str; 6] = [
+    "encoding",
+    "segments",
+    "characters",
+    "non_gsm_characters",
+    "warnings",
+    "error",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InputFormat {
+    Csv,
+    Jsonl,
+}
+
+impl InputFormat {
+    /// JSONL for `.jsonl` and `.ndjson` files, CSV otherwise
+    fn from_path(path: Option<&Path>) -> Self {
+        let extension = path
+            .and_then(Path::extension)
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("jsonl" | "ndjson") => InputFormat::Jsonl,
+            _ => InputFormat::Csv,
+        }
+    }
+}
+
+/// Segments every message of a CSV or JSONL file and writes the rows back
+/// with the results appended
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+    /// CSV or JSONL file of messages. Read from stdin when absent or "-"
+    input: Option<PathBuf>,
+    /// File to write the rows to, stdout when absent
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Format of the input, from the file extension by default
+    #[arg(long, value_enum)]
+    input_format: Option<InputFormat>,
+    /// CSV column or JSON field holding the message
+    #[arg(short, long, default_value = "text")]
+    column: String,
+    /// Threads segmenting rows, one per CPU by default
+    #[arg(short, long)]
+    jobs: Option<usize>,
+    #[command(flatten)]
+    segmentation: SegmentationArgs,
+}
+
+pub fn run(args: &BatchArgs) -> Result<(), Failure> {
+    let options = args.segmentation.options()?;
+    let input_path = args.input.as_deref().filter(|path| *path != Path::new("-"));
+    let format = args
+        .input_format
+        .unwrap_or_else(|| InputFormat::from_path(input_path));
+
+    let input: Box<dyn Read> = match input_path {
+        Some(path) => Box::new(
+            File::open(path)
+                .map_err(|e| Failure::Io(format!("Can't read {}", path.display()), e))?,
+        ),
+        None => Box::new(io::stdin().lock()),
+    };
+    let output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(
+            File::create(path)
+                .map_err(|e| Failure::Io(format!("Can't write {}", path.display()), e))?,
+        ),
+        None => Box::new(io::stdout().lock()),
+    };
+    let input = BufReader::new(input);
+    let output = BufWriter::new(output);
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.unwrap_or(0))
+        .build()
+        .map_err(|e| Failure::Invalid(e.to_string()))?;
+    match format {
+        InputFormat::Csv => segment_csv(input, output, &args.column, &options, &pool),
+        InputFormat::Jsonl => segment_jsonl(input, output, &args.column, &options, &pool),
+    }
+}
+
+fn segment(
+    text: Option<&str>,
+    column: &str,
+    options: &SegmentationOptions,
+) -> Result<MessageSummary, String> {
+    let text = text.ok_or_else(|| format!("No \"{}\" value", column))?;
+    SegmentedMessage::with_options(text, options)
+        .map(|segmented_message| MessageSummary::new(&segmented_message))
+        .map_err(|error: Error| error.to_string())
+}
+
+fn write_error(e: io::Error) -> Failure {
+    Failure::Io("Can't write the output".to_string(), e)
+}
+
+fn segment_csv(
+    input: impl Read,
+    output: impl Write,
+    column: &str,
+    options: &SegmentationOptions,
+    pool: &ThreadPool,
+) -> Result<(), Failure> {
+    let invalid = |e: csv::Error| Failure::Invalid(format!("Invalid CSV: {}", e));
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(input);
+    let headers = reader.headers().map_err(invalid)?.clone();
+    let text_index = headers
+        .iter()
+        .position(|header| header == column)
+        .ok_or_else(|| Failure::Invalid(format!("No \"{}\" column in the CSV header", column)))?;
+    if let Some(result_column) = RESULT_COLUMNS
+        .iter()
+        .find(|result_column| headers.iter().any(|header| header == **result_column))
+    {
+        return Err(Failure::Invalid(format!(
+            "The CSV header already has a \"{}\" column",
+            result_column
+        )));
+    }
+
+    let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(output);
+    writer
+        .write_record(headers.iter().chain(RESULT_COLUMNS))
+        .map_err(|e| write_error(e.into()))?;
+
+    let mut records = reader.into_records();
+    loop {
+        let chunk = records
+            .by_ref()
+            .take(CHUNK_ROWS)
+            .collect::<Result<Vec<csv::StringRecord>, csv::Error>>()
+            .map_err(invalid)?;
+        if chunk.is_empty() {
+            break;
+        }
+        // Extra fields would shift the result columns
+        if let Some(record) = chunk.iter().find(|record| record.len() > headers.len()) {
+            return Err(Failure::Invalid(format!(
+                "Row on line {} has {} fields, but the header has {}",
+                record.position().map_or(0, |position| position.line()),
+                record.len(),
+                headers.len()
+            )));
+        }
+
+        let rows = pool.install(|| {
+            chunk
+                .par_iter()
+                .map(|record| {
+                    let result = segment(record.get(text_index), column, options);
+                    let mut row = record.iter().map(str::to_string).collect::<Vec<String>>();
+                    row.resize(headers.len(), String::new());
+                    row.extend(result_fields(&result));
+                    row
+                })
+                .collect::<Vec<Vec<String>>>()
+        });
+        for row in rows {
+            writer
+                .write_record(row)
+                .map_err(|e| write_error(e.into()))?;
+        }
+    }
+    writer.flush().map_err(write_error)
+}
+
+// Fields of `RESULT_COLUMNS`, formatted like the CLI's CSV output
+fn result_fields(result: &Result<MessageSummary, String>) -> Vec<String> {
+    match result {
+        Ok(summary) => {
+            let record = summary.csv_record();
+            RESULT_COLUMNS
+                .iter()
+                .map(|column| {
+                    MessageSummary::CSV_HEADER
+                        .iter()
+                        .position(|header| header == column)
+                        .map(|index| record[index].clone())
+                        .unwrap_or_default()
+                })
+                .collect()
+        }
+        Err(error) => {
+            let mut fields = vec![String::new(); RESULT_COLUMNS.len() - 1];
+            fields.push(error.clone());
+            fields
+        }
+    }
+}
+
+fn segment_jsonl(
+    input: impl BufRead,
+    mut output: impl Write,
+    field: &str,
+    options: &SegmentationOptions,
+    pool: &ThreadPool,
+) -> Result<(), Failure> {
+    let mut lines = input.lines().enumerate();
+    loop {
+        let chunk = lines
+            .by_ref()
+            .take(CHUNK_ROWS)
+            .map(|(index, line)| line.map(|line| (index + 1, line)))
+            .collect::<io::Result<Vec<(usize, String)>>>()
+            .map_err(|e| Failure::Io("Can't read the input".to_string(), e))?;
+        if chunk.is_empty() {
+            break;
+        }
+
+        let rows = pool.install(|| {
+            chunk
+                .par_iter()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(number, line)| {
+                    let mut row =
+                        serde_json::from_str::<Map<String, Value>>(line).map_err(|e| {
+                            Failure::Invalid(format!("Invalid JSON on line {}: {}", number, e))
+                        })?;
+                    if let Some(result_field) = RESULT_COLUMNS
+                        .iter()
+                        .find(|result_field| row.contains_key(**result_field))
+                    {
+                        return Err(Failure::Invalid(format!(
+                            "Line {} already has a \"{}\" field",
+                            number, result_field
+                        )));
+                    }
+                    let text = row.get(field).and_then(Value::as_str);
+                    match segment(text, field, options) {
+                        Ok(summary) => append_summary(&mut row, summary),
+                        Err(error) => {
+                            row.insert("error".to_string(), Value::from(error));
+                        }
+                    }
+                    Ok(Value::Object(row).to_string())
+                })
+                .collect::<Result<Vec<String>, Failure>>()
+        })?;
+        for row in rows {
+            writeln!(output, "{}", row).map_err(write_error)?;
+        }
+    }
+    output.flush().map_err(write_error)
+}
+
+fn append_summary(row: &mut Map<String, Value>, summary: MessageSummary) {
+    let warnings = summary
+        .warnings
+        .iter()
+        .map(|warning| serde_json::to_value(warning).unwrap_or_default())
+        .collect::<Vec<Value>>();
+
+    row.insert(
+        "encoding".to_string(),
+        Value::from(format!("{:?}", summary.encoding)),
+    );
+    row.insert("segments".to_string(), Value::from(summary.segments));
+    row.insert("characters".to_string(), Value::from(summary.characters));
+    row.insert(
+        "non_gsm_characters".to_string(),
+        Value::from(summary.non_gsm_characters),
+    );
+    row.insert("warnings".to_string(), Value::from(warnings));
+}
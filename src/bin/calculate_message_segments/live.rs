@@ -0,0 +1,163 @@
+use clap::Args;
+use crossterm::cursor::MoveTo;
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEventKind, KeyModifiers,
+};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use std::io::{self, IsTerminal, Read, Write};
+
+use message_segment_calculator::{Encoding, MessageEditor};
+
+use crate::view::{paint, DIM, RED};
+use crate::{Failure, SegmentationArgs};
+
+/// Chars deleting the last character when keystrokes are piped in
+const BACKSPACE: [char; 2] = ['\u{8}', '\u{7f}'];
+
+/// Shows the encoding, segments and characters left in the current segment
+/// while typing a message. Esc or Ctrl-C ends it.
+///
+/// When stdin isn't a terminal, every char read from it is a keystroke, with
+/// backspace and delete removing the last character, and a status line is
+/// printed after each one.
+#[derive(Debug, Args)]
+pub struct LiveArgs {
+    #[command(flatten)]
+    segmentation: SegmentationArgs,
+}
+
+pub fn run(args: &LiveArgs) -> Result<(), Failure> {
+    let options = args.segmentation.options()?;
+    let mut editor = MessageEditor::new(&options)?;
+
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        run_terminal(&mut editor).map_err(|e| Failure::Io("Can't use the terminal".to_string(), e))
+    } else {
+        replay(&mut editor)
+    }
+}
+
+fn replay(editor: &mut MessageEditor) -> Result<(), Failure> {
+    let mut keystrokes = String::new();
+    io::stdin()
+        .read_to_string(&mut keystrokes)
+        .map_err(|e| Failure::Io("Can't read stdin".to_string(), e))?;
+
+    let mut out = io::stdout().lock();
+    for c in keystrokes.chars() {
+        let switched_by = if BACKSPACE.contains(&c) {
+            editor.pop();
+            None
+        } else {
+            type_text(editor, &c.to_string())
+        };
+        writeln!(
+            out,
+            "{}",
+            status_line(editor, switched_by.as_deref(), false)
+        )
+        .map_err(|e| Failure::Io("Can't write the output".to_string(), e))?;
+    }
+    Ok(())
+}
+
+fn run_terminal(editor: &mut MessageEditor) -> io::Result<()> {
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(out, EnterAlternateScreen, EnableBracketedPaste)?;
+    let result = edit_in_terminal(editor, &mut out);
+    execute!(out, DisableBracketedPaste, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result?;
+
+    // Leave the message and its status in the scrollback
+    writeln!(out, "{}", editor.text())?;
+    writeln!(out, "{}", status_line(editor, None, true))
+}
+
+fn edit_in_terminal(editor: &mut MessageEditor, out: &mut impl Write) -> io::Result<()> {
+    let mut switched_by = None;
+    loop {
+        draw(editor, switched_by.as_deref(), out)?;
+        switched_by = match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                KeyCode::Char(c) => type_text(editor, &c.to_string()),
+                KeyCode::Enter => type_text(editor, "\n"),
+                KeyCode::Tab => type_text(editor, "\t"),
+                KeyCode::Backspace => {
+                    editor.pop();
+                    None
+                }
+                _ => continue,
+            },
+            Event::Paste(text) => type_text(editor, &text),
+            _ => continue,
+        };
+    }
+}
+
+fn draw(editor: &MessageEditor, switched_by: Option<&str>, out: &mut impl Write) -> io::Result<()> {
+    queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+    write!(
+        out,
+        "{}\r\n{}\r\n\r\n{}",
+        paint("Type a message, Esc to finish", Some(DIM), true),
+        status_line(editor, switched_by, true),
+        editor.text().replace('\n', "\r\n")
+    )?;
+    out.flush()
+}
+
+/// Types the text, returning the character that switched the message to UCS-2
+fn type_text(editor: &mut MessageEditor, text: &str) -> Option<String> {
+    let encoding = editor.encoding();
+    editor.push_str(text);
+
+    if encoding == Encoding::GSM7 && editor.encoding() == Encoding::UCS2 {
+        // The message had no UCS-2 characters before
+        editor.non_gsm_characters().first().map(|g| g.to_string())
+    } else {
+        None
+    }
+}
+
+fn status_line(editor: &MessageEditor, switched_by: Option<&str>, color: bool) -> String {
+    let summary = editor.summary();
+    let encoding = format!("{:?}", summary.encoding);
+    let is_ucs2 = summary.encoding == Encoding::UCS2;
+    let mut status = format!(
+        "{}, {} segment{}, {} characters left in this segment",
+        paint(&encoding, is_ucs2.then_some(RED), color),
+        summary.segments,
+        if summary.segments == 1 { "" } else { "s" },
+        summary.remaining_characters
+    );
+
+    let max_segments = editor.options().carrier_profile.max_segments;
+    if summary.segments > max_segments {
+        status.push_str(&format!(
+            ", over the limit of {} segment{}",
+            max_segments,
+            if max_segments == 1 { "" } else { "s" }
+        ));
+    }
+    if editor.options().encoding == Encoding::Auto {
+        if let Some(grapheme) = switched_by {
+            status.push_str(&format!(
+                ", {} just switched the message to UCS-2",
+                paint(&format!("{:?}", grapheme), Some(RED), color)
+            ));
+        } else if let Some(grapheme) = editor.non_gsm_characters().first() {
+            status.push_str(&format!(
+                ", UCS-2 because of {}",
+                paint(&format!("{:?}", grapheme), Some(RED), color)
+            ));
+        }
+    }
+    status
+}
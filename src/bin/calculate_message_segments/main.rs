@@ -0,0 +1,239 @@
+mod batch;
+mod live;
+mod summary;
+mod view;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use batch::BatchArgs;
+use live::LiveArgs;
+use message_segment_calculator::{
+    CarrierProfile, Encoding, Error, SegmentationOptions, SegmentedMessage,
+};
+use summary::MessageSummary;
+
+// Exit codes besides success and clap's 2 for invalid arguments
+const EXIT_FAILURE: u8 = 1;
+const EXIT_GSM7_INCOMPATIBLE: u8 = 3;
+const EXIT_TOO_LONG: u8 = 4;
+
+/// Calculates the encoding and segments of an SMS message.
+///
+/// Exits with 0 on success, 1 on other errors, 2 for invalid arguments, 3 when
+/// the message can't be encoded in GSM-7 as requested and 4 when it needs
+/// more segments than the carrier profile allows.
+#[derive(Debug, Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Message to segment. Read from --file or stdin when absent
+    message: Option<String>,
+    /// File to read the message from
+    #[arg(short, long, conflicts_with = "message")]
+    file: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Color the segments view, when stdout is a terminal by default
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    #[command(flatten)]
+    segmentation: SegmentationArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    Batch(BatchArgs),
+    Live(LiveArgs),
+}
+
+#[derive(Debug, Args)]
+struct SegmentationArgs {
+    #[arg(short, long, value_enum, default_value_t = EncodingArg::Auto)]
+    encoding: EncodingArg,
+    /// Replace characters with GSM-7 lookalikes
+    #[arg(short, long)]
+    smart: bool,
+    /// Built-in carrier profile (3gpp, twilio, cdma) or a TOML or JSON
+    /// profile file
+    #[arg(short, long)]
+    profile: Option<String>,
+}
+
+impl SegmentationArgs {
+    fn options(&self) -> Result<SegmentationOptions, Failure> {
+        let mut options = SegmentationOptions::new()
+            .encoding(self.encoding.into())
+            .smart_encoding(self.smart);
+        if let Some(profile) = &self.profile {
+            options = options.carrier_profile(&load_profile(profile)?);
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Yaml,
+    Csv,
+    /// Every segment with its header, bit usage and characters
+    Segments,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum EncodingArg {
+    Gsm7,
+    Ucs2,
+    Auto,
+}
+
+impl From<EncodingArg> for Encoding {
+    fn from(encoding: EncodingArg) -> Self {
+        match encoding {
+            EncodingArg::Gsm7 => Encoding::GSM7,
+            EncodingArg::Ucs2 => Encoding::UCS2,
+            EncodingArg::Auto => Encoding::Auto,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Failure {
+    Io(String, io::Error),
+    /// Input that can't be processed, like a CSV file without the message column
+    Invalid(String),
+    Segmentation(Error),
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Segmentation(Error::IncompatibleCharacters { .. }) => EXIT_GSM7_INCOMPATIBLE,
+            Failure::Segmentation(Error::MessageTooLong { .. }) => EXIT_TOO_LONG,
+            _ => EXIT_FAILURE,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Io(context, error) => write!(f, "{}: {}", context, error),
+            Failure::Invalid(message) => write!(f, "{}", message),
+            Failure::Segmentation(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        Failure::Segmentation(error)
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Some(Command::Batch(args)) => batch::run(args),
+        Some(Command::Live(args)) => live::run(args),
+        None => run(&cli),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("Error: {}", failure);
+            ExitCode::from(failure.exit_code())
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), Failure> {
+    let message = read_message(cli)?;
+    let options = cli.segmentation.options()?;
+
+    let segmented_message = SegmentedMessage::with_options(&message, &options)?;
+    segmented_message.check_segment_limit()?;
+    write_output(&segmented_message, cli, &mut io::stdout().lock())
+        .map_err(|e| Failure::Io("Can't write the output".to_string(), e))
+}
+
+fn write_output(
+    segmented_message: &SegmentedMessage,
+    cli: &Cli,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let summary = MessageSummary::new(segmented_message);
+    match cli.format {
+        OutputFormat::Text => summary.write_text(out),
+        OutputFormat::Json => summary.write_json(out),
+        OutputFormat::Yaml => summary.write_yaml(out),
+        OutputFormat::Csv => summary.write_csv(out),
+        OutputFormat::Segments => {
+            let color = match cli.color {
+                ColorChoice::Always => true,
+                ColorChoice::Never => false,
+                ColorChoice::Auto => io::stdout().is_terminal(),
+            };
+            view::write_segments(segmented_message, out, color)
+        }
+    }
+}
+
+/// The message argument, or the contents of the file or stdin without the
+/// line break ending them
+fn read_message(cli: &Cli) -> Result<String, Failure> {
+    let text = match (&cli.message, &cli.file) {
+        (Some(message), _) => return Ok(message.clone()),
+        (None, Some(path)) => fs::read_to_string(path)
+            .map_err(|e| Failure::Io(format!("Can't read {}", path.display()), e))?,
+        (None, None) => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| Failure::Io("Can't read stdin".to_string(), e))?;
+            text
+        }
+    };
+
+    let message = text
+        .strip_suffix("\r\n")
+        .or_else(|| text.strip_suffix('\n'))
+        .unwrap_or(&text);
+    Ok(message.to_string())
+}
+
+/// A built-in profile by name, or a profile file, read as JSON when it has a
+/// `.json` extension and as TOML otherwise
+fn load_profile(profile: &str) -> Result<CarrierProfile, Failure> {
+    if let Some(built_in) = CarrierProfile::named(profile) {
+        return Ok(built_in);
+    }
+
+    let contents = fs::read_to_string(profile)
+        .map_err(|e| Failure::Io(format!("Can't read carrier profile {}", profile), e))?;
+    let is_json = Path::new(profile)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+    let profile = if is_json {
+        CarrierProfile::from_json(&contents)?
+    } else {
+        CarrierProfile::from_toml(&contents)?
+    };
+    Ok(profile)
+}
@@ -0,0 +1,131 @@
+use serde::Serialize;
+use std::io::{self, Write};
+
+use message_segment_calculator::{Encoding, SegmentedMessage, Severity};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WarningSummary {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Statistics of a segmented message, as printed by the CLI
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageSummary {
+    pub message: String,
+    pub encoding: Encoding,
+    pub segments: usize,
+    pub characters: usize,
+    pub unicode_scalars: usize,
+    pub total_size_in_bits: u32,
+    pub message_size_in_bits: u32,
+    pub non_gsm_characters: Vec<String>,
+    pub warnings: Vec<WarningSummary>,
+}
+
+impl MessageSummary {
+    pub const CSV_HEADER: [&'static str; 9] = [
+        "message",
+        "encoding",
+        "segments",
+        "characters",
+        "unicode_scalars",
+        "total_size_in_bits",
+        "message_size_in_bits",
+        "non_gsm_characters",
+        "warnings",
+    ];
+
+    pub fn new(segmented_message: &SegmentedMessage) -> Self {
+        let mut non_gsm_characters = segmented_message
+            .get_non_gsm_characters()
+            .into_iter()
+            .collect::<Vec<String>>();
+        non_gsm_characters.sort();
+
+        MessageSummary {
+            message: segmented_message.original_message.clone(),
+            encoding: segmented_message.get_encoding_name(),
+            segments: segmented_message.segments_count(),
+            characters: segmented_message.number_of_characters,
+            unicode_scalars: segmented_message.number_of_unicode_scalars,
+            total_size_in_bits: segmented_message.total_size(),
+            message_size_in_bits: segmented_message.message_size(),
+            non_gsm_characters,
+            warnings: segmented_message
+                .warnings
+                .iter()
+                .map(|warning| WarningSummary {
+                    code: warning.code(),
+                    severity: warning.severity(),
+                    message: warning.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Fields in `CSV_HEADER` order, with lists joined into one field
+    pub fn csv_record(&self) -> Vec<String> {
+        vec![
+            self.message.clone(),
+            format!("{:?}", self.encoding),
+            self.segments.to_string(),
+            self.characters.to_string(),
+            self.unicode_scalars.to_string(),
+            self.total_size_in_bits.to_string(),
+            self.message_size_in_bits.to_string(),
+            self.non_gsm_characters.join(" "),
+            self.warnings
+                .iter()
+                .map(|warning| format!("{}: {}", warning.code, warning.message))
+                .collect::<Vec<String>>()
+                .join("; "),
+        ]
+    }
+
+    pub fn write_json(&self, out: &mut impl Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, self)?;
+        writeln!(out)
+    }
+
+    pub fn write_yaml(&self, out: &mut impl Write) -> io::Result<()> {
+        serde_norway::to_writer(out, self).map_err(io::Error::other)
+    }
+
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut writer = csv::Writer::from_writer(out);
+        writer.write_record(Self::CSV_HEADER)?;
+        writer.write_record(self.csv_record())?;
+        writer.flush()
+    }
+
+    pub fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "Message: \"{}\"", self.message)?;
+        writeln!(out, "Number of segments: {}", self.segments)?;
+        writeln!(out, "Encoding used: {:?}", self.encoding)?;
+        writeln!(out, "Number of characters: {}", self.characters)?;
+        writeln!(out, "Number of unicode scalars: {}", self.unicode_scalars)?;
+        writeln!(out, "Total size in bits: {}", self.total_size_in_bits)?;
+        writeln!(out, "Message size in bits: {}", self.message_size_in_bits)?;
+
+        if self.non_gsm_characters.is_empty() {
+            writeln!(out, "All characters are GSM-compatible.")?;
+        } else {
+            writeln!(
+                out,
+                "Non-GSM characters found: {}",
+                self.non_gsm_characters.join(" ")
+            )?;
+        }
+
+        for warning in &self.warnings {
+            writeln!(
+                out,
+                "{:?} [{}]: {}",
+                warning.severity, warning.code, warning.message
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+
+use message_segment_calculator::{Encoding, GsmCode, Segment, SegmentedMessage};
+
+/// Cells of the bit usage bar of each segment
+const BAR_WIDTH: usize = 24;
+
+const RESET: &str = "\x1b[0m";
+pub const DIM: &str = "\x1b[2m";
+const YELLOW: &str = "\x1b[33m";
+pub const RED: &str = "\x1b[31m";
+
+/// How a character is sent in GSM-7
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Basic,
+    /// Escape followed by an extension or single shift code, 2 septets
+    Extension,
+    /// Not in the GSM-7 tables, so the message has to be UCS-2
+    Ucs2,
+}
+
+impl CharClass {
+    fn color(&self) -> Option<&'static str> {
+        match self {
+            CharClass::Basic => None,
+            CharClass::Extension => Some(YELLOW),
+            CharClass::Ucs2 => Some(RED),
+        }
+    }
+}
+
+/// Prints every segment on its own line with its header, bit usage and
+/// text, followed by the space left in the last segment and the number of
+/// characters of each kind. Without color, characters aren't marked and bars
+/// use ASCII.
+pub fn write_segments(
+    segmented_message: &SegmentedMessage,
+    out: &mut impl Write,
+    color: bool,
+) -> io::Result<()> {
+    let charset = segmented_message.language_shift.charset();
+    let is_ucs2 = segmented_message.get_encoding_name() == Encoding::UCS2;
+    let classify = |grapheme: &str| {
+        if is_ucs2 {
+            // Every character of a UCS-2 message is sent as UCS-2
+            return CharClass::Ucs2;
+        }
+        let codes = grapheme
+            .chars()
+            .map(|c| charset.code(c))
+            .collect::<Option<Vec<GsmCode>>>();
+        match codes {
+            None => CharClass::Ucs2,
+            Some(codes)
+                if codes
+                    .iter()
+                    .any(|code| matches!(code, GsmCode::Extension(_))) =>
+            {
+                CharClass::Extension
+            }
+            Some(_) => CharClass::Basic,
+        }
+    };
+    let total = segmented_message.segments_count();
+    let mut counts = [0usize; 3];
+
+    for (index, segment) in segmented_message.segments.iter().enumerate() {
+        let header = segment.user_data_header.to_bytes();
+        let header = if header.is_empty() {
+            "-".to_string()
+        } else {
+            header
+                .iter()
+                .map(|octet| format!("{:02X}", octet))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        write!(
+            out,
+            "Segment {}/{}  {}  {}/{} bits  UDH {}  ",
+            index + 1,
+            total,
+            bar(segment, color),
+            segment.size_in_bits(),
+            segment.capacity.bits(),
+            paint(&header, Some(DIM), color)
+        )?;
+        for c in segment.data.iter().filter(|c| !c.is_user_data_header()) {
+            let text = c
+                .raw()
+                .chars()
+                .map(|c| {
+                    if c.is_control() {
+                        c.escape_debug().to_string()
+                    } else {
+                        c.to_string()
+                    }
+                })
+                .collect::<String>();
+            let class = classify(c.raw());
+            counts[class as usize] += 1;
+            write!(out, "{}", paint(&text, class.color(), color))?;
+        }
+        writeln!(out)?;
+    }
+
+    if let Some(last) = segmented_message.segments.last() {
+        let free_bits = last.free_size_in_bits().max(0) as u32;
+        let encoding = segmented_message.get_encoding_name();
+        let char_bits = if encoding == Encoding::UCS2 { 16 } else { 7 };
+        writeln!(
+            out,
+            "{:?}, {} segment{}: {} bits free in the last segment ({} more characters)",
+            encoding,
+            total,
+            if total == 1 { "" } else { "s" },
+            free_bits,
+            free_bits / char_bits
+        )?;
+    }
+    writeln!(
+        out,
+        "Characters: {} basic GSM-7, {}, {}",
+        counts[CharClass::Basic as usize],
+        paint(
+            &format!(
+                "{} GSM-7 extension (2 septets)",
+                counts[CharClass::Extension as usize]
+            ),
+            CharClass::Extension.color(),
+            color
+        ),
+        paint(
+            &format!("{} UCS-2", counts[CharClass::Ucs2 as usize]),
+            CharClass::Ucs2.color(),
+            color
+        )
+    )
+}
+
+fn bar(segment: &Segment, color: bool) -> String {
+    let (full, empty) = if color { ('█', '░') } else { ('#', '.') };
+    let capacity = segment.capacity.bits() as usize;
+    let filled = (segment.size_in_bits() as usize * BAR_WIDTH).div_ceil(capacity);
+    let filled = filled.min(BAR_WIDTH);
+
+    format!(
+        "[{}{}]",
+        full.to_string().repeat(filled),
+        empty.to_string().repeat(BAR_WIDTH - filled)
+    )
+}
+
+pub fn paint(text: &str, code: Option<&str>, color: bool) -> String {
+    match code {
+        Some(code) if color => format!("{}{}{}", code, text, RESET),
+        _ => text.to_string(),
+    }
+}
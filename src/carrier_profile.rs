@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use crate::charset::Charset;
+use crate::error::Error;
+use crate::segment_capacity::SegmentCapacity;
+use crate::user_data_header::ReferenceWidth;
+
+/// Segments a concatenation header can number
+const MAX_CONCATENATED_SEGMENTS: usize = u8::MAX as usize;
+/// 7-bit ASCII characters in the user data of an IS-637 message
+const CDMA_SEGMENT_CHARACTERS: u32 = 160;
+/// Parts CDMA carriers concatenate a message from
+const CDMA_MAX_SEGMENTS: usize = 10;
+
+/// How a provider would deliver a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    Sms {
+        segments: usize,
+    },
+    /// Converted to MMS because it needs more segments than the provider's
+    /// conversion threshold
+    Mms {
+        segments: usize,
+    },
+}
+
+/// Carrier Profile Class
+///
+/// How a provider or network counts and delivers segments. Profiles can be
+/// loaded from TOML or JSON; missing fields take the generic 3GPP values.
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CarrierProfile {
+    pub name: String,
+    pub segment_capacity: SegmentCapacity,
+    pub reference_width: ReferenceWidth,
+    /// Most segments a message may be concatenated from
+    pub max_segments: usize,
+    /// Messages needing more segments are sent as MMS
+    pub mms_conversion_threshold: Option<usize>,
+    /// Characters the network doesn't accept in GSM-7 messages, so they
+    /// make the message UCS-2
+    pub rejected_characters: Vec<char>,
+    /// Marks the concatenation header of each segment as reserved by the
+    /// provider, like Twilio does
+    pub twilio_reserved_bits: bool,
+}
+
+impl Default for CarrierProfile {
+    fn default() -> Self {
+        Self::generic_3gpp()
+    }
+}
+
+impl CarrierProfile {
+    /// 140 octet segments with an 8-bit concatenation reference, as in
+    /// 3GPP TS 23.040
+    pub fn generic_3gpp() -> Self {
+        CarrierProfile {
+            name: "3gpp".to_string(),
+            segment_capacity: SegmentCapacity::GSM,
+            reference_width: ReferenceWidth::EightBit,
+            max_segments: MAX_CONCATENATED_SEGMENTS,
+            mms_conversion_threshold: None,
+            rejected_characters: Vec::new(),
+            twilio_reserved_bits: false,
+        }
+    }
+
+    pub fn twilio() -> Self {
+        CarrierProfile {
+            name: "twilio".to_string(),
+            twilio_reserved_bits: true,
+            ..Self::generic_3gpp()
+        }
+    }
+
+    /// IS-637 (3GPP2 C.S0015) networks: segments of 160 7-bit ASCII
+    /// characters, at most 10 of them concatenated, and the GSM-7 characters
+    /// outside ASCII, including the euro sign of the extension table, sent
+    /// as Unicode
+    pub fn cdma() -> Self {
+        let charset = Charset::default();
+        CarrierProfile {
+            name: "cdma".to_string(),
+            segment_capacity: SegmentCapacity::from_bits(CDMA_SEGMENT_CHARACTERS * 7),
+            max_segments: CDMA_MAX_SEGMENTS,
+            rejected_characters: charset
+                .basic_table()
+                .entries()
+                .chain(charset.extension_table().entries())
+                .map(|(_, c)| c)
+                .filter(|c| !c.is_ascii())
+                .collect(),
+            ..Self::generic_3gpp()
+        }
+    }
+
+    pub fn built_in() -> Vec<Self> {
+        vec![Self::generic_3gpp(), Self::twilio(), Self::cdma()]
+    }
+
+    /// Built-in profile with the given name
+    pub fn named(name: &str) -> Option<Self> {
+        Self::built_in()
+            .into_iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        let profile: Self = toml::from_str(toml)
+            .map_err(|e| Error::Decode(format!("Invalid carrier profile: {}", e)))?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let profile: Self = serde_json::from_str(json)
+            .map_err(|e| Error::Decode(format!("Invalid carrier profile: {}", e)))?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(1..=MAX_CONCATENATED_SEGMENTS).contains(&self.max_segments) {
+            return Err(Error::InvalidOption(format!(
+                "Carrier profile \"{}\" allows {} segments, it must allow between 1 and {}",
+                self.name, self.max_segments, MAX_CONCATENATED_SEGMENTS
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn rejects(&self, grapheme: &str) -> bool {
+        grapheme
+            .chars()
+            .any(|c| self.rejected_characters.contains(&c))
+    }
+
+    pub fn delivery(&self, segments: usize) -> Delivery {
+        match self.mms_conversion_threshold {
+            Some(threshold) if segments > threshold => Delivery::Mms { segments },
+            _ => Delivery::Sms { segments },
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use crate::national_language::{LanguageShift, NationalLanguage};
+use crate::utils::national_language_tables::{
+    BENGALI_LOCKING_SHIFT, BENGALI_SINGLE_SHIFT, ESCAPE, GSM7_DEFAULT_ALPHABET,
+    GSM7_EXTENSION_TABLE, GUJARATI_LOCKING_SHIFT, GUJARATI_SINGLE_SHIFT, HINDI_LOCKING_SHIFT,
+    HINDI_SINGLE_SHIFT, KANNADA_LOCKING_SHIFT, KANNADA_SINGLE_SHIFT, MALAYALAM_LOCKING_SHIFT,
+    MALAYALAM_SINGLE_SHIFT, ORIYA_LOCKING_SHIFT, ORIYA_SINGLE_SHIFT, PORTUGUESE_LOCKING_SHIFT,
+    PORTUGUESE_SINGLE_SHIFT, PUNJABI_LOCKING_SHIFT, PUNJABI_SINGLE_SHIFT, SPANISH_SINGLE_SHIFT,
+    TAMIL_LOCKING_SHIFT, TAMIL_SINGLE_SHIFT, TELUGU_LOCKING_SHIFT, TELUGU_SINGLE_SHIFT,
+    TURKISH_LOCKING_SHIFT, TURKISH_SINGLE_SHIFT, UNDEFINED, URDU_LOCKING_SHIFT, URDU_SINGLE_SHIFT,
+};
+
+/// Position of a character in a pair of GSM 7-bit tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GsmCode {
+    /// Code in the default alphabet or a locking shift table
+    Basic(u8),
+    /// Code following an escape, in the extension or a single shift table
+    Extension(u8),
+}
+
+impl GsmCode {
+    /// Code units sent for the character: the code, preceded by an escape
+    /// for extension codes
+    pub fn septets(&self) -> Vec<u8> {
+        match *self {
+            GsmCode::Basic(code) => vec![code],
+            GsmCode::Extension(code) => vec![ESCAPE as u8, code],
+        }
+    }
+}
+
+/// One GSM 7-bit table, looked up by code or by character
+#[derive(Debug)]
+pub struct CharacterTable {
+    chars: [Option<char>; 128],
+    codes: HashMap<char, u8>,
+}
+
+impl CharacterTable {
+    fn new(entries: impl IntoIterator<Item = (u8, char)>) -> Self {
+        let mut chars = [None; 128];
+        let mut codes = HashMap::new();
+        for (code, c) in entries {
+            chars[code as usize] = Some(c);
+            // Characters listed twice are encoded with their first code
+            codes.entry(c).or_insert(code);
+        }
+        CharacterTable { chars, codes }
+    }
+
+    fn from_basic_table(table: &[char; 128]) -> Self {
+        Self::new(
+            table
+                .iter()
+                .enumerate()
+                .filter(|(_, &c)| c != ESCAPE && c != UNDEFINED)
+                .map(|(code, &c)| (code as u8, c)),
+        )
+    }
+
+    pub fn char(&self, code: u8) -> Option<char> {
+        self.chars.get(code as usize).copied().flatten()
+    }
+
+    pub fn code(&self, c: char) -> Option<u8> {
+        self.codes.get(&c).copied()
+    }
+
+    /// Defined codes and their characters, in code order
+    pub fn entries(&self) -> impl Iterator<Item = (u8, char)> + '_ {
+        self.chars
+            .iter()
+            .enumerate()
+            .filter_map(|(code, c)| Some((code as u8, (*c)?)))
+    }
+}
+
+struct CharacterTables {
+    default_alphabet: CharacterTable,
+    extension_table: CharacterTable,
+    locking_shift: HashMap<NationalLanguage, CharacterTable>,
+    single_shift: HashMap<NationalLanguage, CharacterTable>,
+}
+
+lazy_static! {
+    static ref TABLES: CharacterTables = CharacterTables {
+        default_alphabet: CharacterTable::from_basic_table(&GSM7_DEFAULT_ALPHABET),
+        extension_table: CharacterTable::new(GSM7_EXTENSION_TABLE.iter().copied()),
+        locking_shift: HashMap::from([
+            (
+                NationalLanguage::Turkish,
+                CharacterTable::from_basic_table(&TURKISH_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Portuguese,
+                CharacterTable::from_basic_table(&PORTUGUESE_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Bengali,
+                CharacterTable::from_basic_table(&BENGALI_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Gujarati,
+                CharacterTable::from_basic_table(&GUJARATI_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Hindi,
+                CharacterTable::from_basic_table(&HINDI_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Kannada,
+                CharacterTable::from_basic_table(&KANNADA_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Malayalam,
+                CharacterTable::from_basic_table(&MALAYALAM_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Oriya,
+                CharacterTable::from_basic_table(&ORIYA_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Punjabi,
+                CharacterTable::from_basic_table(&PUNJABI_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Tamil,
+                CharacterTable::from_basic_table(&TAMIL_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Telugu,
+                CharacterTable::from_basic_table(&TELUGU_LOCKING_SHIFT),
+            ),
+            (
+                NationalLanguage::Urdu,
+                CharacterTable::from_basic_table(&URDU_LOCKING_SHIFT),
+            ),
+        ]),
+        single_shift: HashMap::from([
+            (
+                NationalLanguage::Turkish,
+                CharacterTable::new(TURKISH_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Spanish,
+                CharacterTable::new(SPANISH_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Portuguese,
+                CharacterTable::new(PORTUGUESE_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Bengali,
+                CharacterTable::new(BENGALI_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Gujarati,
+                CharacterTable::new(GUJARATI_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Hindi,
+                CharacterTable::new(HINDI_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Kannada,
+                CharacterTable::new(KANNADA_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Malayalam,
+                CharacterTable::new(MALAYALAM_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Oriya,
+                CharacterTable::new(ORIYA_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Punjabi,
+                CharacterTable::new(PUNJABI_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Tamil,
+                CharacterTable::new(TAMIL_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Telugu,
+                CharacterTable::new(TELUGU_SINGLE_SHIFT.iter().copied()),
+            ),
+            (
+                NationalLanguage::Urdu,
+                CharacterTable::new(URDU_SINGLE_SHIFT.iter().copied()),
+            ),
+        ]),
+    };
+}
+
+pub(crate) fn locking_shift_table(language: NationalLanguage) -> Option<&'static CharacterTable> {
+    TABLES.locking_shift.get(&language)
+}
+
+pub(crate) fn single_shift_table(language: NationalLanguage) -> Option<&'static CharacterTable> {
+    TABLES.single_shift.get(&language)
+}
+
+/// Charset Class
+///
+/// The basic and extension tables of a GSM 7-bit message, looked up in both
+/// directions. Extension codes the table leaves undefined decode as the basic
+/// table character, as required by 3GPP TS 23.038.
+
+#[derive(Debug, Clone, Copy)]
+pub struct Charset {
+    language_shift: LanguageShift,
+    basic: &'static CharacterTable,
+    extension: &'static CharacterTable,
+}
+
+impl Default for Charset {
+    fn default() -> Self {
+        Self::new(LanguageShift::default())
+    }
+}
+
+impl Charset {
+    /// Tables of the given shift languages. A language without a locking
+    /// shift table, like Spanish, keeps the default alphabet.
+    pub fn new(language_shift: LanguageShift) -> Self {
+        Charset {
+            language_shift,
+            basic: language_shift
+                .locking_shift
+                .and_then(locking_shift_table)
+                .unwrap_or(&TABLES.default_alphabet),
+            extension: language_shift
+                .single_shift
+                .and_then(single_shift_table)
+                .unwrap_or(&TABLES.extension_table),
+        }
+    }
+
+    pub fn language_shift(&self) -> LanguageShift {
+        self.language_shift
+    }
+
+    pub fn basic_table(&self) -> &'static CharacterTable {
+        self.basic
+    }
+
+    pub fn extension_table(&self) -> &'static CharacterTable {
+        self.extension
+    }
+
+    /// Code of a character, preferring the basic table
+    pub fn code(&self, c: char) -> Option<GsmCode> {
+        self.basic
+            .code(c)
+            .map(GsmCode::Basic)
+            .or_else(|| self.extension.code(c).map(GsmCode::Extension))
+    }
+
+    pub fn char(&self, code: GsmCode) -> Option<char> {
+        match code {
+            GsmCode::Basic(code) => self.basic.char(code),
+            GsmCode::Extension(code) => self.extension.char(code).or_else(|| self.basic.char(code)),
+        }
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.code(c).is_some()
+    }
+
+    /// Code units for a character: a basic code or an escape followed by an
+    /// extension code
+    pub fn encode_char(&self, c: char) -> Option<Vec<u8>> {
+        self.code(c).map(|code| code.septets())
+    }
+
+    pub fn decode_code(&self, code: u8) -> Option<char> {
+        self.char(GsmCode::Basic(code))
+    }
+
+    pub fn decode_extension_code(&self, code: u8) -> Option<char> {
+        self.char(GsmCode::Extension(code))
+    }
+}
@@ -1,14 +1,19 @@
-use crate::utils::unicode_to_gsm::UNICODE_TO_GSM;
-use crate::utils::utils::is_gsm7_character;
+use serde::{Deserialize, Serialize};
+
+use crate::national_language::LanguageShift;
 
 /// Encoded Character Class
 ///
 /// Utility class to represent a character in a given encoding
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum Encoding {
+    #[serde(alias = "gsm7")]
     GSM7,
+    #[serde(alias = "ucs2")]
     UCS2,
+    #[serde(alias = "auto")]
     Auto,
 }
 
@@ -25,6 +30,7 @@ pub trait EncodedCharTrait: std::fmt::Debug {
     fn clone_box(&self) -> Box<dyn EncodedCharTrait>;
     fn is_user_data_header(&self) -> bool;
     fn raw(&self) -> &str;
+    fn code_units(&self) -> &[u16];
 }
 
 impl Clone for Box<dyn EncodedCharTrait> {
@@ -35,14 +41,22 @@ impl Clone for Box<dyn EncodedCharTrait> {
 
 impl EncodedChar {
     pub fn new(char: &str, encoding: Encoding) -> Self {
-        let is_gsm7 = char.chars().all(is_gsm7_character);
-        let code_units = if is_gsm7 {
-            UNICODE_TO_GSM[&(char.chars().next().unwrap() as u32)]
-                .iter()
-                .map(|&x| x as u16)
-                .collect()
-        } else {
-            char.encode_utf16().collect()
+        Self::with_language_shift(char, encoding, LanguageShift::default())
+    }
+
+    pub fn with_language_shift(
+        char: &str,
+        encoding: Encoding,
+        language_shift: LanguageShift,
+    ) -> Self {
+        let gsm_codes = char
+            .chars()
+            .map(|c| language_shift.encode_char(c))
+            .collect::<Option<Vec<Vec<u8>>>>();
+        let is_gsm7 = gsm_codes.is_some();
+        let code_units = match gsm_codes {
+            Some(codes) => codes.into_iter().flatten().map(|x| x as u16).collect(),
+            None => char.encode_utf16().collect(),
         };
 
         EncodedChar {
@@ -90,4 +104,8 @@ impl EncodedCharTrait for EncodedChar {
     fn raw(&self) -> &str {
         &self.raw
     }
+
+    fn code_units(&self) -> &[u16] {
+        &self.code_units
+    }
 }
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::encoded_char::Encoding;
+
+/// A grapheme the requested encoding can't represent, with its position in
+/// the original message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncompatibleCharacter {
+    pub grapheme: String,
+    pub char_index: usize,
+    pub byte_range: Range<usize>,
+}
+
+/// Sequence of code units that has to stay within one segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sequence {
+    EscapeSequence,
+    SurrogatePair,
+    GraphemeCluster,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The message has characters the requested encoding can't represent
+    IncompatibleCharacters {
+        encoding: Encoding,
+        characters: Vec<IncompatibleCharacter>,
+    },
+    /// The message needs more segments than the carrier profile allows
+    MessageTooLong {
+        segments: usize,
+        max_segments: usize,
+    },
+    /// An argument that can't be used for the requested operation
+    InvalidOption(String),
+    /// A value that doesn't fit in the field it is encoded into
+    Encode(String),
+    /// Malformed hex, PDUs, headers or user data
+    Decode(String),
+    /// Segment `segment` (starting at 1) ends in the middle of `sequence`
+    SplitSequence { segment: usize, sequence: Sequence },
+    /// The rate table has no price for the destination and encoding, or no
+    /// MMS price for a message converted to MMS
+    NoRate {
+        destination: String,
+        encoding: Encoding,
+        mms: bool,
+    },
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sequence::EscapeSequence => write!(f, "an escape sequence"),
+            Sequence::SurrogatePair => write!(f, "a surrogate pair"),
+            Sequence::GraphemeCluster => write!(f, "a grapheme cluster"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IncompatibleCharacters {
+                encoding,
+                characters,
+            } => {
+                let graphemes = characters
+                    .iter()
+                    .map(|c| format!("\"{}\" at {}", c.grapheme, c.char_index))
+                    .collect::<Vec<String>>();
+                write!(
+                    f,
+                    "The string provided is incompatible with {:?} encoding: {}",
+                    encoding,
+                    graphemes.join(", ")
+                )
+            }
+            Error::MessageTooLong {
+                segments,
+                max_segments,
+            } => write!(
+                f,
+                "The message needs {} segments, more than the {} allowed",
+                segments, max_segments
+            ),
+            Error::InvalidOption(message) | Error::Encode(message) | Error::Decode(message) => {
+                write!(f, "{}", message)
+            }
+            Error::SplitSequence { segment, sequence } => {
+                write!(f, "Segment {} ends in the middle of {}", segment, sequence)
+            }
+            Error::NoRate {
+                destination,
+                mms: true,
+                ..
+            } => write!(f, "No MMS rate for destination {}", destination),
+            Error::NoRate {
+                destination,
+                encoding,
+                ..
+            } => write!(f, "No {:?} rate for destination {}", encoding, destination),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
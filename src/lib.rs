@@ -1,18 +1,53 @@
+mod carrier_profile;
+mod charset;
 mod encoded_char;
+mod error;
+mod message_editor;
+mod national_language;
+mod packing;
+pub mod pdu;
+pub mod pricing;
+mod reader;
+mod reassembly;
 mod segment;
+mod segment_capacity;
+mod segmentation_options;
 mod segmented_message;
+mod smart_encoding;
+pub mod smpp;
+mod split_strategy;
 mod user_data_header;
 mod utils;
+mod validation;
+mod warning;
 
+pub use carrier_profile::{CarrierProfile, Delivery};
+pub use charset::{CharacterTable, Charset, GsmCode};
 pub use encoded_char::{EncodedChar, EncodedCharTrait, Encoding};
+pub use error::{Error, IncompatibleCharacter, Sequence};
+pub use message_editor::{EditorSummary, MessageEditor};
+pub use national_language::{LanguageShift, NationalLanguage, LOCKING_SHIFT_IEI, SINGLE_SHIFT_IEI};
+pub use packing::{
+    decode_gsm7, decode_septets, decode_ucs2, fill_bits, pack_septets, unpack_septets,
+};
+pub use reassembly::{
+    ExpiredMessage, ReassembledMessage, Reassembler, ReassemblyStatus, ReceivedSegment,
+};
 pub use segment::Segment;
-pub use segmented_message::{LineBreakStyle, SegmentedMessage};
-pub use user_data_header::UserDataHeader;
-
-pub mod unicode_to_gsm {
-    pub use crate::utils::unicode_to_gsm::UNICODE_TO_GSM;
-}
+pub use segment_capacity::SegmentCapacity;
+pub use segmentation_options::{LineBreakNormalization, SegmentationOptions};
+pub use segmented_message::{LineBreakStyle, SegmentText, SegmentedMessage};
+pub use smart_encoding::{
+    AppliedSubstitution, SmartEncodingMap, Substitution, SubstitutionCategory, SubstitutionReport,
+};
+pub use split_strategy::{Atomicity, SplitStrategy};
+pub use user_data_header::{
+    next_concatenation_reference, Concatenation, InformationElement, ReferenceWidth,
+    UserDataHeader, UserDataHeaderOctet, CONCATENATION_16_BIT_IEI, CONCATENATION_8_BIT_IEI,
+};
+pub use validation::validate_segmentation;
+pub use warning::{Severity, Span, Warning, INVISIBLE_CHARACTERS};
 
 pub mod smart_encoding_map {
-    pub use crate::utils::smart_encoding_map::SMART_ENCODING_MAP;
+    pub use crate::utils::smart_encoding_map::{DEFAULT_SUBSTITUTIONS, SMART_ENCODING_MAP};
 }
@@ -0,0 +1,376 @@
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::encoded_char::{EncodedChar, Encoding};
+use crate::error::Error;
+use crate::national_language::LanguageShift;
+use crate::segment::Segment;
+use crate::segmentation_options::{LineBreakNormalization, SegmentationOptions};
+use crate::segmented_message::SegmentedMessage;
+use crate::split_strategy::{Atomicity, SplitStrategy};
+
+/// Encoding, segments and room left after an edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditorSummary {
+    pub encoding: Encoding,
+    pub segments: usize,
+    /// Characters that still fit in the last segment, counting GSM-7
+    /// extension characters as two
+    pub remaining_characters: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Grapheme {
+    text: String,
+    /// Absent when the grapheme isn't in the GSM-7 alphabet or the carrier
+    /// profile rejects it
+    gsm7_bits: Option<u16>,
+    ucs2_bits: u16,
+}
+
+/// Message Editor Class
+///
+/// A message being typed, keeping the encoding and segment count up to date
+/// without segmenting the whole message again. Edits take time proportional
+/// to the text after them, so appending and removing characters at the end
+/// takes constant time, except when the edit changes the encoding.
+///
+/// In forced GSM-7, characters outside the alphabet count as one septet, while
+/// `SegmentedMessage` reports them as an error.
+
+#[derive(Debug, Clone)]
+pub struct MessageEditor {
+    options: SegmentationOptions,
+    text: String,
+    /// Chars of the transformed message each char of `text` became
+    transformed_lengths: Vec<usize>,
+    graphemes: Vec<Grapheme>,
+    non_gsm_graphemes: usize,
+    gsm7_bits: u32,
+    ucs2_bits: u32,
+    encoding: Encoding,
+    /// Message bits of each segment when the message needs concatenation
+    segment_bits: Vec<u32>,
+    single_segment_capacity: u32,
+    concatenated_segment_capacity: u32,
+}
+
+impl MessageEditor {
+    /// An empty message. National languages, split strategies other than
+    /// `Character`, line break normalization and code point atomicity aren't
+    /// supported.
+    pub fn new(options: &SegmentationOptions) -> Result<Self, Error> {
+        SegmentedMessage::validate_options(options)?;
+        let unsupported = if !options.national_languages.is_empty() {
+            Some("national languages")
+        } else if options.split_strategy != SplitStrategy::Character {
+            Some("split strategies")
+        } else if options.line_break_normalization != LineBreakNormalization::Keep {
+            Some("line break normalization")
+        } else if options.atomicity != Atomicity::GraphemeCluster {
+            Some("code point atomicity")
+        } else {
+            None
+        };
+        if let Some(unsupported) = unsupported {
+            return Err(Error::InvalidOption(format!(
+                "The message editor doesn't support {}",
+                unsupported
+            )));
+        }
+
+        let capacity = |has_user_data_header| {
+            Segment::with_header(
+                has_user_data_header,
+                LanguageShift::default(),
+                options.reference_width,
+            )
+            .with_capacity(options.segment_capacity)
+            .free_size_in_bits()
+            .max(0) as u32
+        };
+
+        Ok(MessageEditor {
+            options: options.clone(),
+            text: String::new(),
+            transformed_lengths: Vec::new(),
+            graphemes: Vec::new(),
+            non_gsm_graphemes: 0,
+            gsm7_bits: 0,
+            ucs2_bits: 0,
+            encoding: Self::select_encoding(options.encoding, 0),
+            segment_bits: Vec::new(),
+            single_segment_capacity: capacity(false),
+            concatenated_segment_capacity: capacity(true),
+        })
+    }
+
+    pub fn options(&self) -> &SegmentationOptions {
+        &self.options
+    }
+
+    /// The message as typed, before smart encoding
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Chars in the message as typed
+    pub fn len(&self) -> usize {
+        self.transformed_lengths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn segments_count(&self) -> usize {
+        if self.total_bits() <= self.single_segment_capacity {
+            1
+        } else {
+            self.segment_bits.len()
+        }
+    }
+
+    pub fn remaining_characters(&self) -> usize {
+        let free_bits = if self.total_bits() <= self.single_segment_capacity {
+            self.single_segment_capacity - self.total_bits()
+        } else {
+            let last = self.segment_bits.last().copied().unwrap_or(0);
+            self.concatenated_segment_capacity.saturating_sub(last)
+        };
+        let char_bits = if self.encoding == Encoding::UCS2 {
+            16
+        } else {
+            7
+        };
+        (free_bits / char_bits) as usize
+    }
+
+    pub fn summary(&self) -> EditorSummary {
+        EditorSummary {
+            encoding: self.encoding(),
+            segments: self.segments_count(),
+            remaining_characters: self.remaining_characters(),
+        }
+    }
+
+    /// Graphemes outside the GSM-7 alphabet, after smart encoding, in the
+    /// order they appear
+    pub fn non_gsm_characters(&self) -> Vec<&str> {
+        self.graphemes
+            .iter()
+            .filter(|g| g.gsm7_bits.is_none())
+            .map(|g| g.text.as_str())
+            .collect()
+    }
+
+    /// The message segmented from scratch, with segments and warnings
+    pub fn segmented_message(&self) -> Result<SegmentedMessage, Error> {
+        SegmentedMessage::with_options(&self.text, &self.options)
+    }
+
+    pub fn push(&mut self, c: char) -> EditorSummary {
+        self.append(c);
+        self.update_encoding();
+        self.summary()
+    }
+
+    pub fn push_str(&mut self, text: &str) -> EditorSummary {
+        text.chars().for_each(|c| self.append(c));
+        self.update_encoding();
+        self.summary()
+    }
+
+    /// Removes the last char of the message, like a backspace. `None` when
+    /// the message is empty.
+    pub fn pop(&mut self) -> Option<EditorSummary> {
+        self.remove_last()?;
+        self.update_encoding();
+        Some(self.summary())
+    }
+
+    /// Inserts the text before the char at `position`, or at the end when
+    /// `position` is the length of the message
+    pub fn insert(&mut self, position: usize, text: &str) -> Result<EditorSummary, Error> {
+        self.check_position(position)?;
+        let tail = self.truncate(position);
+        text.chars().chain(tail).for_each(|c| self.append(c));
+        self.update_encoding();
+        Ok(self.summary())
+    }
+
+    /// Deletes the chars in `range`
+    pub fn delete(&mut self, range: Range<usize>) -> Result<EditorSummary, Error> {
+        self.check_position(range.end)?;
+        if range.start > range.end {
+            return Err(Error::InvalidOption(format!(
+                "Range {}..{} starts after it ends",
+                range.start, range.end
+            )));
+        }
+        let tail = self.truncate(range.start);
+        tail.into_iter()
+            .skip(range.len())
+            .for_each(|c| self.append(c));
+        self.update_encoding();
+        Ok(self.summary())
+    }
+
+    fn check_position(&self, position: usize) -> Result<(), Error> {
+        if position > self.len() {
+            return Err(Error::InvalidOption(format!(
+                "Position {} is past the end of the {} char message",
+                position,
+                self.len()
+            )));
+        }
+        Ok(())
+    }
+
+    // Removes the chars from `position` on, returning them
+    fn truncate(&mut self, position: usize) -> Vec<char> {
+        let mut tail = Vec::new();
+        while self.len() > position {
+            tail.extend(self.remove_last());
+        }
+        tail.reverse();
+        tail
+    }
+
+    fn remove_last(&mut self) -> Option<char> {
+        let c = self.text.pop()?;
+        let transformed_length = self.transformed_lengths.pop().unwrap_or(0);
+
+        // The removed chars may be part of a grapheme with chars before them
+        let mut tail = Vec::new();
+        while tail.len() < transformed_length {
+            let Some(grapheme) = self.pop_grapheme() else {
+                break;
+            };
+            tail.splice(0..0, grapheme.text.chars());
+        }
+        tail.truncate(tail.len() - transformed_length.min(tail.len()));
+        self.push_graphemes(&tail.into_iter().collect::<String>());
+
+        Some(c)
+    }
+
+    fn append(&mut self, c: char) {
+        let transformed = match self.options.smart_encoding_map.get(c) {
+            Some(replacement) if self.options.smart_encoding => replacement.to_string(),
+            _ => c.to_string(),
+        };
+        self.text.push(c);
+        self.transformed_lengths.push(transformed.chars().count());
+
+        // A combining mark or joiner extends the last grapheme
+        let mut tail = self
+            .pop_grapheme()
+            .map(|grapheme| grapheme.text)
+            .unwrap_or_default();
+        tail.push_str(&transformed);
+        self.push_graphemes(&tail);
+    }
+
+    fn push_graphemes(&mut self, text: &str) {
+        for g in text.graphemes(true) {
+            if g == "\r\n" {
+                self.push_grapheme("\r");
+                self.push_grapheme("\n");
+            } else {
+                self.push_grapheme(g);
+            }
+        }
+    }
+
+    fn push_grapheme(&mut self, text: &str) {
+        let language_shift = LanguageShift::default();
+        let is_gsm7 =
+            language_shift.can_encode(text) && !self.options.carrier_profile.rejects(text);
+        let grapheme = Grapheme {
+            text: text.to_string(),
+            gsm7_bits: is_gsm7.then(|| {
+                EncodedChar::with_language_shift(text, Encoding::GSM7, language_shift)
+                    .size_in_bits()
+            }),
+            ucs2_bits: EncodedChar::new(text, Encoding::UCS2).size_in_bits(),
+        };
+
+        if grapheme.gsm7_bits.is_none() {
+            self.non_gsm_graphemes += 1;
+        }
+        self.gsm7_bits += Self::grapheme_bits(&grapheme, Encoding::GSM7);
+        self.ucs2_bits += Self::grapheme_bits(&grapheme, Encoding::UCS2);
+        self.push_bits(Self::grapheme_bits(&grapheme, self.encoding));
+        self.graphemes.push(grapheme);
+    }
+
+    fn pop_grapheme(&mut self) -> Option<Grapheme> {
+        let grapheme = self.graphemes.pop()?;
+
+        if grapheme.gsm7_bits.is_none() {
+            self.non_gsm_graphemes -= 1;
+        }
+        self.gsm7_bits -= Self::grapheme_bits(&grapheme, Encoding::GSM7);
+        self.ucs2_bits -= Self::grapheme_bits(&grapheme, Encoding::UCS2);
+
+        if let Some(last) = self.segment_bits.last_mut() {
+            *last -= Self::grapheme_bits(&grapheme, self.encoding);
+            if *last == 0 {
+                self.segment_bits.pop();
+            }
+        }
+        Some(grapheme)
+    }
+
+    // Graphemes are laid out in the encoding from before the edit, so a
+    // grapheme removed and pushed back doesn't switch it twice. Lays the
+    // segments out again when the edited message needs another encoding.
+    fn update_encoding(&mut self) {
+        let encoding = Self::select_encoding(self.options.encoding, self.non_gsm_graphemes);
+        if encoding == self.encoding {
+            return;
+        }
+
+        self.encoding = encoding;
+        self.segment_bits.clear();
+        for index in 0..self.graphemes.len() {
+            self.push_bits(Self::grapheme_bits(&self.graphemes[index], encoding));
+        }
+    }
+
+    // Fills the segments greedily, as `SegmentedMessage` does once the
+    // message needs concatenation headers
+    fn push_bits(&mut self, bits: u32) {
+        match self.segment_bits.last_mut() {
+            Some(last) if *last + bits <= self.concatenated_segment_capacity => *last += bits,
+            _ => self.segment_bits.push(bits),
+        }
+    }
+
+    fn total_bits(&self) -> u32 {
+        match self.encoding {
+            Encoding::UCS2 => self.ucs2_bits,
+            _ => self.gsm7_bits,
+        }
+    }
+
+    fn select_encoding(encoding: Encoding, non_gsm_graphemes: usize) -> Encoding {
+        match encoding {
+            Encoding::Auto if non_gsm_graphemes > 0 => Encoding::UCS2,
+            Encoding::Auto => Encoding::GSM7,
+            encoding => encoding,
+        }
+    }
+
+    fn grapheme_bits(grapheme: &Grapheme, encoding: Encoding) -> u32 {
+        match encoding {
+            Encoding::UCS2 => grapheme.ucs2_bits as u32,
+            _ => grapheme.gsm7_bits.unwrap_or(7) as u32,
+        }
+    }
+}
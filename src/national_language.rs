@@ -0,0 +1,182 @@
+use crate::charset::{locking_shift_table, single_shift_table, Charset};
+
+/// National Language Class
+///
+/// Languages with a national language shift table defined in 3GPP TS 23.038.
+/// Spanish only defines a single shift table.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NationalLanguage {
+    Turkish,
+    Spanish,
+    Portuguese,
+    Bengali,
+    Gujarati,
+    Hindi,
+    Kannada,
+    Malayalam,
+    Oriya,
+    Punjabi,
+    Tamil,
+    Telugu,
+    Urdu,
+}
+
+/// Information Element Identifier of the national language single shift UDH element
+pub const SINGLE_SHIFT_IEI: u8 = 0x24;
+/// Information Element Identifier of the national language locking shift UDH element
+pub const LOCKING_SHIFT_IEI: u8 = 0x25;
+
+impl NationalLanguage {
+    pub const ALL: [NationalLanguage; 13] = [
+        NationalLanguage::Turkish,
+        NationalLanguage::Spanish,
+        NationalLanguage::Portuguese,
+        NationalLanguage::Bengali,
+        NationalLanguage::Gujarati,
+        NationalLanguage::Hindi,
+        NationalLanguage::Kannada,
+        NationalLanguage::Malayalam,
+        NationalLanguage::Oriya,
+        NationalLanguage::Punjabi,
+        NationalLanguage::Tamil,
+        NationalLanguage::Telugu,
+        NationalLanguage::Urdu,
+    ];
+
+    /// Language identifier carried in the shift table information elements
+    pub fn language_id(&self) -> u8 {
+        match self {
+            NationalLanguage::Turkish => 0x01,
+            NationalLanguage::Spanish => 0x02,
+            NationalLanguage::Portuguese => 0x03,
+            NationalLanguage::Bengali => 0x04,
+            NationalLanguage::Gujarati => 0x05,
+            NationalLanguage::Hindi => 0x06,
+            NationalLanguage::Kannada => 0x07,
+            NationalLanguage::Malayalam => 0x08,
+            NationalLanguage::Oriya => 0x09,
+            NationalLanguage::Punjabi => 0x0a,
+            NationalLanguage::Tamil => 0x0b,
+            NationalLanguage::Telugu => 0x0c,
+            NationalLanguage::Urdu => 0x0d,
+        }
+    }
+
+    pub fn from_language_id(language_id: u8) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|l| l.language_id() == language_id)
+    }
+
+    pub fn has_locking_shift_table(&self) -> bool {
+        locking_shift_table(*self).is_some()
+    }
+
+    pub fn has_single_shift_table(&self) -> bool {
+        single_shift_table(*self).is_some()
+    }
+
+    pub fn locking_shift_code(&self, c: char) -> Option<u8> {
+        locking_shift_table(*self)?.code(c)
+    }
+
+    pub fn single_shift_code(&self, c: char) -> Option<u8> {
+        single_shift_table(*self)?.code(c)
+    }
+
+    pub fn locking_shift_char(&self, code: u8) -> Option<char> {
+        locking_shift_table(*self)?.char(code)
+    }
+
+    pub fn single_shift_char(&self, code: u8) -> Option<char> {
+        single_shift_table(*self)?.char(code)
+    }
+}
+
+/// Combination of a locking and a single shift table used to encode a message.
+/// `None` on either side means the GSM 7-bit default alphabet or extension table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LanguageShift {
+    pub locking_shift: Option<NationalLanguage>,
+    pub single_shift: Option<NationalLanguage>,
+}
+
+impl LanguageShift {
+    pub fn new(
+        locking_shift: Option<NationalLanguage>,
+        single_shift: Option<NationalLanguage>,
+    ) -> Self {
+        LanguageShift {
+            locking_shift,
+            single_shift,
+        }
+    }
+
+    /// Every combination of shift tables available for the given languages,
+    /// starting with the default alphabet.
+    pub fn candidates(languages: &[NationalLanguage]) -> Vec<LanguageShift> {
+        let locking = std::iter::once(None).chain(
+            languages
+                .iter()
+                .filter(|l| l.has_locking_shift_table())
+                .map(|&l| Some(l)),
+        );
+
+        let mut candidates = Vec::new();
+        for locking_shift in locking {
+            let single = std::iter::once(None).chain(
+                languages
+                    .iter()
+                    .filter(|l| l.has_single_shift_table())
+                    .map(|&l| Some(l)),
+            );
+            for single_shift in single {
+                let candidate = LanguageShift::new(locking_shift, single_shift);
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.locking_shift.is_none() && self.single_shift.is_none()
+    }
+
+    /// Octets taken by the shift table information elements in the UDH
+    /// (IEI, length and language identifier for each table in use)
+    pub fn header_octets(&self) -> usize {
+        3 * (self.locking_shift.is_some() as usize + self.single_shift.is_some() as usize)
+    }
+
+    /// Basic and extension tables of the shift languages
+    pub fn charset(&self) -> Charset {
+        Charset::new(*self)
+    }
+
+    /// GSM code units for a character: one code from the locking shift table
+    /// or an escape followed by a code from the single shift table
+    pub fn encode_char(&self, c: char) -> Option<Vec<u8>> {
+        self.charset().encode_char(c)
+    }
+
+    /// Character for a code of the locking shift table (or default alphabet)
+    pub fn decode_code(&self, code: u8) -> Option<char> {
+        self.charset().decode_code(code)
+    }
+
+    /// Character for a code following an escape. Codes the single shift table
+    /// (or default extension table) leaves undefined are displayed as the
+    /// character of the locking shift table, as required by 3GPP TS 23.038
+    pub fn decode_extension_code(&self, code: u8) -> Option<char> {
+        self.charset().decode_extension_code(code)
+    }
+
+    /// Whether every char of the grapheme is in the tables. Indian scripts
+    /// build syllables from letters, vowel signs and viramas that all are.
+    pub fn can_encode(&self, grapheme: &str) -> bool {
+        !grapheme.is_empty() && grapheme.chars().all(|c| self.encode_char(c).is_some())
+    }
+}
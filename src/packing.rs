@@ -0,0 +1,106 @@
+use crate::error::Error;
+use crate::national_language::LanguageShift;
+use crate::utils::national_language_tables::ESCAPE;
+
+/// Fill bits needed after a User Data Header of the given length so that the
+/// first septet of the message starts on a septet boundary
+pub fn fill_bits(header_octets: usize) -> u8 {
+    ((7 - (header_octets * 8) % 7) % 7) as u8
+}
+
+/// Packs GSM 7-bit code units into octets, least significant bit first,
+/// leaving `fill_bits` zero bits before the first septet
+pub fn pack_septets(septets: &[u8], fill_bits: u8) -> Vec<u8> {
+    let total_bits = fill_bits as usize + septets.len() * 7;
+    let mut octets = vec![0u8; total_bits.div_ceil(8)];
+
+    for (i, &septet) in septets.iter().enumerate() {
+        let bit = fill_bits as usize + i * 7;
+        let (index, shift) = (bit / 8, bit % 8);
+        let value = ((septet & 0x7f) as u16) << shift;
+
+        octets[index] |= value as u8;
+        if shift > 1 {
+            octets[index + 1] |= (value >> 8) as u8;
+        }
+    }
+
+    octets
+}
+
+/// Unpacks `septet_count` GSM 7-bit code units from octets, skipping
+/// `fill_bits` bits before the first septet
+pub fn unpack_septets(octets: &[u8], septet_count: usize, fill_bits: u8) -> Result<Vec<u8>, Error> {
+    let required_octets = (fill_bits as usize + septet_count * 7).div_ceil(8);
+    if octets.len() < required_octets {
+        return Err(Error::Decode(format!(
+            "Expected at least {} octets for {} septets, got {}",
+            required_octets,
+            septet_count,
+            octets.len()
+        )));
+    }
+
+    Ok((0..septet_count)
+        .map(|i| {
+            let bit = fill_bits as usize + i * 7;
+            let (index, shift) = (bit / 8, bit % 8);
+            let low = octets[index] as u16;
+            let high = octets.get(index + 1).copied().unwrap_or(0) as u16;
+
+            (((high << 8 | low) >> shift) & 0x7f) as u8
+        })
+        .collect())
+}
+
+/// Converts GSM 7-bit code units back to text using the given shift tables
+pub fn decode_septets(septets: &[u8], language_shift: LanguageShift) -> Result<String, Error> {
+    let mut message = String::new();
+    let mut codes = septets.iter();
+
+    while let Some(&code) = codes.next() {
+        let c = if code == ESCAPE as u8 {
+            match codes.next() {
+                Some(&extension_code) => language_shift.decode_extension_code(extension_code),
+                // A trailing escape has no character to extend
+                None => break,
+            }
+        } else {
+            language_shift.decode_code(code)
+        };
+
+        match c {
+            Some(c) => message.push(c),
+            None => return Err(Error::Decode(format!("Invalid GSM-7 code 0x{:02x}", code))),
+        }
+    }
+
+    Ok(message)
+}
+
+/// Decodes packed GSM 7-bit user data back to text
+pub fn decode_gsm7(
+    octets: &[u8],
+    septet_count: usize,
+    fill_bits: u8,
+    language_shift: LanguageShift,
+) -> Result<String, Error> {
+    let septets = unpack_septets(octets, septet_count, fill_bits)?;
+    decode_septets(&septets, language_shift)
+}
+
+/// Decodes big endian UCS-2 (UTF-16) user data back to text
+pub fn decode_ucs2(octets: &[u8]) -> Result<String, Error> {
+    if !octets.len().is_multiple_of(2) {
+        return Err(Error::Decode(format!(
+            "UCS-2 data has an odd number of octets: {}",
+            octets.len()
+        )));
+    }
+
+    let code_units = octets
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect::<Vec<u16>>();
+    String::from_utf16(&code_units).map_err(|_| Error::Decode("Invalid UCS-2 data".to_string()))
+}
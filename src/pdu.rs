@@ -0,0 +1,782 @@
+use crate::encoded_char::Encoding;
+use crate::error::Error;
+use crate::national_language::LanguageShift;
+use crate::packing::{
+    decode_gsm7, decode_septets, decode_ucs2, fill_bits, pack_septets, unpack_septets,
+};
+use crate::reader::Reader;
+use crate::segmented_message::SegmentedMessage;
+use crate::user_data_header::UserDataHeader;
+
+const TYPE_OF_ADDRESS_UNKNOWN: u8 = 0x81;
+const TYPE_OF_ADDRESS_INTERNATIONAL: u8 = 0x91;
+const TYPE_OF_ADDRESS_ALPHANUMERIC: u8 = 0xd0;
+
+const MESSAGE_TYPE_DELIVER: u8 = 0b00;
+const MESSAGE_TYPE_SUBMIT: u8 = 0b01;
+const MESSAGE_TYPE_STATUS_REPORT: u8 = 0b10;
+
+const MAX_USER_DATA_OCTETS: usize = 140;
+
+const SEMI_OCTET_DIGITS: &[u8; 16] = b"0123456789*#abc?";
+
+// TP-OA and TP-DA hold at most 10 octets of address value
+const MAX_ADDRESS_DIGITS: usize = 20;
+const MAX_ADDRESS_SEPTETS: usize = 11;
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, Error> {
+    let hex = hex
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    if let Some(c) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(Error::Decode(format!("Invalid hex digit {:?}", c)));
+    }
+    if hex.len() % 2 != 0 {
+        return Err(Error::Decode(
+            "Hex string has an odd number of digits".to_string(),
+        ));
+    }
+
+    // Only ASCII digits are left, so every octet is two bytes
+    Ok(hex
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let digit = |b: u8| (b as char).to_digit(16).unwrap_or(0) as u8;
+            digit(pair[0]) << 4 | digit(pair[1])
+        })
+        .collect())
+}
+
+/// Alphabet of the user data, from the TP-DCS octet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataCoding {
+    GSM7,
+    EightBit,
+    UCS2,
+}
+
+impl DataCoding {
+    pub fn from_encoding(encoding: Encoding) -> Result<Self, Error> {
+        match encoding {
+            Encoding::GSM7 => Ok(DataCoding::GSM7),
+            Encoding::UCS2 => Ok(DataCoding::UCS2),
+            Encoding::Auto => Err(Error::InvalidOption(
+                "Encoding::Auto has no data coding scheme".to_string(),
+            )),
+        }
+    }
+
+    /// TP-DCS octet in the general data coding group, without message class
+    pub fn data_coding_scheme(&self) -> u8 {
+        match self {
+            DataCoding::GSM7 => 0x00,
+            DataCoding::EightBit => 0x04,
+            DataCoding::UCS2 => 0x08,
+        }
+    }
+
+    pub fn from_data_coding_scheme(data_coding_scheme: u8) -> Result<Self, Error> {
+        let alphabet = match data_coding_scheme >> 4 {
+            // General data coding and automatic deletion groups
+            0b0000..=0b0111 => (data_coding_scheme >> 2) & 0b11,
+            // Message waiting indication groups
+            0b1100 | 0b1101 => 0b00,
+            0b1110 => 0b10,
+            // Data coding / message class group
+            0b1111 => (data_coding_scheme >> 2) & 0b01,
+            _ => {
+                return Err(Error::Decode(format!(
+                    "Unsupported data coding scheme 0x{:02x}",
+                    data_coding_scheme
+                )))
+            }
+        };
+
+        match alphabet {
+            0b00 => Ok(DataCoding::GSM7),
+            0b01 => Ok(DataCoding::EightBit),
+            0b10 => Ok(DataCoding::UCS2),
+            _ => Err(Error::Decode(format!(
+                "Reserved alphabet in data coding scheme 0x{:02x}",
+                data_coding_scheme
+            ))),
+        }
+    }
+}
+
+/// Originating, destination or recipient address of a TPDU
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    pub type_of_address: u8,
+    pub value: String,
+}
+
+/// Type of number of an address given as text, shared with SMPP addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberType {
+    International,
+    Unknown,
+    Alphanumeric,
+}
+
+impl NumberType {
+    /// Numbers starting with `+` are international, other numbers unknown and
+    /// anything else alphanumeric. Returns the address without its `+`.
+    pub(crate) fn classify(address: &str) -> (Self, &str) {
+        let is_number = |digits: &str| {
+            !digits.is_empty()
+                && digits
+                    .bytes()
+                    .all(|b| SEMI_OCTET_DIGITS[..15].contains(&b.to_ascii_lowercase()))
+        };
+
+        match address.strip_prefix('+') {
+            Some(digits) if is_number(digits) => (NumberType::International, digits),
+            _ if is_number(address) => (NumberType::Unknown, address),
+            _ => (NumberType::Alphanumeric, address),
+        }
+    }
+}
+
+impl Address {
+    /// Numbers starting with `+` are international, other numbers unknown and
+    /// anything else alphanumeric
+    pub fn new(value: &str) -> Self {
+        let (number_type, value) = NumberType::classify(value);
+        let type_of_address = match number_type {
+            NumberType::International => TYPE_OF_ADDRESS_INTERNATIONAL,
+            NumberType::Unknown => TYPE_OF_ADDRESS_UNKNOWN,
+            NumberType::Alphanumeric => TYPE_OF_ADDRESS_ALPHANUMERIC,
+        };
+
+        Address {
+            type_of_address,
+            value: value.to_string(),
+        }
+    }
+
+    pub fn is_international(&self) -> bool {
+        (self.type_of_address >> 4) & 0b111 == 0b001
+    }
+
+    pub fn is_alphanumeric(&self) -> bool {
+        (self.type_of_address >> 4) & 0b111 == 0b101
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        if self.is_alphanumeric() {
+            let septets = self
+                .value
+                .chars()
+                .map(|c| LanguageShift::default().encode_char(c))
+                .collect::<Option<Vec<Vec<u8>>>>()
+                .ok_or_else(|| {
+                    Error::Encode(format!(
+                        "Address \"{}\" is not GSM-7 compatible",
+                        self.value
+                    ))
+                })?
+                .concat();
+            if septets.len() > MAX_ADDRESS_SEPTETS {
+                return Err(Error::Encode(format!(
+                    "Address \"{}\" needs {} septets, at most {} fit",
+                    self.value,
+                    septets.len(),
+                    MAX_ADDRESS_SEPTETS
+                )));
+            }
+            let octets = pack_septets(&septets, 0);
+            let semi_octets = (septets.len() * 7).div_ceil(4);
+
+            let mut bytes = vec![semi_octets as u8, self.type_of_address];
+            bytes.extend(octets);
+            return Ok(bytes);
+        }
+
+        let digits = self
+            .value
+            .bytes()
+            .map(|b| {
+                SEMI_OCTET_DIGITS[..15]
+                    .iter()
+                    .position(|&d| d == b.to_ascii_lowercase())
+                    .map(|n| n as u8)
+                    .ok_or_else(|| Error::Encode(format!("Invalid address digit '{}'", b as char)))
+            })
+            .collect::<Result<Vec<u8>, Error>>()?;
+        if digits.len() > MAX_ADDRESS_DIGITS {
+            return Err(Error::Encode(format!(
+                "Address \"{}\" has {} digits, at most {} fit",
+                self.value,
+                digits.len(),
+                MAX_ADDRESS_DIGITS
+            )));
+        }
+
+        let mut bytes = vec![digits.len() as u8, self.type_of_address];
+        bytes.extend(
+            digits
+                .chunks(2)
+                .map(|pair| pair[0] | pair.get(1).copied().unwrap_or(0x0f) << 4),
+        );
+        Ok(bytes)
+    }
+
+    fn read(reader: &mut Reader, field: &str) -> Result<Self, Error> {
+        let length = reader.octet(field)? as usize;
+        let type_of_address = reader.octet(field)?;
+        let octets = reader.take(length.div_ceil(2), field)?;
+
+        let mut address = Address {
+            type_of_address,
+            value: String::new(),
+        };
+
+        address.value = if address.is_alphanumeric() {
+            decode_gsm7(octets, length * 4 / 7, 0, LanguageShift::default())?
+        } else {
+            Self::decode_digits(octets, length)
+        };
+        Ok(address)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::read(&mut Reader::new(bytes), "address")
+    }
+
+    /// SMSC address as modems prepend it to a TPDU, with a length counting
+    /// octets instead of digits
+    pub fn to_service_centre_bytes(&self) -> Result<Vec<u8>, Error> {
+        if self.is_alphanumeric() {
+            return Err(Error::Encode(format!(
+                "SMSC address \"{}\" is not a number",
+                self.value
+            )));
+        }
+        let mut bytes = self.to_bytes()?;
+        bytes[0] = (bytes.len() - 1) as u8;
+        Ok(bytes)
+    }
+
+    /// Reads the SMSC address before a TPDU, absent when its length is 0
+    fn read_service_centre(reader: &mut Reader) -> Result<Option<Self>, Error> {
+        let length = reader.octet("SMSC address")? as usize;
+        if length == 0 {
+            return Ok(None);
+        }
+        let type_of_address = reader.octet("SMSC address")?;
+        let octets = reader.take(length - 1, "SMSC address")?;
+
+        // An odd number of digits ends with a 0xf filler
+        let mut digits = octets.len() * 2;
+        if octets.last().is_some_and(|octet| octet >> 4 == 0x0f) {
+            digits -= 1;
+        }
+        Ok(Some(Address {
+            type_of_address,
+            value: Self::decode_digits(octets, digits),
+        }))
+    }
+
+    fn decode_digits(octets: &[u8], digits: usize) -> String {
+        octets
+            .iter()
+            .flat_map(|&octet| [octet & 0x0f, octet >> 4])
+            .take(digits)
+            .map(|n| SEMI_OCTET_DIGITS[n as usize] as char)
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_international() {
+            write!(f, "+{}", self.value)
+        } else {
+            write!(f, "{}", self.value)
+        }
+    }
+}
+
+/// TP-SCTS and TP-DT time stamps, in the sender's local time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeStamp {
+    pub year: u8,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// Offset from GMT in quarters of an hour
+    pub time_zone: i8,
+}
+
+impl TimeStamp {
+    fn swapped_semi_octets(value: u8) -> u8 {
+        (value % 10) << 4 | (value / 10)
+    }
+
+    fn from_swapped_semi_octets(octet: u8) -> u8 {
+        (octet & 0x0f) * 10 + (octet >> 4)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let time_zone = Self::swapped_semi_octets(self.time_zone.unsigned_abs())
+            | if self.time_zone < 0 { 0x08 } else { 0x00 };
+
+        vec![
+            Self::swapped_semi_octets(self.year),
+            Self::swapped_semi_octets(self.month),
+            Self::swapped_semi_octets(self.day),
+            Self::swapped_semi_octets(self.hour),
+            Self::swapped_semi_octets(self.minute),
+            Self::swapped_semi_octets(self.second),
+            time_zone,
+        ]
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: &[u8; 7] = bytes
+            .try_into()
+            .map_err(|_| Error::Decode("Time stamp must be 7 octets".to_string()))?;
+        let quarters = Self::from_swapped_semi_octets(bytes[6] & !0x08) as i8;
+
+        Ok(TimeStamp {
+            year: Self::from_swapped_semi_octets(bytes[0]),
+            month: Self::from_swapped_semi_octets(bytes[1]),
+            day: Self::from_swapped_semi_octets(bytes[2]),
+            hour: Self::from_swapped_semi_octets(bytes[3]),
+            minute: Self::from_swapped_semi_octets(bytes[4]),
+            second: Self::from_swapped_semi_octets(bytes[5]),
+            time_zone: if bytes[6] & 0x08 != 0 {
+                -quarters
+            } else {
+                quarters
+            },
+        })
+    }
+}
+
+/// TP-VP in one of the three formats selected by TP-VPF
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidityPeriod {
+    Relative(u8),
+    Absolute(TimeStamp),
+    Enhanced([u8; 7]),
+}
+
+impl ValidityPeriod {
+    fn format(validity_period: &Option<ValidityPeriod>) -> u8 {
+        match validity_period {
+            None => 0b00,
+            Some(ValidityPeriod::Enhanced(_)) => 0b01,
+            Some(ValidityPeriod::Relative(_)) => 0b10,
+            Some(ValidityPeriod::Absolute(_)) => 0b11,
+        }
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        match self {
+            ValidityPeriod::Relative(period) => vec![period],
+            ValidityPeriod::Absolute(time_stamp) => time_stamp.to_bytes(),
+            ValidityPeriod::Enhanced(octets) => octets.to_vec(),
+        }
+    }
+
+    fn read(reader: &mut Reader, format: u8) -> Result<Option<Self>, Error> {
+        Ok(match format {
+            0b10 => Some(ValidityPeriod::Relative(reader.octet("TP-VP")?)),
+            0b11 => Some(ValidityPeriod::Absolute(TimeStamp::from_bytes(
+                reader.take(7, "TP-VP")?,
+            )?)),
+            0b01 => {
+                let mut octets = [0u8; 7];
+                octets.copy_from_slice(reader.take(7, "TP-VP")?);
+                Some(ValidityPeriod::Enhanced(octets))
+            }
+            _ => None,
+        })
+    }
+}
+
+/// TP-UD split into its header and decoded text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserData {
+    pub user_data_header: Option<UserDataHeader>,
+    /// Message payload after the header: septets for GSM-7, octets otherwise
+    pub payload: Vec<u8>,
+    /// Decoded text, absent for 8-bit data
+    pub text: Option<String>,
+}
+
+impl UserData {
+    pub fn decode(
+        data_coding: DataCoding,
+        has_user_data_header: bool,
+        user_data_length: u8,
+        user_data: &[u8],
+    ) -> Result<Self, Error> {
+        let user_data_header = if has_user_data_header {
+            Some(UserDataHeader::from_bytes(user_data)?)
+        } else {
+            None
+        };
+        let header_octets = user_data_header.as_ref().map_or(0, |h| h.len());
+        let language_shift = user_data_header
+            .as_ref()
+            .map(|h| h.language_shift())
+            .unwrap_or_default();
+        let body = &user_data[header_octets.min(user_data.len())..];
+
+        match data_coding {
+            DataCoding::GSM7 => {
+                let fill = fill_bits(header_octets);
+                let header_septets = (header_octets * 8 + fill as usize) / 7;
+                let septet_count = (user_data_length as usize)
+                    .checked_sub(header_septets)
+                    .ok_or_else(|| {
+                        Error::Decode("TP-UDL is shorter than the User Data Header".to_string())
+                    })?;
+                let septets = unpack_septets(body, septet_count, fill)?;
+                let text = decode_septets(&septets, language_shift)?;
+
+                Ok(UserData {
+                    user_data_header,
+                    payload: septets,
+                    text: Some(text),
+                })
+            }
+            DataCoding::EightBit | DataCoding::UCS2 => {
+                let payload = body
+                    .get(..(user_data_length as usize).saturating_sub(header_octets))
+                    .ok_or_else(|| Error::Decode("TP-UD is shorter than TP-UDL".to_string()))?
+                    .to_vec();
+                let text = if data_coding == DataCoding::UCS2 {
+                    Some(decode_ucs2(&payload)?)
+                } else {
+                    None
+                };
+
+                Ok(UserData {
+                    user_data_header,
+                    payload,
+                    text,
+                })
+            }
+        }
+    }
+}
+
+/// SMS-SUBMIT TPDU, sent from the mobile station to the service centre
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmsSubmit {
+    pub reject_duplicates: bool,
+    pub status_report_request: bool,
+    pub reply_path: bool,
+    pub message_reference: u8,
+    pub destination_address: Address,
+    pub protocol_identifier: u8,
+    pub data_coding_scheme: u8,
+    pub validity_period: Option<ValidityPeriod>,
+    pub user_data_header: Option<UserDataHeader>,
+    pub user_data_length: u8,
+    /// TP-UD, including the User Data Header
+    pub user_data: Vec<u8>,
+}
+
+impl SmsSubmit {
+    /// Builds one SMS-SUBMIT per segment. Message references are assigned
+    /// sequentially starting at `message_reference`.
+    pub fn from_segmented_message(
+        segmented_message: &SegmentedMessage,
+        destination: &str,
+        message_reference: u8,
+    ) -> Result<Vec<Self>, Error> {
+        segmented_message.check_segment_limit()?;
+        let encoding = segmented_message.get_encoding_name();
+        let data_coding = DataCoding::from_encoding(encoding)?;
+
+        for (index, segment) in segmented_message.segments.iter().enumerate() {
+            let octets = segment.user_data(encoding).len();
+            if octets > MAX_USER_DATA_OCTETS {
+                return Err(Error::Encode(format!(
+                    "Segment {} has {} octets of user data, a TPDU holds at most {}",
+                    index + 1,
+                    octets,
+                    MAX_USER_DATA_OCTETS
+                )));
+            }
+        }
+
+        Ok(segmented_message
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| SmsSubmit {
+                reject_duplicates: false,
+                status_report_request: false,
+                reply_path: false,
+                message_reference: message_reference.wrapping_add(index as u8),
+                destination_address: Address::new(destination),
+                protocol_identifier: 0x00,
+                data_coding_scheme: data_coding.data_coding_scheme(),
+                validity_period: None,
+                user_data_header: Some(segment.user_data_header.clone()).filter(|h| !h.is_empty()),
+                user_data_length: segment.user_data_length(encoding) as u8,
+                user_data: segment.user_data(encoding),
+            })
+            .collect())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let first_octet = MESSAGE_TYPE_SUBMIT
+            | (self.reject_duplicates as u8) << 2
+            | ValidityPeriod::format(&self.validity_period) << 3
+            | (self.status_report_request as u8) << 5
+            | (self.user_data_header.is_some() as u8) << 6
+            | (self.reply_path as u8) << 7;
+
+        let mut bytes = vec![first_octet, self.message_reference];
+        bytes.extend(self.destination_address.to_bytes()?);
+        bytes.push(self.protocol_identifier);
+        bytes.push(self.data_coding_scheme);
+        if let Some(validity_period) = self.validity_period {
+            bytes.extend(validity_period.to_bytes());
+        }
+        bytes.push(self.user_data_length);
+        bytes.extend(&self.user_data);
+        Ok(bytes)
+    }
+
+    pub fn to_hex(&self) -> Result<String, Error> {
+        Ok(encode_hex(&self.to_bytes()?))
+    }
+
+    /// The TPDU prefixed with an SMSC address, as modems take it in PDU
+    /// mode. Without one, the modem uses the SMSC it has stored.
+    pub fn to_bytes_with_smsc(&self, service_centre: Option<&Address>) -> Result<Vec<u8>, Error> {
+        let mut bytes = match service_centre {
+            Some(address) => address.to_service_centre_bytes()?,
+            None => vec![0x00],
+        };
+        bytes.extend(self.to_bytes()?);
+        Ok(bytes)
+    }
+
+    pub fn to_hex_with_smsc(&self, service_centre: Option<&Address>) -> Result<String, Error> {
+        Ok(encode_hex(&self.to_bytes_with_smsc(service_centre)?))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        let first_octet = reader.octet("first octet")?;
+        if first_octet & 0b11 != MESSAGE_TYPE_SUBMIT {
+            return Err(Error::Decode("PDU is not an SMS-SUBMIT".to_string()));
+        }
+
+        let message_reference = reader.octet("TP-MR")?;
+        let destination_address = Address::read(&mut reader, "TP-DA")?;
+        let protocol_identifier = reader.octet("TP-PID")?;
+        let data_coding_scheme = reader.octet("TP-DCS")?;
+        let validity_period = ValidityPeriod::read(&mut reader, (first_octet >> 3) & 0b11)?;
+        let user_data_length = reader.octet("TP-UDL")?;
+        let user_data = reader.rest().to_vec();
+        let user_data_header = if first_octet & 0x40 != 0 {
+            Some(UserDataHeader::from_bytes(&user_data)?)
+        } else {
+            None
+        };
+
+        Ok(SmsSubmit {
+            reject_duplicates: first_octet & 0x04 != 0,
+            status_report_request: first_octet & 0x20 != 0,
+            reply_path: first_octet & 0x80 != 0,
+            message_reference,
+            destination_address,
+            protocol_identifier,
+            data_coding_scheme,
+            validity_period,
+            user_data_header,
+            user_data_length,
+            user_data,
+        })
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_hex(hex)?)
+    }
+
+    /// Decodes an SMS-SUBMIT after its SMSC address, as written by
+    /// `to_bytes_with_smsc()`
+    pub fn from_bytes_with_smsc(bytes: &[u8]) -> Result<(Option<Address>, Self), Error> {
+        let mut reader = Reader::new(bytes);
+        let service_centre = Address::read_service_centre(&mut reader)?;
+        Ok((service_centre, Self::from_bytes(reader.rest())?))
+    }
+
+    pub fn from_hex_with_smsc(hex: &str) -> Result<(Option<Address>, Self), Error> {
+        Self::from_bytes_with_smsc(&decode_hex(hex)?)
+    }
+
+    pub fn decode_user_data(&self) -> Result<UserData, Error> {
+        UserData::decode(
+            DataCoding::from_data_coding_scheme(self.data_coding_scheme)?,
+            self.user_data_header.is_some(),
+            self.user_data_length,
+            &self.user_data,
+        )
+    }
+}
+
+/// SMS-DELIVER TPDU, sent from the service centre to the mobile station
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmsDeliver {
+    pub more_messages_to_send: bool,
+    pub status_report_indication: bool,
+    pub reply_path: bool,
+    pub originating_address: Address,
+    pub protocol_identifier: u8,
+    pub data_coding_scheme: u8,
+    pub service_centre_time_stamp: TimeStamp,
+    pub user_data: UserData,
+}
+
+impl SmsDeliver {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        let first_octet = reader.octet("first octet")?;
+        if first_octet & 0b11 != MESSAGE_TYPE_DELIVER {
+            return Err(Error::Decode("PDU is not an SMS-DELIVER".to_string()));
+        }
+
+        let originating_address = Address::read(&mut reader, "TP-OA")?;
+        let protocol_identifier = reader.octet("TP-PID")?;
+        let data_coding_scheme = reader.octet("TP-DCS")?;
+        let service_centre_time_stamp = TimeStamp::from_bytes(reader.take(7, "TP-SCTS")?)?;
+        let user_data_length = reader.octet("TP-UDL")?;
+        let user_data = UserData::decode(
+            DataCoding::from_data_coding_scheme(data_coding_scheme)?,
+            first_octet & 0x40 != 0,
+            user_data_length,
+            reader.rest(),
+        )?;
+
+        Ok(SmsDeliver {
+            // TP-MMS is set when no more messages are waiting
+            more_messages_to_send: first_octet & 0x04 == 0,
+            status_report_indication: first_octet & 0x20 != 0,
+            reply_path: first_octet & 0x80 != 0,
+            originating_address,
+            protocol_identifier,
+            data_coding_scheme,
+            service_centre_time_stamp,
+            user_data,
+        })
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_hex(hex)?)
+    }
+
+    /// Decodes a TPDU prefixed with an SMSC address, as modems return it in
+    /// PDU mode
+    pub fn from_bytes_with_smsc(bytes: &[u8]) -> Result<(Option<Address>, Self), Error> {
+        let mut reader = Reader::new(bytes);
+        let service_centre = Address::read_service_centre(&mut reader)?;
+        Ok((service_centre, Self::from_bytes(reader.rest())?))
+    }
+
+    pub fn from_hex_with_smsc(hex: &str) -> Result<(Option<Address>, Self), Error> {
+        Self::from_bytes_with_smsc(&decode_hex(hex)?)
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.user_data.text.as_deref()
+    }
+}
+
+/// SMS-STATUS-REPORT TPDU, reporting the delivery of a submitted message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmsStatusReport {
+    pub more_messages_to_send: bool,
+    pub status_report_qualifier: bool,
+    pub message_reference: u8,
+    pub recipient_address: Address,
+    pub service_centre_time_stamp: TimeStamp,
+    pub discharge_time: TimeStamp,
+    pub status: u8,
+}
+
+impl SmsStatusReport {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        let first_octet = reader.octet("first octet")?;
+        if first_octet & 0b11 != MESSAGE_TYPE_STATUS_REPORT {
+            return Err(Error::Decode("PDU is not an SMS-STATUS-REPORT".to_string()));
+        }
+
+        Ok(SmsStatusReport {
+            more_messages_to_send: first_octet & 0x04 == 0,
+            status_report_qualifier: first_octet & 0x20 != 0,
+            message_reference: reader.octet("TP-MR")?,
+            recipient_address: Address::read(&mut reader, "TP-RA")?,
+            service_centre_time_stamp: TimeStamp::from_bytes(reader.take(7, "TP-SCTS")?)?,
+            discharge_time: TimeStamp::from_bytes(reader.take(7, "TP-DT")?)?,
+            status: reader.octet("TP-ST")?,
+        })
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_hex(hex)?)
+    }
+
+    /// TP-ST values below 0x20 report a completed transaction
+    pub fn is_delivered(&self) -> bool {
+        self.status < 0x20
+    }
+}
+
+/// A TPDU received by a mobile station
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceivedPdu {
+    Deliver(SmsDeliver),
+    StatusReport(SmsStatusReport),
+}
+
+impl ReceivedPdu {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match bytes.first().map(|b| b & 0b11) {
+            Some(MESSAGE_TYPE_DELIVER) => Ok(ReceivedPdu::Deliver(SmsDeliver::from_bytes(bytes)?)),
+            Some(MESSAGE_TYPE_STATUS_REPORT) => Ok(ReceivedPdu::StatusReport(
+                SmsStatusReport::from_bytes(bytes)?,
+            )),
+            Some(message_type) => Err(Error::Decode(format!(
+                "Unsupported TP-MTI 0b{:02b} for a received PDU",
+                message_type
+            ))),
+            None => Err(Error::Decode("PDU is empty".to_string())),
+        }
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_hex(hex)?)
+    }
+
+    /// Decodes a PDU read with AT+CMGR or AT+CMGL in PDU mode, which starts
+    /// with the address of the SMSC that delivered it
+    pub fn from_bytes_with_smsc(bytes: &[u8]) -> Result<(Option<Address>, Self), Error> {
+        let mut reader = Reader::new(bytes);
+        let service_centre = Address::read_service_centre(&mut reader)?;
+        Ok((service_centre, Self::from_bytes(reader.rest())?))
+    }
+
+    pub fn from_hex_with_smsc(hex: &str) -> Result<(Option<Address>, Self), Error> {
+        Self::from_bytes_with_smsc(&decode_hex(hex)?)
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+pub use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::carrier_profile::Delivery;
+use crate::encoded_char::Encoding;
+use crate::error::Error;
+use crate::segmented_message::SegmentedMessage;
+
+/// Destination of rates that apply to any destination without its own
+const ANY_DESTINATION: &str = "*";
+
+/// Price of messages to a destination. CSV rate tables have one per row,
+/// with the columns `destination,encoding,price_per_segment,mms_price`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rate {
+    /// ISO 3166 country code or MCC, or `*` for any other destination
+    pub destination: String,
+    /// Encoding the rate applies to, any encoding when absent
+    pub encoding: Option<Encoding>,
+    pub price_per_segment: Decimal,
+    /// Price of a message converted to MMS
+    pub mms_price: Option<Decimal>,
+}
+
+/// Rate Table Class
+///
+/// Prices of segments per destination and encoding, in a single currency
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateTable {
+    pub currency: String,
+    pub rates: Vec<Rate>,
+}
+
+// JSON prices are kept as written, since serde_json would read numbers as
+// floats on the way to `Decimal`
+#[derive(Deserialize)]
+struct JsonRateTable {
+    currency: String,
+    rates: Vec<JsonRate>,
+}
+
+#[derive(Deserialize)]
+struct JsonRate {
+    destination: String,
+    encoding: Option<Encoding>,
+    price_per_segment: Box<RawValue>,
+    mms_price: Option<Box<RawValue>>,
+}
+
+fn json_price(price: &RawValue) -> Result<Decimal, Error> {
+    let text = match serde_json::from_str::<String>(price.get()) {
+        Ok(text) => text,
+        Err(_) => price.get().to_string(),
+    };
+    let decimal = if text.contains(['e', 'E']) {
+        Decimal::from_scientific(&text)
+    } else {
+        text.parse::<Decimal>()
+    };
+    decimal.map_err(|e| {
+        Error::Decode(format!(
+            "Invalid rate table: price {} is not a decimal: {}",
+            price.get(),
+            e
+        ))
+    })
+}
+
+/// Cost of sending a message to one destination
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostBreakdown {
+    pub destination: String,
+    pub encoding: Encoding,
+    pub delivery: Delivery,
+    /// Price of a segment for SMS, of the whole message for MMS
+    pub unit_price: Decimal,
+    pub total: Decimal,
+    pub currency: String,
+}
+
+/// Cost of sending a message to several recipients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchCost {
+    pub breakdowns: Vec<CostBreakdown>,
+    pub total: Decimal,
+    pub currency: String,
+}
+
+impl BatchCost {
+    /// Total cost of the recipients of each destination
+    pub fn by_destination(&self) -> BTreeMap<String, Decimal> {
+        let mut totals = BTreeMap::new();
+        for breakdown in &self.breakdowns {
+            *totals
+                .entry(breakdown.destination.clone())
+                .or_insert(Decimal::ZERO) += breakdown.total;
+        }
+        totals
+    }
+}
+
+impl RateTable {
+    pub fn new(currency: &str, rates: Vec<Rate>) -> Self {
+        RateTable {
+            currency: currency.to_string(),
+            rates,
+        }
+    }
+
+    /// Reads rates from CSV with a header row. An empty `encoding` or
+    /// `mms_price` leaves it unset.
+    pub fn from_csv(csv: &str, currency: &str) -> Result<Self, Error> {
+        let rates = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(csv.as_bytes())
+            .deserialize()
+            .collect::<Result<Vec<Rate>, csv::Error>>()
+            .map_err(|e| Error::Decode(format!("Invalid rate table: {}", e)))?;
+        Ok(Self::new(currency, rates))
+    }
+
+    /// Reads `{"currency": "USD", "rates": [...]}`. Prices may be JSON
+    /// numbers or strings, both read digit for digit.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let table: JsonRateTable = serde_json::from_str(json)
+            .map_err(|e| Error::Decode(format!("Invalid rate table: {}", e)))?;
+
+        let rates = table
+            .rates
+            .into_iter()
+            .map(|rate| {
+                Ok(Rate {
+                    destination: rate.destination,
+                    encoding: rate.encoding,
+                    price_per_segment: json_price(&rate.price_per_segment)?,
+                    mms_price: rate.mms_price.map(|price| json_price(&price)).transpose()?,
+                })
+            })
+            .collect::<Result<Vec<Rate>, Error>>()?;
+        Ok(Self::new(&table.currency, rates))
+    }
+
+    // Rates for the destination come before `*` ones, and rates for the
+    // encoding before those for any encoding
+    fn find_rate(&self, destination: &str, encoding: Encoding) -> Option<&Rate> {
+        let matching = |rate_destination: &str, rate_encoding: Option<Encoding>| {
+            self.rates.iter().find(|rate| {
+                rate.destination.eq_ignore_ascii_case(rate_destination)
+                    && rate.encoding == rate_encoding
+            })
+        };
+
+        matching(destination, Some(encoding))
+            .or_else(|| matching(destination, None))
+            .or_else(|| matching(ANY_DESTINATION, Some(encoding)))
+            .or_else(|| matching(ANY_DESTINATION, None))
+    }
+
+    pub fn cost(
+        &self,
+        segmented_message: &SegmentedMessage,
+        destination: &str,
+    ) -> Result<CostBreakdown, Error> {
+        let encoding = segmented_message.get_encoding_name();
+        let delivery = segmented_message.delivery();
+        let no_rate = || Error::NoRate {
+            destination: destination.to_string(),
+            encoding,
+            mms: matches!(delivery, Delivery::Mms { .. }),
+        };
+        let rate = self.find_rate(destination, encoding).ok_or_else(no_rate)?;
+
+        let (unit_price, total) = match delivery {
+            Delivery::Sms { segments } => (
+                rate.price_per_segment,
+                rate.price_per_segment * Decimal::from(segments),
+            ),
+            Delivery::Mms { .. } => {
+                let mms_price = rate.mms_price.ok_or_else(no_rate)?;
+                (mms_price, mms_price)
+            }
+        };
+
+        Ok(CostBreakdown {
+            destination: destination.to_string(),
+            encoding,
+            delivery,
+            unit_price,
+            total,
+            currency: self.currency.clone(),
+        })
+    }
+
+    /// Cost of sending the message to every destination, one per recipient
+    pub fn batch_cost(
+        &self,
+        segmented_message: &SegmentedMessage,
+        destinations: &[&str],
+    ) -> Result<BatchCost, Error> {
+        let breakdowns = destinations
+            .iter()
+            .map(|destination| self.cost(segmented_message, destination))
+            .collect::<Result<Vec<CostBreakdown>, Error>>()?;
+        let total = breakdowns.iter().map(|b| b.total).sum();
+
+        Ok(BatchCost {
+            breakdowns,
+            total,
+            currency: self.currency.clone(),
+        })
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::Error;
+
+// Sequential reader over the octets of a TPDU or an SMPP PDU body
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, offset: 0 }
+    }
+
+    pub(crate) fn take(&mut self, count: usize, field: &str) -> Result<&'a [u8], Error> {
+        let taken = self
+            .bytes
+            .get(self.offset..self.offset + count)
+            .ok_or_else(|| Error::Decode(format!("PDU is truncated in {}", field)))?;
+        self.offset += count;
+        Ok(taken)
+    }
+
+    pub(crate) fn octet(&mut self, field: &str) -> Result<u8, Error> {
+        Ok(self.take(1, field)?[0])
+    }
+
+    /// Big endian
+    pub(crate) fn u16(&mut self, field: &str) -> Result<u16, Error> {
+        let octets = self.take(2, field)?;
+        Ok(u16::from_be_bytes([octets[0], octets[1]]))
+    }
+
+    /// The octets not read yet, without reading them
+    pub(crate) fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.offset.min(self.bytes.len())..]
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+
+    pub(crate) fn rest(&mut self) -> &'a [u8] {
+        let rest = self.remaining();
+        self.offset = self.bytes.len();
+        rest
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use crate::encoded_char::Encoding;
+use crate::error::Error;
+use crate::packing::{decode_septets, decode_ucs2};
+use crate::pdu::{DataCoding, SmsDeliver};
+use crate::user_data_header::{ReferenceWidth, UserDataHeader};
+
+/// A received part of a message, before reassembly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedSegment {
+    pub originator: String,
+    pub user_data_header: UserDataHeader,
+    /// Message payload after the header: unpacked septets for GSM-7,
+    /// big endian code units for UCS-2
+    pub payload: Vec<u8>,
+    pub encoding: Encoding,
+}
+
+impl ReceivedSegment {
+    /// `user_data_header` holds the raw header octets, or nothing when the
+    /// segment has no header
+    pub fn new(
+        originator: &str,
+        user_data_header: &[u8],
+        payload: Vec<u8>,
+        encoding: Encoding,
+    ) -> Result<Self, Error> {
+        if encoding == Encoding::Auto {
+            return Err(Error::InvalidOption(
+                "Received segments need a GSM-7 or UCS-2 encoding".to_string(),
+            ));
+        }
+
+        let user_data_header = if user_data_header.is_empty() {
+            UserDataHeader::new()
+        } else {
+            UserDataHeader::from_bytes(user_data_header)?
+        };
+
+        Ok(ReceivedSegment {
+            originator: originator.to_string(),
+            user_data_header,
+            payload,
+            encoding,
+        })
+    }
+
+    pub fn from_sms_deliver(deliver: &SmsDeliver) -> Result<Self, Error> {
+        let encoding = match DataCoding::from_data_coding_scheme(deliver.data_coding_scheme)? {
+            DataCoding::GSM7 => Encoding::GSM7,
+            DataCoding::UCS2 => Encoding::UCS2,
+            DataCoding::EightBit => {
+                return Err(Error::Decode(
+                    "8-bit data messages cannot be reassembled into text".to_string(),
+                ))
+            }
+        };
+
+        Ok(ReceivedSegment {
+            originator: deliver.originating_address.to_string(),
+            user_data_header: deliver
+                .user_data
+                .user_data_header
+                .clone()
+                .unwrap_or_default(),
+            payload: deliver.user_data.payload.clone(),
+            encoding,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReassembledMessage {
+    pub originator: String,
+    /// Concatenation reference, absent for single part messages
+    pub reference: Option<u16>,
+    pub total: u8,
+    pub encoding: Encoding,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReassemblyStatus {
+    Complete(ReassembledMessage),
+    /// More parts are needed; `missing` lists their sequence numbers
+    Pending {
+        received: usize,
+        missing: Vec<u8>,
+    },
+    /// A part with the same sequence number was already received
+    Duplicate {
+        missing: Vec<u8>,
+    },
+    /// Every part arrived but they don't decode together. The group is
+    /// dropped and its parts are returned in sequence order.
+    Undecodable {
+        parts: Vec<ReceivedSegment>,
+        error: Error,
+    },
+}
+
+/// A group that timed out before all of its parts arrived
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpiredMessage {
+    pub originator: String,
+    pub reference_width: ReferenceWidth,
+    pub reference: u16,
+    pub total: u8,
+    pub received: Vec<u8>,
+    pub missing: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct GroupKey {
+    originator: String,
+    // 8-bit and 16-bit references are counted separately
+    reference_width: ReferenceWidth,
+    reference: u16,
+    total: u8,
+}
+
+#[derive(Debug, Clone)]
+struct PartialMessage {
+    first_received: Instant,
+    parts: BTreeMap<u8, ReceivedSegment>,
+}
+
+impl PartialMessage {
+    fn missing(&self, total: u8) -> Vec<u8> {
+        (1..=total)
+            .filter(|sequence| !self.parts.contains_key(sequence))
+            .collect()
+    }
+}
+
+/// Message Reassembler Class
+///
+/// Collects the parts of concatenated messages until they are complete
+
+#[derive(Debug, Clone)]
+pub struct Reassembler {
+    pub timeout: Duration,
+    groups: HashMap<GroupKey, PartialMessage>,
+}
+
+impl Reassembler {
+    /// Groups are dropped by `expire` once `timeout` has passed since their
+    /// first part arrived
+    pub fn new(timeout: Duration) -> Self {
+        Reassembler {
+            timeout,
+            groups: HashMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, segment: ReceivedSegment) -> Result<ReassemblyStatus, Error> {
+        self.push_at(segment, Instant::now())
+    }
+
+    pub fn push_at(
+        &mut self,
+        segment: ReceivedSegment,
+        now: Instant,
+    ) -> Result<ReassemblyStatus, Error> {
+        let concatenation = match segment.user_data_header.concatenation() {
+            Some(concatenation) if concatenation.total > 1 => concatenation,
+            _ => {
+                let originator = segment.originator.clone();
+                let encoding = segment.encoding;
+                return Ok(ReassemblyStatus::Complete(ReassembledMessage {
+                    originator,
+                    reference: None,
+                    total: 1,
+                    encoding,
+                    text: Self::decode(&[&segment])?,
+                }));
+            }
+        };
+
+        if concatenation.sequence == 0 || concatenation.sequence > concatenation.total {
+            return Err(Error::Decode(format!(
+                "Sequence number {} is outside of 1..={}",
+                concatenation.sequence, concatenation.total
+            )));
+        }
+
+        let key = GroupKey {
+            originator: segment.originator.clone(),
+            reference_width: concatenation.reference_width,
+            reference: concatenation.reference,
+            total: concatenation.total,
+        };
+        let group = self
+            .groups
+            .entry(key.clone())
+            .or_insert_with(|| PartialMessage {
+                first_received: now,
+                parts: BTreeMap::new(),
+            });
+
+        if group.parts.contains_key(&concatenation.sequence) {
+            return Ok(ReassemblyStatus::Duplicate {
+                missing: group.missing(key.total),
+            });
+        }
+        // The parts are decoded together, with the settings of the first one
+        if let Some(first) = group.parts.values().next() {
+            if first.encoding != segment.encoding
+                || first.user_data_header.language_shift()
+                    != segment.user_data_header.language_shift()
+            {
+                return Err(Error::Decode(format!(
+                    "Part {} of message {} from {} is encoded differently from the parts before it",
+                    concatenation.sequence, key.reference, key.originator
+                )));
+            }
+        }
+        group.parts.insert(concatenation.sequence, segment);
+
+        let missing = group.missing(key.total);
+        if !missing.is_empty() {
+            return Ok(ReassemblyStatus::Pending {
+                received: group.parts.len(),
+                missing,
+            });
+        }
+
+        let parts = self
+            .groups
+            .remove(&key)
+            .map(|group| group.parts.into_values().collect::<Vec<ReceivedSegment>>())
+            .unwrap_or_default();
+        match Self::decode(&parts.iter().collect::<Vec<&ReceivedSegment>>()) {
+            Ok(text) => Ok(ReassemblyStatus::Complete(ReassembledMessage {
+                originator: key.originator,
+                reference: Some(key.reference),
+                total: key.total,
+                encoding: parts[0].encoding,
+                text,
+            })),
+            Err(error) => Ok(ReassemblyStatus::Undecodable { parts, error }),
+        }
+    }
+
+    // Joins the payloads before decoding, so escape sequences and surrogate
+    // pairs split across parts are decoded correctly
+    fn decode(parts: &[&ReceivedSegment]) -> Result<String, Error> {
+        let payload = parts
+            .iter()
+            .flat_map(|p| p.payload.iter().copied())
+            .collect::<Vec<u8>>();
+
+        match parts[0].encoding {
+            Encoding::GSM7 => decode_septets(&payload, parts[0].user_data_header.language_shift()),
+            _ => decode_ucs2(&payload),
+        }
+    }
+
+    /// Sequence numbers still missing for a pending message
+    pub fn missing_parts(
+        &self,
+        originator: &str,
+        reference_width: ReferenceWidth,
+        reference: u16,
+        total: u8,
+    ) -> Option<Vec<u8>> {
+        let key = GroupKey {
+            originator: originator.to_string(),
+            reference_width,
+            reference,
+            total,
+        };
+        self.groups.get(&key).map(|group| group.missing(total))
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn expire(&mut self) -> Vec<ExpiredMessage> {
+        self.expire_at(Instant::now())
+    }
+
+    /// Drops and returns the groups that timed out
+    pub fn expire_at(&mut self, now: Instant) -> Vec<ExpiredMessage> {
+        let timeout = self.timeout;
+        let expired_keys = self
+            .groups
+            .iter()
+            .filter(|(_, group)| now.saturating_duration_since(group.first_received) >= timeout)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<GroupKey>>();
+
+        expired_keys
+            .into_iter()
+            .map(|key| {
+                let group = self.groups.remove(&key).unwrap();
+                ExpiredMessage {
+                    missing: group.missing(key.total),
+                    received: group.parts.keys().copied().collect(),
+                    originator: key.originator,
+                    reference_width: key.reference_width,
+                    reference: key.reference,
+                    total: key.total,
+                }
+            })
+            .collect()
+    }
+}
@@ -1,47 +1,121 @@
-use crate::encoded_char::EncodedCharTrait;
-use crate::user_data_header::UserDataHeader;
+use crate::encoded_char::{EncodedCharTrait, Encoding};
+use crate::national_language::LanguageShift;
+use crate::packing::{fill_bits, pack_septets};
+use crate::segment_capacity::SegmentCapacity;
+use crate::user_data_header::{Concatenation, ReferenceWidth, UserDataHeader, UserDataHeaderOctet};
 
 #[derive(Debug, Clone)]
 pub struct Segment {
     pub data: Vec<Box<dyn EncodedCharTrait>>,
     pub has_twilio_reserved_bits: bool,
     pub has_user_data_header: bool,
+    pub user_data_header: UserDataHeader,
+    pub reference_width: ReferenceWidth,
+    /// User data space, including the User Data Header
+    pub capacity: SegmentCapacity,
 }
 
 impl Segment {
     pub fn new(with_user_data_header: bool) -> Self {
+        Self::with_language_shift(with_user_data_header, LanguageShift::default())
+    }
+
+    pub fn with_language_shift(with_user_data_header: bool, language_shift: LanguageShift) -> Self {
+        Self::with_header(
+            with_user_data_header,
+            language_shift,
+            ReferenceWidth::default(),
+        )
+    }
+
+    pub fn with_header(
+        with_user_data_header: bool,
+        language_shift: LanguageShift,
+        reference_width: ReferenceWidth,
+    ) -> Self {
+        let mut user_data_header = UserDataHeader::new();
+        if with_user_data_header {
+            user_data_header.set_concatenation(Concatenation::new(reference_width, 0, 0, 0));
+        }
+        user_data_header.set_language_shift(language_shift);
+
         let mut segment = Segment {
             data: Vec::new(),
             has_twilio_reserved_bits: with_user_data_header,
             has_user_data_header: with_user_data_header,
+            user_data_header,
+            reference_width,
+            capacity: SegmentCapacity::default(),
         };
+        segment.write_header();
 
-        if with_user_data_header {
-            for _ in 0..6 {
-                segment.data.push(Box::new(UserDataHeader::new()));
-            }
+        segment
+    }
+
+    pub fn with_capacity(mut self, capacity: SegmentCapacity) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Octets of User Data Header: the header length, the concatenation
+    /// element and any national language shift elements
+    pub fn header_octets(&self) -> usize {
+        self.user_data_header.len()
+    }
+
+    pub fn language_shift(&self) -> LanguageShift {
+        self.user_data_header.language_shift()
+    }
+
+    /// Sets the concatenation reference, total and sequence numbers of a
+    /// segment that has a concatenation header
+    pub fn set_concatenation(&mut self, reference: u16, total: u8, sequence: u8) {
+        if !self.has_user_data_header {
+            return;
         }
 
-        segment
+        self.user_data_header.set_concatenation(Concatenation::new(
+            self.reference_width,
+            reference,
+            total,
+            sequence,
+        ));
+        self.write_header();
+    }
+
+    // Replaces the header octets at the start of the segment with the
+    // serialized User Data Header
+    fn write_header(&mut self) {
+        let current_header_octets = self
+            .data
+            .iter()
+            .take_while(|c| c.is_user_data_header())
+            .count();
+        let header =
+            self.user_data_header.to_bytes().into_iter().map(|value| {
+                Box::new(UserDataHeaderOctet::new(value)) as Box<dyn EncodedCharTrait>
+            });
+
+        self.data.splice(0..current_header_octets, header);
     }
 
-    pub fn size_in_bits(&self) -> u16 {
-        self.data.iter().map(|c| c.size_in_bits()).sum()
+    pub fn size_in_bits(&self) -> u32 {
+        self.data.iter().map(|c| c.size_in_bits() as u32).sum()
     }
 
-    pub fn message_size_in_bits(&self) -> u16 {
+    pub fn message_size_in_bits(&self) -> u32 {
         self.data
             .iter()
             .filter(|c| !c.is_user_data_header())
-            .map(|c| c.size_in_bits())
+            .map(|c| c.size_in_bits() as u32)
             .sum()
     }
 
     pub fn is_user_data_header(&self) -> bool {
         true
     }
-    pub fn free_size_in_bits(&self) -> i16 {
-        1120 - self.size_in_bits() as i16
+    pub fn free_size_in_bits(&self) -> i32 {
+        self.capacity.bits() as i32 - self.size_in_bits() as i32
     }
 
     pub fn add_header(&mut self) -> Vec<Box<dyn EncodedCharTrait>> {
@@ -53,9 +127,9 @@ impl Segment {
         self.has_twilio_reserved_bits = true;
         self.has_user_data_header = true;
 
-        for _ in 0..6 {
-            self.data.insert(0, Box::new(UserDataHeader::new()));
-        }
+        self.user_data_header
+            .set_concatenation(Concatenation::new(self.reference_width, 0, 0, 0));
+        self.write_header();
 
         while self.free_size_in_bits() < 0 {
             if let Some(char) = self.data.pop() {
@@ -66,6 +140,53 @@ impl Segment {
         left_over_char
     }
 
+    pub fn fill_bits(&self) -> u8 {
+        fill_bits(self.header_octets())
+    }
+
+    /// GSM 7-bit code units of the message characters in this segment
+    pub fn septets(&self) -> Vec<u8> {
+        self.data
+            .iter()
+            .filter(|c| !c.is_user_data_header())
+            .flat_map(|c| c.code_units().iter().map(|&code| code as u8))
+            .collect()
+    }
+
+    /// Packed GSM 7-bit user data following the User Data Header, starting
+    /// with the fill bits needed to align the first septet
+    pub fn packed_user_data(&self) -> Vec<u8> {
+        pack_septets(&self.septets(), self.fill_bits())
+    }
+
+    /// TP-UD of the segment: the serialized User Data Header followed by the
+    /// packed GSM 7-bit septets or the UCS-2 code units
+    pub fn user_data(&self, encoding: Encoding) -> Vec<u8> {
+        let mut user_data = self.user_data_header.to_bytes();
+        match encoding {
+            Encoding::GSM7 => user_data.extend(self.packed_user_data()),
+            _ => user_data.extend(
+                self.data
+                    .iter()
+                    .filter(|c| !c.is_user_data_header())
+                    .flat_map(|c| c.raw().encode_utf16().collect::<Vec<u16>>())
+                    .flat_map(|code_unit| code_unit.to_be_bytes()),
+            ),
+        }
+        user_data
+    }
+
+    /// TP-UDL of the segment: septets (including the header and fill bits)
+    /// for GSM-7, octets otherwise
+    pub fn user_data_length(&self, encoding: Encoding) -> usize {
+        match encoding {
+            Encoding::GSM7 => {
+                (self.header_octets() * 8 + self.fill_bits() as usize) / 7 + self.septets().len()
+            }
+            _ => self.user_data(encoding).len(),
+        }
+    }
+
     pub fn push(&mut self, item: Box<dyn EncodedCharTrait>) {
         self.data.push(item);
     }
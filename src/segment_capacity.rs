@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// User data space of a segment, including the User Data Header. Serialized
+/// as `{ octets = 140 }` or `{ bits = 1000 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "CapacityUnits", into = "CapacityUnits")]
+pub struct SegmentCapacity {
+    bits: u32,
+}
+
+impl SegmentCapacity {
+    /// TP-UD of a GSM 03.40 TPDU: 140 octets
+    pub const GSM: Self = Self::from_octets(140);
+    /// SMPP short_message field: 254 octets
+    pub const SMPP_SHORT_MESSAGE: Self = Self::from_octets(254);
+    /// SMPP message_payload TLV: 65535 octets
+    pub const SMPP_MESSAGE_PAYLOAD: Self = Self::from_octets(u16::MAX);
+    /// Largest supported capacity
+    pub const MAX: Self = Self::SMPP_MESSAGE_PAYLOAD;
+
+    pub const fn from_octets(octets: u16) -> Self {
+        SegmentCapacity {
+            bits: octets as u32 * 8,
+        }
+    }
+
+    /// Capacities that aren't a whole number of octets, such as the 7-bit
+    /// character limits of some CDMA networks
+    pub const fn from_bits(bits: u32) -> Self {
+        SegmentCapacity { bits }
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Whole octets that fit in the capacity
+    pub fn octets(&self) -> u32 {
+        self.bits / 8
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum CapacityUnits {
+    Octets { octets: u16 },
+    Bits { bits: u32 },
+}
+
+impl From<CapacityUnits> for SegmentCapacity {
+    fn from(units: CapacityUnits) -> Self {
+        match units {
+            CapacityUnits::Octets { octets } => Self::from_octets(octets),
+            CapacityUnits::Bits { bits } => Self::from_bits(bits),
+        }
+    }
+}
+
+impl From<SegmentCapacity> for CapacityUnits {
+    fn from(capacity: SegmentCapacity) -> Self {
+        match u16::try_from(capacity.octets()) {
+            Ok(octets) if capacity.bits.is_multiple_of(8) => CapacityUnits::Octets { octets },
+            _ => CapacityUnits::Bits {
+                bits: capacity.bits,
+            },
+        }
+    }
+}
+
+impl Default for SegmentCapacity {
+    fn default() -> Self {
+        Self::GSM
+    }
+}
+
+impl fmt::Display for SegmentCapacity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bits.is_multiple_of(8) {
+            write!(f, "{} octets", self.octets())
+        } else {
+            write!(f, "{} bits", self.bits)
+        }
+    }
+}
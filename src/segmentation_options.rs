@@ -0,0 +1,132 @@
+use crate::carrier_profile::CarrierProfile;
+use crate::encoded_char::Encoding;
+use crate::national_language::NationalLanguage;
+use crate::segment_capacity::SegmentCapacity;
+use crate::smart_encoding::SmartEncodingMap;
+use crate::split_strategy::{Atomicity, SplitStrategy};
+use crate::user_data_header::ReferenceWidth;
+
+/// How line breaks are rewritten before segmentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineBreakNormalization {
+    /// Send line breaks as they are
+    #[default]
+    Keep,
+    /// Replace CRLF with LF, saving a character per line break
+    Lf,
+    /// Replace lone LF with CRLF
+    CrLf,
+}
+
+/// Segmentation Options Class
+///
+/// Settings used by `SegmentedMessage::with_options`
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentationOptions {
+    pub encoding: Encoding,
+    pub smart_encoding: bool,
+    /// Substitutions applied when `smart_encoding` is on
+    pub smart_encoding_map: SmartEncodingMap,
+    /// National language shift tables GSM-7 messages may use
+    pub national_languages: Vec<NationalLanguage>,
+    pub reference_width: ReferenceWidth,
+    /// Concatenation reference number, a new one for every message when absent
+    pub concatenation_reference: Option<u16>,
+    /// User data space of every segment, including the User Data Header
+    pub segment_capacity: SegmentCapacity,
+    pub line_break_normalization: LineBreakNormalization,
+    pub split_strategy: SplitStrategy,
+    pub atomicity: Atomicity,
+    /// Twilio by default, whose reserved bits segments have always been
+    /// marked with
+    pub carrier_profile: CarrierProfile,
+}
+
+impl Default for SegmentationOptions {
+    fn default() -> Self {
+        SegmentationOptions {
+            encoding: Encoding::Auto,
+            smart_encoding: false,
+            smart_encoding_map: SmartEncodingMap::default(),
+            national_languages: Vec::new(),
+            reference_width: ReferenceWidth::default(),
+            concatenation_reference: None,
+            segment_capacity: SegmentCapacity::default(),
+            line_break_normalization: LineBreakNormalization::default(),
+            split_strategy: SplitStrategy::default(),
+            atomicity: Atomicity::default(),
+            carrier_profile: CarrierProfile::twilio(),
+        }
+    }
+}
+
+impl SegmentationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn smart_encoding(mut self, smart_encoding: bool) -> Self {
+        self.smart_encoding = smart_encoding;
+        self
+    }
+
+    /// Also turns smart encoding on
+    pub fn smart_encoding_map(mut self, smart_encoding_map: SmartEncodingMap) -> Self {
+        self.smart_encoding = true;
+        self.smart_encoding_map = smart_encoding_map;
+        self
+    }
+
+    pub fn national_languages(mut self, national_languages: &[NationalLanguage]) -> Self {
+        self.national_languages = national_languages.to_vec();
+        self
+    }
+
+    pub fn reference_width(mut self, reference_width: ReferenceWidth) -> Self {
+        self.reference_width = reference_width;
+        self
+    }
+
+    pub fn concatenation_reference(mut self, concatenation_reference: u16) -> Self {
+        self.concatenation_reference = Some(concatenation_reference);
+        self
+    }
+
+    pub fn segment_capacity(mut self, segment_capacity: SegmentCapacity) -> Self {
+        self.segment_capacity = segment_capacity;
+        self
+    }
+
+    pub fn line_break_normalization(
+        mut self,
+        line_break_normalization: LineBreakNormalization,
+    ) -> Self {
+        self.line_break_normalization = line_break_normalization;
+        self
+    }
+
+    pub fn split_strategy(mut self, split_strategy: SplitStrategy) -> Self {
+        self.split_strategy = split_strategy;
+        self
+    }
+
+    pub fn atomicity(mut self, atomicity: Atomicity) -> Self {
+        self.atomicity = atomicity;
+        self
+    }
+
+    /// Counts segments the way the given provider does, taking its segment
+    /// capacity and reference width
+    pub fn carrier_profile(mut self, carrier_profile: &CarrierProfile) -> Self {
+        self.segment_capacity = carrier_profile.segment_capacity;
+        self.reference_width = carrier_profile.reference_width;
+        self.carrier_profile = carrier_profile.clone();
+        self
+    }
+}
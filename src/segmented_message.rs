@@ -1,25 +1,52 @@
 use std::collections::HashSet;
+use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::carrier_profile::{CarrierProfile, Delivery};
 use crate::encoded_char::{EncodedChar, Encoding};
+use crate::error::{Error, IncompatibleCharacter};
+use crate::national_language::{LanguageShift, NationalLanguage};
 use crate::segment::Segment;
-use crate::utils::smart_encoding_map::SMART_ENCODING_MAP;
-use crate::utils::utils::is_gsm7_character;
+use crate::segment_capacity::SegmentCapacity;
+use crate::segmentation_options::{LineBreakNormalization, SegmentationOptions};
+use crate::smart_encoding::{AppliedSubstitution, SubstitutionReport};
+use crate::split_strategy::{Atomicity, SplitStrategy};
+use crate::user_data_header::{next_concatenation_reference, ReferenceWidth};
+use crate::validation::validate_segmentation;
+use crate::warning::{Span, Warning, INVISIBLE_CHARACTERS};
+
+/// Octets of the largest User Data Header: a 16-bit reference concatenation
+/// element and both national language shift elements
+const MAX_HEADER_OCTETS: u32 = 13;
 
 #[derive(Debug, Clone)]
 pub struct SegmentedMessage {
+    pub original_message: String,
     pub encoding: Encoding,
     pub segments: Vec<Segment>,
     pub graphemes: Vec<String>,
     pub encoding_name: Encoding,
+    pub language_shift: LanguageShift,
+    pub options: SegmentationOptions,
     pub number_of_unicode_scalars: usize,
     pub number_of_characters: usize,
     pub encoded_chars: Vec<EncodedChar>,
     pub line_break_style: Option<LineBreakStyle>,
-    pub warnings: Vec<String>,
+    pub warnings: Vec<Warning>,
+    // Index in the original message of every char of the encoded message,
+    // followed by the number of chars of the original message
+    original_char_indices: Vec<usize>,
+}
+
+/// Text carried by one segment, with the range of the original message it covers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentText {
+    pub text: String,
+    pub byte_range: Range<usize>,
+    pub char_range: Range<usize>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LineBreakStyle {
     LF,
     CRLF,
@@ -27,15 +54,39 @@ pub enum LineBreakStyle {
 }
 
 impl SegmentedMessage {
-    pub fn new(message: &str, encoding: Encoding, smart_encoding: bool) -> Result<Self, String> {
-        let message = if smart_encoding {
-            message
-                .chars()
-                .map(|c| *SMART_ENCODING_MAP.get(&c).unwrap_or(&c))
-                .collect::<String>()
-        } else {
-            message.to_string()
-        };
+    pub fn new(message: &str, encoding: Encoding, smart_encoding: bool) -> Result<Self, Error> {
+        Self::with_options(
+            message,
+            &SegmentationOptions::new()
+                .encoding(encoding)
+                .smart_encoding(smart_encoding),
+        )
+    }
+
+    /// Segments a message allowing the national language shift tables of the
+    /// given languages. GSM-7 messages use the table combination that needs
+    /// the fewest segments, then the fewest bits.
+    pub fn with_national_languages(
+        message: &str,
+        encoding: Encoding,
+        smart_encoding: bool,
+        national_languages: &[NationalLanguage],
+    ) -> Result<Self, Error> {
+        Self::with_options(
+            message,
+            &SegmentationOptions::new()
+                .encoding(encoding)
+                .smart_encoding(smart_encoding)
+                .national_languages(national_languages),
+        )
+    }
+
+    pub fn with_options(message: &str, options: &SegmentationOptions) -> Result<Self, Error> {
+        Self::validate_options(options)?;
+
+        let encoding = options.encoding;
+        let original_message = message.to_string();
+        let (message, original_char_indices) = Self::prepare_message(message, options);
 
         let graphemes = message
             .graphemes(true)
@@ -50,20 +101,31 @@ impl SegmentedMessage {
 
         let number_of_unicode_scalars = message.chars().count();
 
-        let encoding_name = if encoding == Encoding::Auto {
-            if Self::has_any_ucs_characters(&graphemes) {
-                Encoding::UCS2
-            } else {
-                Encoding::GSM7
-            }
+        let gsm7_language_shift = if encoding == Encoding::UCS2 {
+            None
         } else {
-            if encoding == Encoding::GSM7 && Self::has_any_ucs_characters(&graphemes) {
-                return Err("The string provided is incompatible with GSM-7 encoding".to_string());
+            Self::select_language_shift(&graphemes, options)
+        };
+
+        let (encoding_name, language_shift) = match (encoding, gsm7_language_shift) {
+            (Encoding::UCS2, _) | (Encoding::Auto, None) => {
+                (Encoding::UCS2, LanguageShift::default())
+            }
+            (_, Some(language_shift)) => (Encoding::GSM7, language_shift),
+            (_, None) => {
+                return Err(Error::IncompatibleCharacters {
+                    encoding: Encoding::GSM7,
+                    characters: Self::incompatible_characters(
+                        &original_message,
+                        &original_char_indices,
+                        &graphemes,
+                        options,
+                    ),
+                });
             }
-            encoding
         };
 
-        let encoded_chars = Self::encode_chars(&graphemes, encoding_name);
+        let encoded_chars = Self::encode_chars(&graphemes, encoding_name, language_shift);
 
         let number_of_characters = if encoding_name == Encoding::UCS2 {
             graphemes.len()
@@ -71,38 +133,217 @@ impl SegmentedMessage {
             Self::count_code_units(&encoded_chars)
         };
 
-        let segments = Self::build_segments(&encoded_chars);
+        let units = Self::split_into_units(
+            &encoded_chars,
+            options.atomicity,
+            encoding_name,
+            language_shift,
+        );
+        let mut segments = Self::build_segments(&units, language_shift, options);
+        Self::number_segments(
+            &mut segments,
+            options
+                .concatenation_reference
+                .unwrap_or_else(next_concatenation_reference),
+        );
 
         let line_break_style = Self::detect_line_break_style(&message);
 
-        let warnings = Self::check_for_warnings(&line_break_style);
+        let ucs2_characters = if encoding == Encoding::Auto && encoding_name == Encoding::UCS2 {
+            Self::incompatible_characters(
+                &original_message,
+                &original_char_indices,
+                &graphemes,
+                options,
+            )
+        } else {
+            Vec::new()
+        };
 
-        Ok(SegmentedMessage {
+        let mut segmented_message = SegmentedMessage {
+            original_message,
             encoding,
             segments,
             graphemes,
             encoding_name,
+            language_shift,
+            options: options.clone(),
             number_of_unicode_scalars,
             number_of_characters,
             encoded_chars,
             line_break_style,
-            warnings,
-        })
+            warnings: Vec::new(),
+            original_char_indices,
+        };
+        segmented_message.warnings = segmented_message.check_for_warnings(ucs2_characters);
+
+        Ok(segmented_message)
+    }
+
+    pub(crate) fn validate_options(options: &SegmentationOptions) -> Result<(), Error> {
+        // Room for the largest header and a UCS-2 code unit
+        let min_capacity = SegmentCapacity::from_bits(MAX_HEADER_OCTETS * 8 + 16);
+        if !(min_capacity..=SegmentCapacity::MAX).contains(&options.segment_capacity) {
+            return Err(Error::InvalidOption(format!(
+                "Segment capacity must be between {} and {}, got {}",
+                min_capacity,
+                SegmentCapacity::MAX,
+                options.segment_capacity
+            )));
+        }
+        options.carrier_profile.validate()
+    }
+
+    // Applies line break normalization and smart encoding, keeping track of
+    // where every char of the result comes from
+    fn prepare_message(message: &str, options: &SegmentationOptions) -> (String, Vec<usize>) {
+        let chars = message.chars().collect::<Vec<char>>();
+        let mut prepared = String::new();
+        let mut original_char_indices = Vec::new();
+        let mut carriage_return = None;
+
+        for (index, &c) in chars.iter().enumerate() {
+            match (options.line_break_normalization, c) {
+                (LineBreakNormalization::Lf, '\r') if chars.get(index + 1) == Some(&'\n') => {
+                    // The LF stands for the whole CRLF
+                    carriage_return = Some(index);
+                    continue;
+                }
+                (LineBreakNormalization::CrLf, '\n') if index == 0 || chars[index - 1] != '\r' => {
+                    prepared.push('\r');
+                    original_char_indices.push(index);
+                }
+                _ => {}
+            }
+
+            // Every char of a multi-char replacement comes from the replaced char
+            let original_index = carriage_return.take().unwrap_or(index);
+            match options.smart_encoding_map.get(c) {
+                Some(replacement) if options.smart_encoding => {
+                    for replacement_char in replacement.chars() {
+                        prepared.push(replacement_char);
+                        original_char_indices.push(original_index);
+                    }
+                }
+                _ => {
+                    prepared.push(c);
+                    original_char_indices.push(original_index);
+                }
+            }
+        }
+        original_char_indices.push(chars.len());
+
+        (prepared, original_char_indices)
+    }
+
+    // Char and byte ranges of the original message covered by a range of
+    // chars of the encoded message
+    fn original_ranges(&self, char_range: Range<usize>) -> (Range<usize>, Range<usize>) {
+        let byte_offsets = Self::byte_offsets(&self.original_message);
+        let start = self.original_char_indices[char_range.start];
+        let end = self.original_char_indices[char_range.end];
+        (start..end, byte_offsets[start]..byte_offsets[end])
     }
 
-    fn has_any_ucs_characters(graphemes: &[String]) -> bool {
-        // UTF-8 uses 3 bytes to encode '€', which fails the length check
-        // That is the only GSM-7 character encoded with more than 2 byte
-        // so we provide a carve out for it
+    // Graphemes none of the table combinations can encode, or if every one
+    // of them can be encoded on its own, those outside the default alphabet
+    fn incompatible_characters(
+        original_message: &str,
+        original_char_indices: &[usize],
+        graphemes: &[String],
+        options: &SegmentationOptions,
+    ) -> Vec<IncompatibleCharacter> {
+        let candidates = LanguageShift::candidates(&options.national_languages);
+        let profile = &options.carrier_profile;
+        let byte_offsets = Self::byte_offsets(original_message);
+        let find = |is_incompatible: &dyn Fn(&String) -> bool| {
+            let mut char_index = 0;
+            let mut characters = Vec::new();
+
+            for grapheme in graphemes {
+                let char_end = char_index + grapheme.chars().count();
+                if is_incompatible(grapheme) {
+                    let start = original_char_indices[char_index];
+                    let end = original_char_indices[char_end];
+                    characters.push(IncompatibleCharacter {
+                        grapheme: grapheme.clone(),
+                        char_index: start,
+                        byte_range: byte_offsets[start]..byte_offsets[end],
+                    });
+                }
+                char_index = char_end;
+            }
+            characters
+        };
+
+        let characters =
+            find(&|g| profile.rejects(g) || !candidates.iter().any(|c| c.can_encode(g)));
+        if characters.is_empty() {
+            find(&|g| profile.rejects(g) || !LanguageShift::default().can_encode(g))
+        } else {
+            characters
+        }
+    }
+
+    // Byte offset of every char of the message, followed by its length
+    fn byte_offsets(message: &str) -> Vec<usize> {
+        message
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(message.len()))
+            .collect()
+    }
+
+    fn has_any_ucs_characters(
+        graphemes: &[String],
+        language_shift: LanguageShift,
+        carrier_profile: &CarrierProfile,
+    ) -> bool {
         graphemes
             .iter()
-            .any(|g| g.encode_utf16().count() > 1 || !g.chars().all(is_gsm7_character))
+            .any(|g| carrier_profile.rejects(g) || !language_shift.can_encode(g))
+    }
+
+    fn select_language_shift(
+        graphemes: &[String],
+        options: &SegmentationOptions,
+    ) -> Option<LanguageShift> {
+        let mut best: Option<(LanguageShift, (usize, u32))> = None;
+
+        for candidate in LanguageShift::candidates(&options.national_languages) {
+            if Self::has_any_ucs_characters(graphemes, candidate, &options.carrier_profile) {
+                continue;
+            }
+
+            let encoded_chars = Self::encode_chars(graphemes, Encoding::GSM7, candidate);
+            let units = Self::split_into_units(
+                &encoded_chars,
+                options.atomicity,
+                Encoding::GSM7,
+                candidate,
+            );
+            let segments = Self::build_segments(&units, candidate, options);
+            let cost = (
+                segments.len(),
+                segments.iter().map(|s| s.size_in_bits()).sum::<u32>(),
+            );
+
+            if best.is_none_or(|(_, best_cost)| cost < best_cost) {
+                best = Some((candidate, cost));
+            }
+        }
+
+        best.map(|(language_shift, _)| language_shift)
     }
 
-    fn encode_chars(graphemes: &[String], encoding: Encoding) -> Vec<EncodedChar> {
+    fn encode_chars(
+        graphemes: &[String],
+        encoding: Encoding,
+        language_shift: LanguageShift,
+    ) -> Vec<EncodedChar> {
         graphemes
             .iter()
-            .map(|g| EncodedChar::new(g, encoding))
+            .map(|g| EncodedChar::with_language_shift(g, encoding, language_shift))
             .collect()
     }
 
@@ -110,17 +351,67 @@ impl SegmentedMessage {
         encoded_chars.iter().map(|ec| ec.code_units.len()).sum()
     }
 
-    fn build_segments(encoded_chars: &[EncodedChar]) -> Vec<Segment> {
-        let mut segments = vec![Segment::new(false)];
+    // Splits code points out of grapheme clusters when they don't need to be
+    // kept whole. Each code point keeps its escape sequence or surrogate pair.
+    fn split_into_units(
+        encoded_chars: &[EncodedChar],
+        atomicity: Atomicity,
+        encoding: Encoding,
+        language_shift: LanguageShift,
+    ) -> Vec<EncodedChar> {
+        match atomicity {
+            Atomicity::GraphemeCluster => encoded_chars.to_vec(),
+            Atomicity::CodePoint => encoded_chars
+                .iter()
+                .flat_map(|encoded_char| {
+                    if encoded_char.raw.chars().count() == 1 {
+                        vec![encoded_char.clone()]
+                    } else {
+                        encoded_char
+                            .raw
+                            .chars()
+                            .map(|c| {
+                                EncodedChar::with_language_shift(
+                                    &c.to_string(),
+                                    encoding,
+                                    language_shift,
+                                )
+                            })
+                            .collect()
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    // Segments are filled with whole units, so a unit's code units always
+    // end up in the same segment
+    fn build_segments(
+        encoded_chars: &[EncodedChar],
+        language_shift: LanguageShift,
+        options: &SegmentationOptions,
+    ) -> Vec<Segment> {
+        let new_segment = |has_user_data_header| {
+            Segment::with_header(
+                has_user_data_header,
+                language_shift,
+                options.reference_width,
+            )
+            .with_capacity(options.segment_capacity)
+        };
+        let break_points = options.split_strategy.break_points(encoded_chars);
+        let mut segments = vec![new_segment(false)];
         let mut current_segment = 0;
+        // Index of the first character of the current segment
+        let mut segment_start = 0;
 
-        for encoded_char in encoded_chars {
+        for (index, encoded_char) in encoded_chars.iter().enumerate() {
             let char_size = encoded_char.size_in_bits();
             let free_size = segments[current_segment].free_size_in_bits();
 
-            if free_size < char_size as i16 {
+            if free_size < char_size as i32 {
                 // Start a new segment
-                segments.push(Segment::new(true));
+                segments.push(new_segment(true));
                 current_segment += 1;
 
                 // Add header to the previous segment if it doesn't have one
@@ -130,6 +421,16 @@ impl SegmentedMessage {
                     segments[current_segment]
                         .extend(removed_chars.into_iter().map(|c| c.clone_box()));
                 }
+
+                let split_index = index - Self::message_chars(&segments[current_segment]);
+                if let Some(break_index) = break_points.find(split_index, segment_start + 1) {
+                    Self::move_to_next_segment(
+                        &mut segments[current_segment - 1..],
+                        split_index - break_index,
+                        char_size,
+                    );
+                }
+                segment_start = index - Self::message_chars(&segments[current_segment]);
             }
 
             segments[current_segment].push(Box::new(encoded_char.clone()));
@@ -141,9 +442,222 @@ impl SegmentedMessage {
             segments[last_index].add_header();
         }
 
+        for segment in &mut segments {
+            segment.has_twilio_reserved_bits =
+                segment.has_user_data_header && options.carrier_profile.twilio_reserved_bits;
+        }
+
         segments
     }
 
+    fn message_chars(segment: &Segment) -> usize {
+        segment
+            .data
+            .iter()
+            .filter(|c| !c.is_user_data_header())
+            .count()
+    }
+
+    // Moves the last `count` characters of the first segment to the start of
+    // the second one, if they fit along with a character of `reserved_size`
+    fn move_to_next_segment(segments: &mut [Segment], count: usize, reserved_size: u16) {
+        if count == 0 {
+            return;
+        }
+
+        let (previous, next) = segments.split_at_mut(1);
+        let (previous, next) = (&mut previous[0], &mut next[0]);
+        let moved_size = previous.data[previous.data.len() - count..]
+            .iter()
+            .map(|c| c.size_in_bits())
+            .sum::<u16>();
+        if next.free_size_in_bits() < (moved_size + reserved_size) as i32 {
+            return;
+        }
+
+        let moved_chars = previous.data.split_off(previous.data.len() - count);
+        let header_octets = next.header_octets();
+        next.data.splice(header_octets..header_octets, moved_chars);
+    }
+
+    fn number_segments(segments: &mut [Segment], reference: u16) {
+        if segments.len() < 2 {
+            return;
+        }
+
+        // Headers count at most 255 segments, longer messages only get sized
+        let total = u8::try_from(segments.len()).unwrap_or(u8::MAX);
+        for (index, segment) in segments.iter_mut().enumerate() {
+            let sequence = u8::try_from(index + 1).unwrap_or(u8::MAX);
+            segment.set_concatenation(reference, total, sequence);
+        }
+    }
+
+    /// Rebuilds the segments with the given concatenation reference width.
+    /// A 16-bit reference takes one more header octet in every segment.
+    pub fn with_reference_width(mut self, reference_width: ReferenceWidth) -> Self {
+        self.options.reference_width = reference_width;
+        self.rebuild_segments();
+        self
+    }
+
+    /// Rebuilds the segments so they end on word or sentence boundaries
+    /// where possible. `split_overhead_in_bits` reports what that costs.
+    pub fn with_split_strategy(mut self, split_strategy: SplitStrategy) -> Self {
+        self.options.split_strategy = split_strategy;
+        self.rebuild_segments();
+        self
+    }
+
+    /// Rebuilds the segments allowing splits inside grapheme clusters, or
+    /// keeping them whole again
+    pub fn with_atomicity(mut self, atomicity: Atomicity) -> Self {
+        self.options.atomicity = atomicity;
+        self.rebuild_segments();
+        self
+    }
+
+    fn rebuild_segments(&mut self) {
+        let reference = self
+            .concatenation_reference()
+            .unwrap_or_else(next_concatenation_reference);
+
+        self.segments = Self::build_segments(
+            &self.segmentation_units(),
+            self.language_shift,
+            &self.options,
+        );
+        Self::number_segments(&mut self.segments, reference);
+
+        self.warnings.retain(|w| {
+            !matches!(
+                w,
+                Warning::NearSegmentBoundary { .. } | Warning::TooManySegments { .. }
+            )
+        });
+        self.warnings.extend(self.near_segment_boundary());
+        self.warnings.extend(self.too_many_segments());
+    }
+
+    fn segmentation_units(&self) -> Vec<EncodedChar> {
+        Self::split_into_units(
+            &self.encoded_chars,
+            self.options.atomicity,
+            self.encoding_name,
+            self.language_shift,
+        )
+    }
+
+    /// Extra bits sent because of the split strategy, compared with splitting
+    /// at the first character that doesn't fit: the space left unused at the
+    /// end of segments plus the header of any segment it adds
+    pub fn split_overhead_in_bits(&self) -> u32 {
+        let character_split = Self::build_segments(
+            &self.segmentation_units(),
+            self.language_shift,
+            &self
+                .options
+                .clone()
+                .split_strategy(SplitStrategy::Character),
+        );
+
+        Self::occupied_bits(&self.segments).saturating_sub(Self::occupied_bits(&character_split))
+    }
+
+    // Every segment but the last one takes its full capacity
+    fn occupied_bits(segments: &[Segment]) -> u32 {
+        let last_index = segments.len().saturating_sub(1);
+        segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                if index < last_index {
+                    segment.capacity.bits()
+                } else {
+                    segment.size_in_bits()
+                }
+            })
+            .sum()
+    }
+
+    /// Uses the given reference number in the concatenation headers
+    pub fn with_concatenation_reference(mut self, reference: u16) -> Self {
+        self.options.concatenation_reference = Some(reference);
+        Self::number_segments(&mut self.segments, reference);
+        self
+    }
+
+    /// Message as encoded, after line break normalization and smart encoding
+    pub fn transformed_message(&self) -> String {
+        self.graphemes.concat()
+    }
+
+    /// Characters smart encoding replaced, in message order
+    pub fn substitutions(&self) -> Vec<AppliedSubstitution> {
+        if !self.options.smart_encoding {
+            return Vec::new();
+        }
+
+        let byte_offsets = Self::byte_offsets(&self.original_message);
+        let mut substitutions = Vec::new();
+        let mut transformed_index = 0;
+
+        for (index, original) in self.original_message.chars().enumerate() {
+            // The final entry is past every original char, so both loops end
+            while self.original_char_indices[transformed_index] < index {
+                transformed_index += 1;
+            }
+            let start = transformed_index;
+            let mut end = start;
+            while self.original_char_indices[end] == index {
+                end += 1;
+            }
+
+            match self.options.smart_encoding_map.get(original) {
+                Some(replacement) if replacement.chars().ne([original]) => {
+                    substitutions.push(AppliedSubstitution {
+                        original,
+                        replacement: replacement.to_string(),
+                        span: Span {
+                            char_range: index..index + 1,
+                            byte_range: byte_offsets[index]..byte_offsets[index + 1],
+                        },
+                        transformed_char_range: start..end,
+                    });
+                }
+                _ => {}
+            }
+        }
+        substitutions
+    }
+
+    /// Substitutions with the original and transformed text, and the
+    /// segments the message would need without them
+    pub fn substitution_report(&self) -> SubstitutionReport {
+        // Keeps the reference so no new one is drawn for the comparison
+        let mut options = self.options.clone().smart_encoding(false);
+        options.concatenation_reference = Some(self.concatenation_reference().unwrap_or(0));
+        let original = Self::with_options(&self.original_message, &options).ok();
+
+        SubstitutionReport {
+            original_message: self.original_message.clone(),
+            transformed_message: self.transformed_message(),
+            substitutions: self.substitutions(),
+            encoding: self.encoding_name,
+            segments: self.segments_count(),
+            original_encoding: original.as_ref().map(|message| message.encoding_name),
+            original_segments: original.as_ref().map(|message| message.segments_count()),
+        }
+    }
+
+    pub fn concatenation_reference(&self) -> Option<u16> {
+        self.segments
+            .first()?
+            .user_data_header
+            .concatenation()
+            .map(|c| c.reference)
+    }
+
     fn detect_line_break_style(message: &str) -> Option<LineBreakStyle> {
         let has_windows_style = message.contains("\r\n");
         let has_unix_style = message.contains('\n');
@@ -156,27 +670,117 @@ impl SegmentedMessage {
         }
     }
 
-    fn check_for_warnings(line_break_style: &Option<LineBreakStyle>) -> Vec<String> {
+    // Spans refer to the original message. Line breaks are reported as they
+    // are sent, after normalization.
+    fn check_for_warnings(&self, ucs2_characters: Vec<IncompatibleCharacter>) -> Vec<Warning> {
+        let byte_offsets = Self::byte_offsets(&self.original_message);
+        let span = |char_range: Range<usize>| Span {
+            byte_range: byte_offsets[char_range.start]..byte_offsets[char_range.end],
+            char_range,
+        };
+        let encoded_span = |char_range: Range<usize>| {
+            let (char_range, byte_range) = self.original_ranges(char_range);
+            Span {
+                char_range,
+                byte_range,
+            }
+        };
+        let original_chars = self.original_message.chars().collect::<Vec<char>>();
+        let encoded_chars = self.graphemes.concat().chars().collect::<Vec<char>>();
         let mut warnings = Vec::new();
-        if line_break_style.is_some() {
-            warnings.push(
-                "The message has line breaks, the web page utility only supports LF style. \
-                If you insert a CRLF it will be converted to LF."
-                    .to_string(),
-            );
+
+        for (index, &c) in encoded_chars.iter().enumerate() {
+            if c == '\n' {
+                let (style, start) = match index.checked_sub(1).map(|i| encoded_chars[i]) {
+                    Some('\r') => (LineBreakStyle::CRLF, index - 1),
+                    _ => (LineBreakStyle::LF, index),
+                };
+                warnings.push(Warning::LineBreak {
+                    style,
+                    span: encoded_span(start..index + 1),
+                });
+            }
+        }
+
+        warnings.extend(ucs2_characters.into_iter().map(|c| Warning::ForcesUcs2 {
+            span: span(c.char_index..c.char_index + c.grapheme.chars().count()),
+            grapheme: c.grapheme,
+        }));
+
+        warnings.extend(self.substitutions().into_iter().map(|substitution| {
+            Warning::SmartEncodingReplacement {
+                original: substitution.original,
+                replacement: substitution.replacement,
+                span: substitution.span,
+            }
+        }));
+
+        for (index, &c) in original_chars.iter().enumerate() {
+            if INVISIBLE_CHARACTERS.contains(&c) {
+                warnings.push(Warning::InvisibleCharacter {
+                    character: c,
+                    span: span(index..index + 1),
+                });
+            }
+        }
+
+        // Runs of whitespace followed by a line break or the end of the message
+        let is_trailing_whitespace = |c: char| {
+            c.is_whitespace() && c != '\r' && c != '\n' && !INVISIBLE_CHARACTERS.contains(&c)
+        };
+        let mut run_start = None;
+        for index in 0..=original_chars.len() {
+            match original_chars.get(index) {
+                Some(&c) if is_trailing_whitespace(c) => {
+                    run_start.get_or_insert(index);
+                }
+                None | Some('\r') | Some('\n') => {
+                    if let Some(start) = run_start.take() {
+                        warnings.push(Warning::TrailingWhitespace {
+                            span: span(start..index),
+                        });
+                    }
+                }
+                Some(_) => run_start = None,
+            }
         }
+
+        warnings.extend(self.near_segment_boundary());
+        warnings.extend(self.too_many_segments());
         warnings
     }
 
+    fn too_many_segments(&self) -> Option<Warning> {
+        let max_segments = self.options.carrier_profile.max_segments;
+        (self.segments_count() > max_segments).then_some(Warning::TooManySegments {
+            segments: self.segments_count(),
+            max_segments,
+        })
+    }
+
+    fn near_segment_boundary(&self) -> Option<Warning> {
+        let code_unit_size = match self.encoding_name {
+            Encoding::GSM7 => 7,
+            _ => 16,
+        };
+        let last_segment = self.segments.last()?;
+
+        (last_segment.message_size_in_bits() > 0
+            && last_segment.free_size_in_bits() < code_unit_size)
+            .then_some(Warning::NearSegmentBoundary {
+                segments: self.segments_count(),
+            })
+    }
+
     pub fn get_encoding_name(&self) -> Encoding {
         self.encoding_name
     }
 
-    pub fn total_size(&self) -> u16 {
+    pub fn total_size(&self) -> u32 {
         self.segments.iter().map(|s| s.size_in_bits()).sum()
     }
 
-    pub fn message_size(&self) -> u16 {
+    pub fn message_size(&self) -> u32 {
         self.segments.iter().map(|s| s.message_size_in_bits()).sum()
     }
 
@@ -184,6 +788,88 @@ impl SegmentedMessage {
         self.segments.len()
     }
 
+    /// Fails with `Error::MessageTooLong` when the message needs more
+    /// segments than the carrier profile concatenates
+    pub fn check_segment_limit(&self) -> Result<(), Error> {
+        let max_segments = self.options.carrier_profile.max_segments;
+        if self.segments_count() > max_segments {
+            return Err(Error::MessageTooLong {
+                segments: self.segments_count(),
+                max_segments,
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether the carrier profile delivers the message as SMS or converts
+    /// it to MMS
+    pub fn delivery(&self) -> Delivery {
+        self.options.carrier_profile.delivery(self.segments_count())
+    }
+
+    /// Text of each segment and the part of the original message it covers.
+    /// Ranges of a normalized or smart encoded message still refer to the
+    /// original.
+    pub fn segment_texts(&self) -> Vec<SegmentText> {
+        let mut char_start = 0;
+
+        self.segments
+            .iter()
+            .map(|segment| {
+                let text = segment
+                    .data
+                    .iter()
+                    .filter(|c| !c.is_user_data_header())
+                    .map(|c| c.raw())
+                    .collect::<String>();
+                let char_end = char_start + text.chars().count();
+                let (char_range, byte_range) = self.original_ranges(char_start..char_end);
+                let segment_text = SegmentText {
+                    char_range,
+                    byte_range,
+                    text,
+                };
+                char_start = char_end;
+                segment_text
+            })
+            .collect()
+    }
+
+    /// Checks the segments with `validate_segmentation`
+    pub fn validate(&self) -> Result<(), Error> {
+        let parts = self
+            .segments
+            .iter()
+            .map(|segment| match self.encoding_name {
+                Encoding::GSM7 => segment.septets().into_iter().map(u16::from).collect(),
+                _ => segment
+                    .data
+                    .iter()
+                    .filter(|c| !c.is_user_data_header())
+                    .flat_map(|c| c.raw().encode_utf16().collect::<Vec<u16>>())
+                    .collect(),
+            })
+            .collect::<Vec<Vec<u16>>>();
+
+        validate_segmentation(
+            &parts,
+            self.encoding_name,
+            self.language_shift,
+            self.options.atomicity,
+        )
+    }
+
+    /// Packed GSM 7-bit user data for each segment
+    pub fn packed_user_data(&self) -> Result<Vec<Vec<u8>>, Error> {
+        if self.encoding_name != Encoding::GSM7 {
+            return Err(Error::InvalidOption(
+                "Only GSM-7 encoded messages can be packed into septets".to_string(),
+            ));
+        }
+
+        Ok(self.segments.iter().map(|s| s.packed_user_data()).collect())
+    }
+
     pub fn get_non_gsm_characters(&self) -> HashSet<String> {
         self.encoded_chars
             .iter()
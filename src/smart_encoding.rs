@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+use crate::encoded_char::Encoding;
+use crate::error::Error;
+use crate::utils::smart_encoding_map::DEFAULT_SUBSTITUTIONS;
+use crate::warning::Span;
+
+/// Kind of characters a substitution flattens, so groups of substitutions
+/// can be turned on or off together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubstitutionCategory {
+    /// Curly, angle and prime quotation marks
+    Quotes,
+    /// Em and en dashes, hyphens and horizontal bars
+    Dashes,
+    /// Non-breaking, typographic and zero width spaces
+    Spaces,
+    /// Vulgar fractions and the fraction slash
+    Fractions,
+    Other,
+}
+
+/// Replacement of one character, by any number of characters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Substitution {
+    pub replacement: String,
+    pub category: SubstitutionCategory,
+}
+
+/// Smart Encoding Map Class
+///
+/// Characters replaced with GSM-7 lookalikes when smart encoding is on.
+/// Entries can be added, overridden or removed, and whole categories
+/// disabled, in code or from a TOML or JSON file.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartEncodingMap {
+    substitutions: BTreeMap<char, Substitution>,
+    disabled_categories: BTreeSet<SubstitutionCategory>,
+}
+
+impl Default for SmartEncodingMap {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+/// File layout of a map. Substitutions extend or override the built-in ones
+/// unless `extends_default` is false.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SmartEncodingMapFile {
+    extends_default: bool,
+    disabled_categories: Vec<SubstitutionCategory>,
+    removed: Vec<char>,
+    substitutions: Vec<SubstitutionEntry>,
+}
+
+impl Default for SmartEncodingMapFile {
+    fn default() -> Self {
+        SmartEncodingMapFile {
+            extends_default: true,
+            disabled_categories: Vec::new(),
+            removed: Vec::new(),
+            substitutions: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SubstitutionEntry {
+    original: char,
+    replacement: String,
+    #[serde(default = "other_category")]
+    category: SubstitutionCategory,
+}
+
+fn other_category() -> SubstitutionCategory {
+    SubstitutionCategory::Other
+}
+
+impl From<SmartEncodingMapFile> for SmartEncodingMap {
+    fn from(file: SmartEncodingMapFile) -> Self {
+        let mut map = if file.extends_default {
+            Self::built_in()
+        } else {
+            Self::empty()
+        };
+        for original in file.removed {
+            map.remove(original);
+        }
+        for entry in file.substitutions {
+            map.insert(entry.original, &entry.replacement, entry.category);
+        }
+        for category in file.disabled_categories {
+            map.disable_category(category);
+        }
+        map
+    }
+}
+
+impl SmartEncodingMap {
+    /// Map without substitutions
+    pub fn empty() -> Self {
+        SmartEncodingMap {
+            substitutions: BTreeMap::new(),
+            disabled_categories: BTreeSet::new(),
+        }
+    }
+
+    /// Substitutions applied by default
+    pub fn built_in() -> Self {
+        let mut map = Self::empty();
+        for &(original, replacement, category) in DEFAULT_SUBSTITUTIONS {
+            map.insert(original, replacement, category);
+        }
+        map
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        toml::from_str::<SmartEncodingMapFile>(toml)
+            .map(Self::from)
+            .map_err(|e| Error::Decode(format!("Invalid smart encoding map: {}", e)))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str::<SmartEncodingMapFile>(json)
+            .map(Self::from)
+            .map_err(|e| Error::Decode(format!("Invalid smart encoding map: {}", e)))
+    }
+
+    /// Adds a substitution, replacing any existing one for the character
+    pub fn insert(&mut self, original: char, replacement: &str, category: SubstitutionCategory) {
+        self.substitutions.insert(
+            original,
+            Substitution {
+                replacement: replacement.to_string(),
+                category,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, original: char) -> Option<Substitution> {
+        self.substitutions.remove(&original)
+    }
+
+    pub fn with_substitution(
+        mut self,
+        original: char,
+        replacement: &str,
+        category: SubstitutionCategory,
+    ) -> Self {
+        self.insert(original, replacement, category);
+        self
+    }
+
+    pub fn without_substitution(mut self, original: char) -> Self {
+        self.remove(original);
+        self
+    }
+
+    pub fn enable_category(&mut self, category: SubstitutionCategory) {
+        self.disabled_categories.remove(&category);
+    }
+
+    pub fn disable_category(&mut self, category: SubstitutionCategory) {
+        self.disabled_categories.insert(category);
+    }
+
+    pub fn with_category(mut self, category: SubstitutionCategory, enabled: bool) -> Self {
+        if enabled {
+            self.enable_category(category);
+        } else {
+            self.disable_category(category);
+        }
+        self
+    }
+
+    pub fn is_category_enabled(&self, category: SubstitutionCategory) -> bool {
+        !self.disabled_categories.contains(&category)
+    }
+
+    /// Substitution for a character, whether or not its category is enabled
+    pub fn substitution(&self, original: char) -> Option<&Substitution> {
+        self.substitutions.get(&original)
+    }
+
+    /// Replacement of a character, if it has one in an enabled category
+    pub fn get(&self, original: char) -> Option<&str> {
+        self.substitution(original)
+            .filter(|substitution| self.is_category_enabled(substitution.category))
+            .map(|substitution| substitution.replacement.as_str())
+    }
+
+    /// Text after substitution of every character
+    pub fn apply(&self, text: &str) -> String {
+        let mut applied = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.get(c) {
+                Some(replacement) => applied.push_str(replacement),
+                None => applied.push(c),
+            }
+        }
+        applied
+    }
+
+    /// Enabled substitutions, in character order
+    pub fn substitutions(&self) -> impl Iterator<Item = (char, &Substitution)> + '_ {
+        self.substitutions
+            .iter()
+            .filter(|(_, substitution)| self.is_category_enabled(substitution.category))
+            .map(|(&original, substitution)| (original, substitution))
+    }
+}
+
+/// A substitution smart encoding made in a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedSubstitution {
+    pub original: char,
+    pub replacement: String,
+    /// Position of the replaced character in the original message
+    pub span: Span,
+    /// Chars of the transformed message the replacement occupies
+    pub transformed_char_range: Range<usize>,
+}
+
+/// Substitution Report Class
+///
+/// What smart encoding changed in a message and the segments it saved, for
+/// review before the message is sent
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubstitutionReport {
+    pub original_message: String,
+    pub transformed_message: String,
+    pub substitutions: Vec<AppliedSubstitution>,
+    pub encoding: Encoding,
+    pub segments: usize,
+    /// Encoding of the message without substitutions, absent when it can't
+    /// be sent with the requested options
+    pub original_encoding: Option<Encoding>,
+    pub original_segments: Option<usize>,
+}
+
+impl SubstitutionReport {
+    /// Segments the substitutions saved, negative if longer replacements
+    /// cost segments
+    pub fn saved_segments(&self) -> Option<isize> {
+        self.original_segments
+            .map(|original_segments| original_segments as isize - self.segments as isize)
+    }
+}
@@ -0,0 +1,476 @@
+use crate::encoded_char::Encoding;
+use crate::error::Error;
+use crate::national_language::LanguageShift;
+use crate::packing::{decode_septets, decode_ucs2};
+use crate::pdu::NumberType;
+use crate::reader::Reader;
+use crate::segment::Segment;
+use crate::segmented_message::SegmentedMessage;
+use crate::user_data_header::{UserDataHeader, CONCATENATION_16_BIT_IEI, CONCATENATION_8_BIT_IEI};
+
+pub const TAG_SAR_MSG_REF_NUM: u16 = 0x020c;
+pub const TAG_SAR_TOTAL_SEGMENTS: u16 = 0x020e;
+pub const TAG_SAR_SEGMENT_SEQNUM: u16 = 0x020f;
+pub const TAG_MESSAGE_PAYLOAD: u16 = 0x0424;
+
+const TON_UNKNOWN: u8 = 0x00;
+const TON_INTERNATIONAL: u8 = 0x01;
+const TON_ALPHANUMERIC: u8 = 0x05;
+const NPI_UNKNOWN: u8 = 0x00;
+const NPI_ISDN: u8 = 0x01;
+
+/// esm_class bit indicating that short_message starts with a User Data Header
+pub const ESM_CLASS_UDHI: u8 = 0x40;
+
+pub const DATA_CODING_DEFAULT: u8 = 0x00;
+pub const DATA_CODING_IA5: u8 = 0x01;
+pub const DATA_CODING_BINARY: u8 = 0x02;
+pub const DATA_CODING_LATIN1: u8 = 0x03;
+pub const DATA_CODING_UCS2: u8 = 0x08;
+
+const MAX_SHORT_MESSAGE_LENGTH: usize = 254;
+
+/// How the parts of a multi-segment message are tied together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConcatenationMethod {
+    /// One PDU per segment with the concatenation UDH in short_message
+    #[default]
+    UserDataHeader,
+    /// One PDU per segment with the sar_* TLVs, leaving the UDH to the SMSC
+    SarTlv,
+    /// A single PDU with the whole message in the message_payload TLV
+    MessagePayload,
+}
+
+/// Optional parameter of an SMPP PDU body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tlv {
+    pub tag: u16,
+    pub value: Vec<u8>,
+}
+
+impl Tlv {
+    pub fn new(tag: u16, value: Vec<u8>) -> Self {
+        Tlv { tag, value }
+    }
+
+    fn write(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
+        let length = u16::try_from(self.value.len()).map_err(|_| {
+            Error::Encode(format!(
+                "TLV 0x{:04x} has {} octets, at most {} fit",
+                self.tag,
+                self.value.len(),
+                u16::MAX
+            ))
+        })?;
+        bytes.extend(self.tag.to_be_bytes());
+        bytes.extend(length.to_be_bytes());
+        bytes.extend(&self.value);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmppAddress {
+    pub ton: u8,
+    pub npi: u8,
+    pub address: String,
+}
+
+impl SmppAddress {
+    /// Numbers starting with `+` are international, other numbers unknown and
+    /// anything else alphanumeric
+    pub fn new(address: &str) -> Self {
+        let (number_type, address) = NumberType::classify(address);
+        let (ton, npi) = match number_type {
+            NumberType::International => (TON_INTERNATIONAL, NPI_ISDN),
+            NumberType::Unknown => (TON_UNKNOWN, NPI_ISDN),
+            NumberType::Alphanumeric => (TON_ALPHANUMERIC, NPI_UNKNOWN),
+        };
+
+        SmppAddress {
+            ton,
+            npi,
+            address: address.to_string(),
+        }
+    }
+}
+
+fn read_c_octet_string(reader: &mut Reader, field: &str) -> Result<String, Error> {
+    let end = reader
+        .remaining()
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or_else(|| Error::Decode(format!("{} is not NULL terminated", field)))?;
+    let value = reader.take(end + 1, field)?;
+    String::from_utf8(value[..end].to_vec())
+        .map_err(|_| Error::Decode(format!("{} is not ASCII", field)))
+}
+
+fn read_address(reader: &mut Reader, field: &str) -> Result<SmppAddress, Error> {
+    Ok(SmppAddress {
+        ton: reader.octet(field)?,
+        npi: reader.octet(field)?,
+        address: read_c_octet_string(reader, field)?,
+    })
+}
+
+fn read_tlvs(reader: &mut Reader) -> Result<Vec<Tlv>, Error> {
+    let mut tlvs = Vec::new();
+    while !reader.is_at_end() {
+        let tag = reader.u16("TLV tag")?;
+        let length = reader.u16("TLV length")?;
+        let value = reader.take(length as usize, "TLV value")?.to_vec();
+        tlvs.push(Tlv::new(tag, value));
+    }
+    Ok(tlvs)
+}
+
+fn write_c_octet_string(bytes: &mut Vec<u8>, value: &str) {
+    bytes.extend(value.as_bytes());
+    bytes.push(0);
+}
+
+fn write_address(bytes: &mut Vec<u8>, address: &SmppAddress) {
+    bytes.push(address.ton);
+    bytes.push(address.npi);
+    write_c_octet_string(bytes, &address.address);
+}
+
+fn find_tlv(tlvs: &[Tlv], tag: u16) -> Option<&[u8]> {
+    tlvs.iter()
+        .find(|tlv| tlv.tag == tag)
+        .map(|tlv| tlv.value.as_slice())
+}
+
+fn data_coding(encoding: Encoding) -> Result<u8, Error> {
+    match encoding {
+        Encoding::GSM7 => Ok(DATA_CODING_DEFAULT),
+        Encoding::UCS2 => Ok(DATA_CODING_UCS2),
+        Encoding::Auto => Err(Error::InvalidOption(
+            "Encoding::Auto has no data_coding".to_string(),
+        )),
+    }
+}
+
+// Message octets of a segment without its header: unpacked septets for the
+// SMSC default alphabet, big endian code units for UCS-2
+fn segment_payload(segment: &Segment, encoding: Encoding) -> Vec<u8> {
+    match encoding {
+        Encoding::GSM7 => segment.septets(),
+        _ => segment.user_data(encoding)[segment.header_octets()..].to_vec(),
+    }
+}
+
+/// Message decoded from a submit_sm, deliver_sm or data_sm body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedMessage {
+    pub user_data_header: Option<UserDataHeader>,
+    /// Message octets after the header
+    pub payload: Vec<u8>,
+    /// Decoded text, absent for binary data
+    pub text: Option<String>,
+    /// sar_msg_ref_num, sar_total_segments and sar_segment_seqnum
+    pub sar: Option<(u16, u8, u8)>,
+}
+
+fn decode_message(
+    esm_class: u8,
+    data_coding: u8,
+    message: &[u8],
+    tlvs: &[Tlv],
+) -> Result<DecodedMessage, Error> {
+    let user_data_header = if esm_class & ESM_CLASS_UDHI != 0 {
+        Some(UserDataHeader::from_bytes(message)?)
+    } else {
+        None
+    };
+    let header_octets = user_data_header.as_ref().map_or(0, |h| h.len());
+    let payload = message[header_octets.min(message.len())..].to_vec();
+    let language_shift = user_data_header
+        .as_ref()
+        .map(|h| h.language_shift())
+        .unwrap_or_else(LanguageShift::default);
+
+    let text = match data_coding {
+        DATA_CODING_DEFAULT => Some(decode_septets(&payload, language_shift)?),
+        DATA_CODING_IA5 => Some(
+            String::from_utf8(payload.clone())
+                .map_err(|_| Error::Decode("Message is not IA5".to_string()))?,
+        ),
+        DATA_CODING_LATIN1 => Some(payload.iter().map(|&b| b as char).collect()),
+        DATA_CODING_UCS2 => Some(decode_ucs2(&payload)?),
+        _ => None,
+    };
+
+    let sar = match (
+        find_tlv(tlvs, TAG_SAR_MSG_REF_NUM),
+        find_tlv(tlvs, TAG_SAR_TOTAL_SEGMENTS),
+        find_tlv(tlvs, TAG_SAR_SEGMENT_SEQNUM),
+    ) {
+        (Some(&[high, low]), Some(&[total]), Some(&[sequence])) => {
+            Some((u16::from_be_bytes([high, low]), total, sequence))
+        }
+        _ => None,
+    };
+
+    Ok(DecodedMessage {
+        user_data_header,
+        payload,
+        text,
+        sar,
+    })
+}
+
+/// Body of a submit_sm or deliver_sm PDU, which share the same layout
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ShortMessageBody {
+    pub service_type: String,
+    pub source: SmppAddress,
+    pub destination: SmppAddress,
+    pub esm_class: u8,
+    pub protocol_id: u8,
+    pub priority_flag: u8,
+    pub schedule_delivery_time: String,
+    pub validity_period: String,
+    pub registered_delivery: u8,
+    pub replace_if_present_flag: u8,
+    pub data_coding: u8,
+    pub sm_default_msg_id: u8,
+    pub short_message: Vec<u8>,
+    pub tlvs: Vec<Tlv>,
+}
+
+pub type SubmitSm = ShortMessageBody;
+pub type DeliverSm = ShortMessageBody;
+
+impl ShortMessageBody {
+    /// Builds the submit_sm bodies for a message: one per segment, or a single
+    /// one carrying the message_payload TLV
+    pub fn from_segmented_message(
+        segmented_message: &SegmentedMessage,
+        source: &str,
+        destination: &str,
+        concatenation_method: ConcatenationMethod,
+    ) -> Result<Vec<Self>, Error> {
+        segmented_message.check_segment_limit()?;
+        let encoding = segmented_message.get_encoding_name();
+        let template = ShortMessageBody {
+            source: SmppAddress::new(source),
+            destination: SmppAddress::new(destination),
+            data_coding: data_coding(encoding)?,
+            ..Default::default()
+        };
+
+        if concatenation_method == ConcatenationMethod::MessagePayload {
+            let segments = &segmented_message.segments;
+            let mut header = segments[0].user_data_header.clone();
+            header
+                .information_elements
+                .retain(|e| header_keeps_element(e.identifier));
+
+            let mut message_payload = header.to_bytes();
+            for segment in segments {
+                message_payload.extend(segment_payload(segment, encoding));
+            }
+
+            return Ok(vec![ShortMessageBody {
+                esm_class: if header.is_empty() { 0 } else { ESM_CLASS_UDHI },
+                tlvs: vec![Tlv::new(TAG_MESSAGE_PAYLOAD, message_payload)],
+                ..template
+            }]);
+        }
+
+        let reference = segmented_message.concatenation_reference();
+        let total = segmented_message.segments_count() as u8;
+
+        Ok(segmented_message
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let mut header = segment.user_data_header.clone();
+                let mut tlvs = Vec::new();
+                if concatenation_method == ConcatenationMethod::SarTlv {
+                    header
+                        .information_elements
+                        .retain(|e| header_keeps_element(e.identifier));
+                    if let Some(reference) = reference {
+                        tlvs.push(Tlv::new(
+                            TAG_SAR_MSG_REF_NUM,
+                            reference.to_be_bytes().to_vec(),
+                        ));
+                        tlvs.push(Tlv::new(TAG_SAR_TOTAL_SEGMENTS, vec![total]));
+                        tlvs.push(Tlv::new(TAG_SAR_SEGMENT_SEQNUM, vec![index as u8 + 1]));
+                    }
+                }
+
+                let mut short_message = header.to_bytes();
+                short_message.extend(segment_payload(segment, encoding));
+
+                ShortMessageBody {
+                    esm_class: if header.is_empty() { 0 } else { ESM_CLASS_UDHI },
+                    short_message,
+                    tlvs,
+                    ..template.clone()
+                }
+            })
+            .collect())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        if self.short_message.len() > MAX_SHORT_MESSAGE_LENGTH {
+            return Err(Error::Encode(format!(
+                "short_message is {} octets, the maximum is {}",
+                self.short_message.len(),
+                MAX_SHORT_MESSAGE_LENGTH
+            )));
+        }
+
+        let mut bytes = Vec::new();
+        write_c_octet_string(&mut bytes, &self.service_type);
+        write_address(&mut bytes, &self.source);
+        write_address(&mut bytes, &self.destination);
+        bytes.push(self.esm_class);
+        bytes.push(self.protocol_id);
+        bytes.push(self.priority_flag);
+        write_c_octet_string(&mut bytes, &self.schedule_delivery_time);
+        write_c_octet_string(&mut bytes, &self.validity_period);
+        bytes.push(self.registered_delivery);
+        bytes.push(self.replace_if_present_flag);
+        bytes.push(self.data_coding);
+        bytes.push(self.sm_default_msg_id);
+        bytes.push(self.short_message.len() as u8);
+        bytes.extend(&self.short_message);
+        for tlv in &self.tlvs {
+            tlv.write(&mut bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+
+        let service_type = read_c_octet_string(&mut reader, "service_type")?;
+        let source = read_address(&mut reader, "source_addr")?;
+        let destination = read_address(&mut reader, "destination_addr")?;
+        let esm_class = reader.octet("esm_class")?;
+        let protocol_id = reader.octet("protocol_id")?;
+        let priority_flag = reader.octet("priority_flag")?;
+        let schedule_delivery_time = read_c_octet_string(&mut reader, "schedule_delivery_time")?;
+        let validity_period = read_c_octet_string(&mut reader, "validity_period")?;
+        let registered_delivery = reader.octet("registered_delivery")?;
+        let replace_if_present_flag = reader.octet("replace_if_present_flag")?;
+        let data_coding = reader.octet("data_coding")?;
+        let sm_default_msg_id = reader.octet("sm_default_msg_id")?;
+        let sm_length = reader.octet("sm_length")?;
+        let short_message = reader.take(sm_length as usize, "short_message")?.to_vec();
+        let tlvs = read_tlvs(&mut reader)?;
+
+        Ok(ShortMessageBody {
+            service_type,
+            source,
+            destination,
+            esm_class,
+            protocol_id,
+            priority_flag,
+            schedule_delivery_time,
+            validity_period,
+            registered_delivery,
+            replace_if_present_flag,
+            data_coding,
+            sm_default_msg_id,
+            short_message,
+            tlvs,
+        })
+    }
+
+    /// Decodes short_message, or the message_payload TLV when short_message is empty
+    pub fn decode_message(&self) -> Result<DecodedMessage, Error> {
+        let message = match find_tlv(&self.tlvs, TAG_MESSAGE_PAYLOAD) {
+            Some(payload) if self.short_message.is_empty() => payload,
+            _ => &self.short_message,
+        };
+        decode_message(self.esm_class, self.data_coding, message, &self.tlvs)
+    }
+}
+
+// Information elements that still belong in the UDH when the SMSC is told
+// about concatenation through TLVs
+fn header_keeps_element(identifier: u8) -> bool {
+    !matches!(
+        identifier,
+        CONCATENATION_8_BIT_IEI | CONCATENATION_16_BIT_IEI
+    )
+}
+
+/// Body of a data_sm PDU, which always carries the message in message_payload
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DataSm {
+    pub service_type: String,
+    pub source: SmppAddress,
+    pub destination: SmppAddress,
+    pub esm_class: u8,
+    pub registered_delivery: u8,
+    pub data_coding: u8,
+    pub tlvs: Vec<Tlv>,
+}
+
+impl DataSm {
+    pub fn from_segmented_message(
+        segmented_message: &SegmentedMessage,
+        source: &str,
+        destination: &str,
+    ) -> Result<Self, Error> {
+        let mut bodies = ShortMessageBody::from_segmented_message(
+            segmented_message,
+            source,
+            destination,
+            ConcatenationMethod::MessagePayload,
+        )?;
+        let body = bodies.remove(0);
+
+        Ok(DataSm {
+            service_type: body.service_type,
+            source: body.source,
+            destination: body.destination,
+            esm_class: body.esm_class,
+            registered_delivery: body.registered_delivery,
+            data_coding: body.data_coding,
+            tlvs: body.tlvs,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        write_c_octet_string(&mut bytes, &self.service_type);
+        write_address(&mut bytes, &self.source);
+        write_address(&mut bytes, &self.destination);
+        bytes.push(self.esm_class);
+        bytes.push(self.registered_delivery);
+        bytes.push(self.data_coding);
+        for tlv in &self.tlvs {
+            tlv.write(&mut bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+
+        Ok(DataSm {
+            service_type: read_c_octet_string(&mut reader, "service_type")?,
+            source: read_address(&mut reader, "source_addr")?,
+            destination: read_address(&mut reader, "destination_addr")?,
+            esm_class: reader.octet("esm_class")?,
+            registered_delivery: reader.octet("registered_delivery")?,
+            data_coding: reader.octet("data_coding")?,
+            tlvs: read_tlvs(&mut reader)?,
+        })
+    }
+
+    pub fn decode_message(&self) -> Result<DecodedMessage, Error> {
+        let message = find_tlv(&self.tlvs, TAG_MESSAGE_PAYLOAD).unwrap_or_default();
+        decode_message(self.esm_class, self.data_coding, message, &self.tlvs)
+    }
+}
@@ -0,0 +1,143 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::encoded_char::EncodedChar;
+
+/// Smallest piece of text a segment boundary may not cut. GSM-7 escape
+/// sequences and UTF-16 surrogate pairs are never cut either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Atomicity {
+    /// Keep user-perceived characters, such as emoji sequences, whole
+    #[default]
+    GraphemeCluster,
+    /// Allow splits between the code points of a grapheme cluster
+    CodePoint,
+}
+
+/// Where a segment may end when the next character doesn't fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitStrategy {
+    /// Split at the first character that doesn't fit
+    #[default]
+    Character,
+    /// Back off up to `max_backoff` characters to the last whitespace or
+    /// punctuation. URLs are never split.
+    Word { max_backoff: usize },
+    /// Back off up to `max_backoff` characters to the last sentence boundary,
+    /// or else to the last word boundary
+    Sentence { max_backoff: usize },
+}
+
+impl SplitStrategy {
+    pub fn max_backoff(&self) -> usize {
+        match self {
+            SplitStrategy::Character => 0,
+            SplitStrategy::Word { max_backoff } | SplitStrategy::Sentence { max_backoff } => {
+                *max_backoff
+            }
+        }
+    }
+
+    /// Break points between the given units of text
+    pub(crate) fn break_points(&self, units: &[EncodedChar]) -> BreakPoints {
+        let (preferred, fallback) = match self {
+            SplitStrategy::Character => (Vec::new(), Vec::new()),
+            SplitStrategy::Word { .. } => (word_breaks(units), Vec::new()),
+            SplitStrategy::Sentence { .. } => (sentence_breaks(units), word_breaks(units)),
+        };
+
+        BreakPoints {
+            preferred,
+            fallback,
+            max_backoff: self.max_backoff(),
+        }
+    }
+}
+
+/// Unit indices a segment may start at, indexed `0..=units.len()`
+pub(crate) struct BreakPoints {
+    preferred: Vec<bool>,
+    fallback: Vec<bool>,
+    max_backoff: usize,
+}
+
+impl BreakPoints {
+    /// Latest break point at or before `index`, backing off at most
+    /// `max_backoff` units and never below `lowest`
+    pub(crate) fn find(&self, index: usize, lowest: usize) -> Option<usize> {
+        let lowest = lowest.max(index.saturating_sub(self.max_backoff));
+        if lowest > index {
+            return None;
+        }
+
+        [&self.preferred, &self.fallback]
+            .into_iter()
+            .find_map(|breaks| {
+                (lowest..=index)
+                    .rev()
+                    .find(|&i| breaks.get(i).copied().unwrap_or(false))
+            })
+    }
+}
+
+fn text(units: &[EncodedChar]) -> String {
+    units.iter().map(|u| u.raw.as_str()).collect()
+}
+
+// Byte offset of the start of every unit, followed by the message length
+fn unit_offsets(units: &[EncodedChar]) -> Vec<usize> {
+    let mut offsets = vec![0];
+    for unit in units {
+        offsets.push(offsets.last().unwrap() + unit.raw.len());
+    }
+    offsets
+}
+
+fn breaks_at(units: &[EncodedChar], byte_offsets: impl Iterator<Item = usize>) -> Vec<bool> {
+    let offsets = unit_offsets(units);
+    let mut breaks = vec![false; offsets.len()];
+    for byte_offset in byte_offsets {
+        if let Ok(index) = offsets.binary_search(&byte_offset) {
+            breaks[index] = true;
+        }
+    }
+    breaks
+}
+
+fn sentence_breaks(units: &[EncodedChar]) -> Vec<bool> {
+    let message = text(units);
+    breaks_at(
+        units,
+        message
+            .split_sentence_bound_indices()
+            .map(|(offset, _)| offset),
+    )
+}
+
+// Breaks after whitespace and punctuation, except inside URLs
+fn word_breaks(units: &[EncodedChar]) -> Vec<bool> {
+    let message = text(units);
+    let mut breaks = breaks_at(
+        units,
+        message
+            .split_word_bound_indices()
+            .filter(|(_, word)| word.chars().all(|c| !c.is_alphanumeric()))
+            .map(|(offset, word)| offset + word.len()),
+    );
+
+    let is_whitespace = |u: &EncodedChar| u.raw.chars().all(char::is_whitespace);
+    let mut start = 0;
+    while start < units.len() {
+        let end = start
+            + units[start..]
+                .iter()
+                .position(is_whitespace)
+                .unwrap_or(units.len() - start);
+        let token = text(&units[start..end]);
+        if token.contains("://") || token.starts_with("www.") {
+            breaks[start + 1..end].iter_mut().for_each(|b| *b = false);
+        }
+        start = end + 1;
+    }
+
+    breaks
+}
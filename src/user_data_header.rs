@@ -1,14 +1,263 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU16, Ordering};
+
 use crate::encoded_char::EncodedCharTrait;
+use crate::error::Error;
+use crate::national_language::{
+    LanguageShift, NationalLanguage, LOCKING_SHIFT_IEI, SINGLE_SHIFT_IEI,
+};
 
-#[derive(Debug, Clone)]
+/// Information Element Identifier of the concatenation element with an 8-bit reference
+pub const CONCATENATION_8_BIT_IEI: u8 = 0x00;
+/// Information Element Identifier of the concatenation element with a 16-bit reference
+pub const CONCATENATION_16_BIT_IEI: u8 = 0x08;
+
+static NEXT_CONCATENATION_REFERENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Returns a new concatenation reference number, unique within the process
+/// until it wraps around
+pub fn next_concatenation_reference() -> u16 {
+    NEXT_CONCATENATION_REFERENCE.fetch_add(1, Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferenceWidth {
+    #[default]
+    EightBit,
+    SixteenBit,
+}
+
+impl ReferenceWidth {
+    pub fn max_reference(&self) -> u16 {
+        match self {
+            ReferenceWidth::EightBit => u8::MAX as u16,
+            ReferenceWidth::SixteenBit => u16::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Concatenation {
+    pub reference_width: ReferenceWidth,
+    pub reference: u16,
+    pub total: u8,
+    pub sequence: u8,
+}
+
+impl Concatenation {
+    pub fn new(reference_width: ReferenceWidth, reference: u16, total: u8, sequence: u8) -> Self {
+        Concatenation {
+            reference_width,
+            reference: reference & reference_width.max_reference(),
+            total,
+            sequence,
+        }
+    }
+
+    fn to_information_element(self) -> InformationElement {
+        match self.reference_width {
+            ReferenceWidth::EightBit => InformationElement::new(
+                CONCATENATION_8_BIT_IEI,
+                vec![self.reference as u8, self.total, self.sequence],
+            ),
+            ReferenceWidth::SixteenBit => InformationElement::new(
+                CONCATENATION_16_BIT_IEI,
+                vec![
+                    (self.reference >> 8) as u8,
+                    self.reference as u8,
+                    self.total,
+                    self.sequence,
+                ],
+            ),
+        }
+    }
+
+    fn from_information_element(element: &InformationElement) -> Option<Self> {
+        match (element.identifier, element.data.as_slice()) {
+            (CONCATENATION_8_BIT_IEI, &[reference, total, sequence]) => Some(Concatenation::new(
+                ReferenceWidth::EightBit,
+                reference as u16,
+                total,
+                sequence,
+            )),
+            (CONCATENATION_16_BIT_IEI, &[high, low, total, sequence]) => Some(Concatenation::new(
+                ReferenceWidth::SixteenBit,
+                u16::from_be_bytes([high, low]),
+                total,
+                sequence,
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InformationElement {
+    pub identifier: u8,
+    pub data: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(identifier: u8, data: Vec<u8>) -> Self {
+        InformationElement { identifier, data }
+    }
+
+    fn is_concatenation(&self) -> bool {
+        matches!(
+            self.identifier,
+            CONCATENATION_8_BIT_IEI | CONCATENATION_16_BIT_IEI
+        )
+    }
+
+    fn is_language_shift(&self) -> bool {
+        matches!(self.identifier, SINGLE_SHIFT_IEI | LOCKING_SHIFT_IEI)
+    }
+}
+
+/// User Data Header Class
+///
+/// Information elements at the start of the user data of a segment
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct UserDataHeader {
-    pub is_reserved_char: bool,
-    pub is_user_data_header: bool,
+    pub information_elements: Vec<InformationElement>,
 }
 
 impl UserDataHeader {
     pub fn new() -> Self {
         UserDataHeader {
+            information_elements: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.information_elements.is_empty()
+    }
+
+    /// Length in octets, including the User Data Header Length octet
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            1 + self
+                .information_elements
+                .iter()
+                .map(|e| 2 + e.data.len())
+                .sum::<usize>()
+        }
+    }
+
+    pub fn concatenation(&self) -> Option<Concatenation> {
+        self.information_elements
+            .iter()
+            .find_map(Concatenation::from_information_element)
+    }
+
+    pub fn set_concatenation(&mut self, concatenation: Concatenation) {
+        self.information_elements.retain(|e| !e.is_concatenation());
+        self.information_elements
+            .insert(0, concatenation.to_information_element());
+    }
+
+    pub fn language_shift(&self) -> LanguageShift {
+        let language = |identifier: u8| {
+            self.information_elements
+                .iter()
+                .find(|e| e.identifier == identifier)
+                .and_then(|e| e.data.first())
+                .and_then(|&id| NationalLanguage::from_language_id(id))
+        };
+
+        LanguageShift::new(language(LOCKING_SHIFT_IEI), language(SINGLE_SHIFT_IEI))
+    }
+
+    pub fn set_language_shift(&mut self, language_shift: LanguageShift) {
+        self.information_elements.retain(|e| !e.is_language_shift());
+        if let Some(language) = language_shift.locking_shift {
+            self.information_elements.push(InformationElement::new(
+                LOCKING_SHIFT_IEI,
+                vec![language.language_id()],
+            ));
+        }
+        if let Some(language) = language_shift.single_shift {
+            self.information_elements.push(InformationElement::new(
+                SINGLE_SHIFT_IEI,
+                vec![language.language_id()],
+            ));
+        }
+    }
+
+    /// Serializes the header, starting with the User Data Header Length octet.
+    /// An empty header serializes to no octets.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        let mut bytes = vec![(self.len() - 1) as u8];
+        for element in &self.information_elements {
+            bytes.push(element.identifier);
+            bytes.push(element.data.len() as u8);
+            bytes.extend(&element.data);
+        }
+        bytes
+    }
+
+    /// Parses the header at the start of the user data. Octets following the
+    /// header are ignored; `len()` gives the offset of the message payload.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let header_length = *bytes
+            .first()
+            .ok_or_else(|| Error::Decode("User Data Header is empty".to_string()))?
+            as usize;
+        let body = bytes.get(1..1 + header_length).ok_or_else(|| {
+            Error::Decode(format!(
+                "User Data Header length {} exceeds the {} octets available",
+                header_length,
+                bytes.len() - 1
+            ))
+        })?;
+
+        let mut information_elements = Vec::new();
+        let mut offset = 0;
+        while offset < body.len() {
+            let identifier = body[offset];
+            let length = *body.get(offset + 1).ok_or_else(|| {
+                Error::Decode(format!(
+                    "Information element 0x{:02x} has no length",
+                    identifier
+                ))
+            })? as usize;
+            let data = body.get(offset + 2..offset + 2 + length).ok_or_else(|| {
+                Error::Decode(format!(
+                    "Information element 0x{:02x} is truncated",
+                    identifier
+                ))
+            })?;
+
+            information_elements.push(InformationElement::new(identifier, data.to_vec()));
+            offset += 2 + length;
+        }
+
+        Ok(UserDataHeader {
+            information_elements,
+        })
+    }
+}
+
+/// A single octet of User Data Header, counted in the segment size like a character
+#[derive(Debug, Clone)]
+pub struct UserDataHeaderOctet {
+    pub value: u8,
+    pub is_reserved_char: bool,
+    pub is_user_data_header: bool,
+}
+
+impl UserDataHeaderOctet {
+    pub fn new(value: u8) -> Self {
+        UserDataHeaderOctet {
+            value,
             is_reserved_char: true,
             is_user_data_header: true,
         }
@@ -23,7 +272,7 @@ impl UserDataHeader {
     }
 }
 
-impl EncodedCharTrait for UserDataHeader {
+impl EncodedCharTrait for UserDataHeaderOctet {
     fn size_in_bits(&self) -> u16 {
         8
     }
@@ -39,4 +288,8 @@ impl EncodedCharTrait for UserDataHeader {
     fn raw(&self) -> &str {
         ""
     }
+
+    fn code_units(&self) -> &[u16] {
+        &[]
+    }
 }
@@ -1,3 +1,2 @@
+pub mod national_language_tables;
 pub mod smart_encoding_map;
-pub mod unicode_to_gsm;
-pub mod utils;
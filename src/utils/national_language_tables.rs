@@ -0,0 +1,1154 @@
+// GSM 7-bit default alphabet and extension table (3GPP TS 23.038, section
+// 6.2.1) and national language shift tables (Annex A).
+//
+// The default alphabet and locking shift tables replace each other and are
+// indexed by GSM code. Position 0x1B is the escape to the extension or single
+// shift table and never maps to a character, nor do the positions holding
+// UNDEFINED. The extension and single shift tables replace each other and only
+// list the defined positions.
+//
+// The Indian language locking shift tables other than Urdu share the layout of
+// the Hindi one, leaving positions undefined where their script has no such
+// letter.
+
+pub const ESCAPE: char = '\u{1b}';
+pub const UNDEFINED: char = '\u{ffff}';
+
+pub const GSM7_DEFAULT_ALPHABET: [char; 128] = [
+    '@', '£', '$', '¥', 'è', 'é', 'ù', 'ì', 'ò', 'Ç', '\n', 'Ø', 'ø', '\r', 'Å', 'å', //
+    'Δ', '_', 'Φ', 'Γ', 'Λ', 'Ω', 'Π', 'Ψ', 'Σ', 'Θ', 'Ξ', ESCAPE, 'Æ', 'æ', 'ß', 'É', //
+    ' ', '!', '"', '#', '¤', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', //
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?', //
+    '¡', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', //
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'Ä', 'Ö', 'Ñ', 'Ü', '§', //
+    '¿', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ä', 'ö', 'ñ', 'ü', 'à', //
+];
+
+pub const GSM7_EXTENSION_TABLE: &[(u8, char)] = &[
+    (0x0a, '\u{000c}'),
+    (0x14, '^'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x65, '€'),
+];
+
+pub const TURKISH_LOCKING_SHIFT: [char; 128] = [
+    '@', '£', '$', '¥', '€', 'é', 'ù', 'ı', 'ò', 'Ç', '\n', 'Ğ', 'ğ', '\r', 'Å', 'å', //
+    'Δ', '_', 'Φ', 'Γ', 'Λ', 'Ω', 'Π', 'Ψ', 'Σ', 'Θ', 'Ξ', ESCAPE, 'Ş', 'ş', 'ß', 'É', //
+    ' ', '!', '"', '#', '¤', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', //
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?', //
+    'İ', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', //
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'Ä', 'Ö', 'Ñ', 'Ü', '§', //
+    'ç', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ä', 'ö', 'ñ', 'ü', 'à', //
+];
+
+pub const PORTUGUESE_LOCKING_SHIFT: [char; 128] = [
+    '@', '£', '$', '¥', 'ê', 'é', 'ú', 'í', 'ó', 'ç', '\n', 'Ô', 'ô', '\r', 'Á', 'á', //
+    'Δ', '_', 'ª', 'Ç', 'À', '∞', '^', '\\', '€', 'Ó', '|', ESCAPE, 'Â', 'â', 'Ê', 'É', //
+    ' ', '!', '"', '#', 'º', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', //
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?', //
+    'Í', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', //
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'Ã', 'Õ', 'Ú', 'Ü', '§', //
+    '~', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ã', 'õ', '`', 'ü', 'à', //
+];
+
+pub const TURKISH_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x0a, '\u{000c}'),
+    (0x14, '^'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x47, 'Ğ'),
+    (0x49, 'İ'),
+    (0x53, 'Ş'),
+    (0x63, 'ç'),
+    (0x65, '€'),
+    (0x67, 'ğ'),
+    (0x69, 'ı'),
+    (0x73, 'ş'),
+];
+
+pub const SPANISH_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x09, 'ç'),
+    (0x0a, '\u{000c}'),
+    (0x14, '^'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'Á'),
+    (0x49, 'Í'),
+    (0x4f, 'Ó'),
+    (0x55, 'Ú'),
+    (0x61, 'á'),
+    (0x65, '€'),
+    (0x69, 'í'),
+    (0x6f, 'ó'),
+    (0x75, 'ú'),
+];
+
+pub const PORTUGUESE_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x05, 'ê'),
+    (0x09, 'ç'),
+    (0x0a, '\u{000c}'),
+    (0x0b, 'Ô'),
+    (0x0c, 'ô'),
+    (0x0e, 'Á'),
+    (0x0f, 'á'),
+    (0x12, 'Φ'),
+    (0x13, 'Γ'),
+    (0x14, '^'),
+    (0x15, 'Ω'),
+    (0x16, 'Π'),
+    (0x17, 'Ψ'),
+    (0x18, 'Σ'),
+    (0x19, 'Θ'),
+    (0x1f, 'Ê'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'À'),
+    (0x49, 'Í'),
+    (0x4f, 'Ó'),
+    (0x55, 'Ú'),
+    (0x5b, 'Ã'),
+    (0x5c, 'Õ'),
+    (0x61, 'Â'),
+    (0x65, '€'),
+    (0x69, 'í'),
+    (0x6f, 'ó'),
+    (0x75, 'ú'),
+    (0x7b, 'ã'),
+    (0x7c, 'õ'),
+    (0x7f, 'â'),
+];
+
+pub const BENGALI_LOCKING_SHIFT: [char; 128] = [
+    'ঁ', 'ং', 'ঃ', 'অ', 'আ', 'ই', 'ঈ', 'উ', //
+    'ঊ', 'ঋ', '\n', 'ঌ', UNDEFINED, '\r', UNDEFINED, 'এ', //
+    'ঐ', UNDEFINED, UNDEFINED, 'ও', 'ঔ', 'ক', 'খ', 'গ', //
+    'ঘ', 'ঙ', 'চ', ESCAPE, 'ছ', 'জ', 'ঝ', 'ঞ', //
+    ' ', '!', 'ট', 'ঠ', 'ড', 'ঢ', 'ণ', 'ত', //
+    ')', '(', 'থ', 'দ', ',', 'ধ', '.', 'ন', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', UNDEFINED, 'প', 'ফ', '?', //
+    'ব', 'ভ', 'ম', 'য', 'র', UNDEFINED, 'ল', UNDEFINED, //
+    UNDEFINED, UNDEFINED, 'শ', 'ষ', 'স', 'হ', '়', 'ঽ', //
+    'া', 'ি', 'ী', 'ু', 'ূ', 'ৃ', 'ৄ', UNDEFINED, //
+    UNDEFINED, 'ে', 'ৈ', UNDEFINED, UNDEFINED, 'ো', 'ৌ', '্', //
+    'ৎ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ৗ', 'ড়', 'ঢ়', 'ৰ', 'ৱ', //
+];
+
+pub const GUJARATI_LOCKING_SHIFT: [char; 128] = [
+    'ઁ', 'ં', 'ઃ', 'અ', 'આ', 'ઇ', 'ઈ', 'ઉ', //
+    'ઊ', 'ઋ', '\n', 'ઌ', 'ઍ', '\r', UNDEFINED, 'એ', //
+    'ઐ', 'ઑ', UNDEFINED, 'ઓ', 'ઔ', 'ક', 'ખ', 'ગ', //
+    'ઘ', 'ઙ', 'ચ', ESCAPE, 'છ', 'જ', 'ઝ', 'ઞ', //
+    ' ', '!', 'ટ', 'ઠ', 'ડ', 'ઢ', 'ણ', 'ત', //
+    ')', '(', 'થ', 'દ', ',', 'ધ', '.', 'ન', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', UNDEFINED, 'પ', 'ફ', '?', //
+    'બ', 'ભ', 'મ', 'ય', 'ર', UNDEFINED, 'લ', 'ળ', //
+    UNDEFINED, 'વ', 'શ', 'ષ', 'સ', 'હ', '઼', 'ઽ', //
+    'ા', 'િ', 'ી', 'ુ', 'ૂ', 'ૃ', 'ૄ', 'ૅ', //
+    UNDEFINED, 'ે', 'ૈ', 'ૉ', UNDEFINED, 'ો', 'ૌ', '્', //
+    'ૐ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ૠ', 'ૡ', 'ૢ', 'ૣ', '૱', //
+];
+
+pub const HINDI_LOCKING_SHIFT: [char; 128] = [
+    'ँ', 'ं', 'ः', 'अ', 'आ', 'इ', 'ई', 'उ', //
+    'ऊ', 'ऋ', '\n', 'ऌ', 'ऍ', '\r', 'ऎ', 'ए', //
+    'ऐ', 'ऑ', 'ऒ', 'ओ', 'औ', 'क', 'ख', 'ग', //
+    'घ', 'ङ', 'च', ESCAPE, 'छ', 'ज', 'झ', 'ञ', //
+    ' ', '!', 'ट', 'ठ', 'ड', 'ढ', 'ण', 'त', //
+    ')', '(', 'थ', 'द', ',', 'ध', '.', 'न', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', 'ऩ', 'प', 'फ', '?', //
+    'ब', 'भ', 'म', 'य', 'र', 'ऱ', 'ल', 'ळ', //
+    'ऴ', 'व', 'श', 'ष', 'स', 'ह', '़', 'ऽ', //
+    'ा', 'ि', 'ी', 'ु', 'ू', 'ृ', 'ॄ', 'ॅ', //
+    'ॆ', 'े', 'ै', 'ॉ', 'ॊ', 'ो', 'ौ', '्', //
+    'ॐ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ॲ', 'ॻ', 'ॼ', 'ॾ', 'ॿ', //
+];
+
+pub const KANNADA_LOCKING_SHIFT: [char; 128] = [
+    UNDEFINED, 'ಂ', 'ಃ', 'ಅ', 'ಆ', 'ಇ', 'ಈ', 'ಉ', //
+    'ಊ', 'ಋ', '\n', 'ಌ', UNDEFINED, '\r', 'ಎ', 'ಏ', //
+    'ಐ', UNDEFINED, 'ಒ', 'ಓ', 'ಔ', 'ಕ', 'ಖ', 'ಗ', //
+    'ಘ', 'ಙ', 'ಚ', ESCAPE, 'ಛ', 'ಜ', 'ಝ', 'ಞ', //
+    ' ', '!', 'ಟ', 'ಠ', 'ಡ', 'ಢ', 'ಣ', 'ತ', //
+    ')', '(', 'ಥ', 'ದ', ',', 'ಧ', '.', 'ನ', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', UNDEFINED, 'ಪ', 'ಫ', '?', //
+    'ಬ', 'ಭ', 'ಮ', 'ಯ', 'ರ', 'ಱ', 'ಲ', 'ಳ', //
+    UNDEFINED, 'ವ', 'ಶ', 'ಷ', 'ಸ', 'ಹ', '಼', 'ಽ', //
+    'ಾ', 'ಿ', 'ೀ', 'ು', 'ೂ', 'ೃ', 'ೄ', UNDEFINED, //
+    'ೆ', 'ೇ', 'ೈ', UNDEFINED, 'ೊ', 'ೋ', 'ೌ', '್', //
+    'ೕ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ೖ', 'ೠ', 'ೡ', 'ೢ', 'ೣ', //
+];
+
+pub const MALAYALAM_LOCKING_SHIFT: [char; 128] = [
+    UNDEFINED, 'ം', 'ഃ', 'അ', 'ആ', 'ഇ', 'ഈ', 'ഉ', //
+    'ഊ', 'ഋ', '\n', 'ഌ', UNDEFINED, '\r', 'എ', 'ഏ', //
+    'ഐ', UNDEFINED, 'ഒ', 'ഓ', 'ഔ', 'ക', 'ഖ', 'ഗ', //
+    'ഘ', 'ങ', 'ച', ESCAPE, 'ഛ', 'ജ', 'ഝ', 'ഞ', //
+    ' ', '!', 'ട', 'ഠ', 'ഡ', 'ഢ', 'ണ', 'ത', //
+    ')', '(', 'ഥ', 'ദ', ',', 'ധ', '.', 'ന', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', UNDEFINED, 'പ', 'ഫ', '?', //
+    'ബ', 'ഭ', 'മ', 'യ', 'ര', 'റ', 'ല', 'ള', //
+    'ഴ', 'വ', 'ശ', 'ഷ', 'സ', 'ഹ', UNDEFINED, 'ഽ', //
+    'ാ', 'ി', 'ീ', 'ു', 'ൂ', 'ൃ', 'ൄ', UNDEFINED, //
+    'െ', 'േ', 'ൈ', UNDEFINED, 'ൊ', 'ോ', 'ൌ', '്', //
+    'ൗ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ൠ', 'ൡ', 'ൢ', 'ൣ', '൹', //
+];
+
+pub const ORIYA_LOCKING_SHIFT: [char; 128] = [
+    'ଁ', 'ଂ', 'ଃ', 'ଅ', 'ଆ', 'ଇ', 'ଈ', 'ଉ', //
+    'ଊ', 'ଋ', '\n', 'ଌ', UNDEFINED, '\r', UNDEFINED, 'ଏ', //
+    'ଐ', UNDEFINED, UNDEFINED, 'ଓ', 'ଔ', 'କ', 'ଖ', 'ଗ', //
+    'ଘ', 'ଙ', 'ଚ', ESCAPE, 'ଛ', 'ଜ', 'ଝ', 'ଞ', //
+    ' ', '!', 'ଟ', 'ଠ', 'ଡ', 'ଢ', 'ଣ', 'ତ', //
+    ')', '(', 'ଥ', 'ଦ', ',', 'ଧ', '.', 'ନ', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', UNDEFINED, 'ପ', 'ଫ', '?', //
+    'ବ', 'ଭ', 'ମ', 'ଯ', 'ର', UNDEFINED, 'ଲ', 'ଳ', //
+    UNDEFINED, 'ଵ', 'ଶ', 'ଷ', 'ସ', 'ହ', '଼', 'ଽ', //
+    'ା', 'ି', 'ୀ', 'ୁ', 'ୂ', 'ୃ', 'ୄ', UNDEFINED, //
+    UNDEFINED, 'େ', 'ୈ', UNDEFINED, UNDEFINED, 'ୋ', 'ୌ', '୍', //
+    'ୖ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ୗ', 'ୠ', 'ୡ', 'ୢ', 'ୣ', //
+];
+
+pub const PUNJABI_LOCKING_SHIFT: [char; 128] = [
+    'ਁ', 'ਂ', 'ਃ', 'ਅ', 'ਆ', 'ਇ', 'ਈ', 'ਉ', //
+    'ਊ', UNDEFINED, '\n', UNDEFINED, UNDEFINED, '\r', UNDEFINED, 'ਏ', //
+    'ਐ', UNDEFINED, UNDEFINED, 'ਓ', 'ਔ', 'ਕ', 'ਖ', 'ਗ', //
+    'ਘ', 'ਙ', 'ਚ', ESCAPE, 'ਛ', 'ਜ', 'ਝ', 'ਞ', //
+    ' ', '!', 'ਟ', 'ਠ', 'ਡ', 'ਢ', 'ਣ', 'ਤ', //
+    ')', '(', 'ਥ', 'ਦ', ',', 'ਧ', '.', 'ਨ', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', UNDEFINED, 'ਪ', 'ਫ', '?', //
+    'ਬ', 'ਭ', 'ਮ', 'ਯ', 'ਰ', UNDEFINED, 'ਲ', 'ਲ਼', //
+    UNDEFINED, 'ਵ', 'ਸ਼', UNDEFINED, 'ਸ', 'ਹ', '਼', UNDEFINED, //
+    'ਾ', 'ਿ', 'ੀ', 'ੁ', 'ੂ', UNDEFINED, UNDEFINED, UNDEFINED, //
+    UNDEFINED, 'ੇ', 'ੈ', UNDEFINED, UNDEFINED, 'ੋ', 'ੌ', '੍', //
+    'ੑ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ੰ', 'ੱ', 'ੲ', 'ੳ', 'ੴ', //
+];
+
+pub const TAMIL_LOCKING_SHIFT: [char; 128] = [
+    UNDEFINED, 'ஂ', 'ஃ', 'அ', 'ஆ', 'இ', 'ஈ', 'உ', //
+    'ஊ', UNDEFINED, '\n', UNDEFINED, UNDEFINED, '\r', 'எ', 'ஏ', //
+    'ஐ', UNDEFINED, 'ஒ', 'ஓ', 'ஔ', 'க', UNDEFINED, UNDEFINED, //
+    UNDEFINED, 'ங', 'ச', ESCAPE, UNDEFINED, 'ஜ', UNDEFINED, 'ஞ', //
+    ' ', '!', 'ட', UNDEFINED, UNDEFINED, UNDEFINED, 'ண', 'த', //
+    ')', '(', UNDEFINED, UNDEFINED, ',', UNDEFINED, '.', 'ந', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', 'ன', 'ப', UNDEFINED, '?', //
+    UNDEFINED, UNDEFINED, 'ம', 'ய', 'ர', 'ற', 'ல', 'ள', //
+    'ழ', 'வ', 'ஶ', 'ஷ', 'ஸ', 'ஹ', UNDEFINED, UNDEFINED, //
+    'ா', 'ி', 'ீ', 'ு', 'ூ', UNDEFINED, UNDEFINED, UNDEFINED, //
+    'ெ', 'ே', 'ை', UNDEFINED, 'ொ', 'ோ', 'ௌ', '்', //
+    'ௐ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ௗ', '௰', '௱', '௲', '௹', //
+];
+
+pub const TELUGU_LOCKING_SHIFT: [char; 128] = [
+    'ఁ', 'ం', 'ః', 'అ', 'ఆ', 'ఇ', 'ఈ', 'ఉ', //
+    'ఊ', 'ఋ', '\n', 'ఌ', UNDEFINED, '\r', 'ఎ', 'ఏ', //
+    'ఐ', UNDEFINED, 'ఒ', 'ఓ', 'ఔ', 'క', 'ఖ', 'గ', //
+    'ఘ', 'ఙ', 'చ', ESCAPE, 'ఛ', 'జ', 'ఝ', 'ఞ', //
+    ' ', '!', 'ట', 'ఠ', 'డ', 'ఢ', 'ణ', 'త', //
+    ')', '(', 'థ', 'ద', ',', 'ధ', '.', 'న', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', UNDEFINED, 'ప', 'ఫ', '?', //
+    'బ', 'భ', 'మ', 'య', 'ర', 'ఱ', 'ల', 'ళ', //
+    UNDEFINED, 'వ', 'శ', 'ష', 'స', 'హ', UNDEFINED, 'ఽ', //
+    'ా', 'ి', 'ీ', 'ు', 'ూ', 'ృ', 'ౄ', UNDEFINED, //
+    'ె', 'ే', 'ై', UNDEFINED, 'ొ', 'ో', 'ౌ', '్', //
+    'ౕ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ౖ', 'ౠ', 'ౡ', 'ౢ', 'ౣ', //
+];
+
+pub const URDU_LOCKING_SHIFT: [char; 128] = [
+    'ا', 'آ', 'ب', 'ٻ', 'ڀ', 'پ', 'ڦ', 'ت', //
+    'ۂ', 'ٿ', '\n', 'ٹ', 'ٽ', '\r', 'ٺ', 'ټ', //
+    'ث', 'ج', 'ځ', 'ڄ', 'ڃ', 'څ', 'چ', 'ڇ', //
+    'ح', 'خ', 'د', ESCAPE, 'ڌ', 'ڈ', 'ډ', 'ڊ', //
+    ' ', '!', 'ڏ', 'ڍ', 'ذ', 'ر', 'ڑ', 'ړ', //
+    ')', '(', 'ڙ', 'ز', ',', 'ږ', '.', 'ژ', //
+    '0', '1', '2', '3', '4', '5', '6', '7', //
+    '8', '9', ':', ';', 'ښ', 'س', 'ش', '?', //
+    'ص', 'ض', 'ط', 'ظ', 'ع', 'ف', 'ق', 'ک', //
+    'ڪ', 'ګ', 'گ', 'ڳ', 'ڱ', 'ل', 'م', 'ن', //
+    'ں', 'ڻ', 'ڼ', 'و', 'ۄ', 'ە', 'ہ', 'ھ', //
+    'ء', 'ی', 'ې', 'ے', 'ٍ', 'ِ', 'ُ', 'ٗ', //
+    'ٔ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', //
+    'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', //
+    'x', 'y', 'z', 'ٕ', 'ّ', 'ٓ', 'ٖ', 'ٰ', //
+];
+
+pub const BENGALI_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '০'),
+    (0x1a, '১'),
+    (0x1c, '২'),
+    (0x1d, '৩'),
+    (0x1e, '৪'),
+    (0x1f, '৫'),
+    (0x20, '৬'),
+    (0x21, '৭'),
+    (0x22, '৮'),
+    (0x23, '৯'),
+    (0x24, 'য়'),
+    (0x25, 'ৠ'),
+    (0x26, 'ৡ'),
+    (0x27, 'ৢ'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, 'ৣ'),
+    (0x2b, '৲'),
+    (0x2c, '৳'),
+    (0x2d, '৴'),
+    (0x2e, '৵'),
+    (0x2f, '\\'),
+    (0x30, '৶'),
+    (0x31, '৷'),
+    (0x32, '৸'),
+    (0x33, '৹'),
+    (0x34, '৺'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const GUJARATI_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '।'),
+    (0x1a, '॥'),
+    (0x1c, '૦'),
+    (0x1d, '૧'),
+    (0x1e, '૨'),
+    (0x1f, '૩'),
+    (0x20, '૪'),
+    (0x21, '૫'),
+    (0x22, '૬'),
+    (0x23, '૭'),
+    (0x24, '૮'),
+    (0x25, '૯'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const HINDI_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '।'),
+    (0x1a, '॥'),
+    (0x1c, '०'),
+    (0x1d, '१'),
+    (0x1e, '२'),
+    (0x1f, '३'),
+    (0x20, '४'),
+    (0x21, '५'),
+    (0x22, '६'),
+    (0x23, '७'),
+    (0x24, '८'),
+    (0x25, '९'),
+    (0x26, '॑'),
+    (0x27, '॒'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, '॓'),
+    (0x2b, '॔'),
+    (0x2c, 'क़'),
+    (0x2d, 'ख़'),
+    (0x2e, 'ग़'),
+    (0x2f, '\\'),
+    (0x30, 'ज़'),
+    (0x31, 'ड़'),
+    (0x32, 'ढ़'),
+    (0x33, 'फ़'),
+    (0x34, 'य़'),
+    (0x35, 'ॠ'),
+    (0x36, 'ॡ'),
+    (0x37, 'ॢ'),
+    (0x38, 'ॣ'),
+    (0x39, '॰'),
+    (0x3a, 'ॱ'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const KANNADA_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '।'),
+    (0x1a, '॥'),
+    (0x1c, '೦'),
+    (0x1d, '೧'),
+    (0x1e, '೨'),
+    (0x1f, '೩'),
+    (0x20, '೪'),
+    (0x21, '೫'),
+    (0x22, '೬'),
+    (0x23, '೭'),
+    (0x24, '೮'),
+    (0x25, '೯'),
+    (0x26, 'ೞ'),
+    (0x27, 'ೱ'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, 'ೲ'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const MALAYALAM_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '।'),
+    (0x1a, '॥'),
+    (0x1c, '൦'),
+    (0x1d, '൧'),
+    (0x1e, '൨'),
+    (0x1f, '൩'),
+    (0x20, '൪'),
+    (0x21, '൫'),
+    (0x22, '൬'),
+    (0x23, '൭'),
+    (0x24, '൮'),
+    (0x25, '൯'),
+    (0x26, '൰'),
+    (0x27, '൱'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, '൲'),
+    (0x2b, '൳'),
+    (0x2c, '൴'),
+    (0x2d, '൵'),
+    (0x2e, 'ൺ'),
+    (0x2f, '\\'),
+    (0x30, 'ൻ'),
+    (0x31, 'ർ'),
+    (0x32, 'ൽ'),
+    (0x33, 'ൾ'),
+    (0x34, 'ൿ'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const ORIYA_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '।'),
+    (0x1a, '॥'),
+    (0x1c, '୦'),
+    (0x1d, '୧'),
+    (0x1e, '୨'),
+    (0x1f, '୩'),
+    (0x20, '୪'),
+    (0x21, '୫'),
+    (0x22, '୬'),
+    (0x23, '୭'),
+    (0x24, '୮'),
+    (0x25, '୯'),
+    (0x26, 'ଡ଼'),
+    (0x27, 'ଢ଼'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, 'ୟ'),
+    (0x2b, '୰'),
+    (0x2c, 'ୱ'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const PUNJABI_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '।'),
+    (0x1a, '॥'),
+    (0x1c, '੦'),
+    (0x1d, '੧'),
+    (0x1e, '੨'),
+    (0x1f, '੩'),
+    (0x20, '੪'),
+    (0x21, '੫'),
+    (0x22, '੬'),
+    (0x23, '੭'),
+    (0x24, '੮'),
+    (0x25, '੯'),
+    (0x26, 'ਖ਼'),
+    (0x27, 'ਗ਼'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, 'ਜ਼'),
+    (0x2b, 'ੜ'),
+    (0x2c, 'ਫ਼'),
+    (0x2d, 'ੵ'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const TAMIL_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '।'),
+    (0x1a, '॥'),
+    (0x1c, '௦'),
+    (0x1d, '௧'),
+    (0x1e, '௨'),
+    (0x1f, '௩'),
+    (0x20, '௪'),
+    (0x21, '௫'),
+    (0x22, '௬'),
+    (0x23, '௭'),
+    (0x24, '௮'),
+    (0x25, '௯'),
+    (0x26, '௳'),
+    (0x27, '௴'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, '௵'),
+    (0x2b, '௶'),
+    (0x2c, '௷'),
+    (0x2d, '௸'),
+    (0x2e, '௺'),
+    (0x2f, '\\'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const TELUGU_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x1c, '౦'),
+    (0x1d, '౧'),
+    (0x1e, '౨'),
+    (0x1f, '౩'),
+    (0x20, '౪'),
+    (0x21, '౫'),
+    (0x22, '౬'),
+    (0x23, '౭'),
+    (0x24, '౮'),
+    (0x25, '౯'),
+    (0x26, 'ౘ'),
+    (0x27, 'ౙ'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, '౸'),
+    (0x2b, '౹'),
+    (0x2c, '౺'),
+    (0x2d, '౻'),
+    (0x2e, '౼'),
+    (0x2f, '\\'),
+    (0x30, '౽'),
+    (0x31, '౾'),
+    (0x32, '౿'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
+
+pub const URDU_SINGLE_SHIFT: &[(u8, char)] = &[
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0a, '\u{000c}'),
+    (0x0b, '*'),
+    (0x0c, '+'),
+    (0x0e, '-'),
+    (0x0f, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '؀'),
+    (0x1a, '؁'),
+    (0x1c, '۰'),
+    (0x1d, '۱'),
+    (0x1e, '۲'),
+    (0x1f, '۳'),
+    (0x20, '۴'),
+    (0x21, '۵'),
+    (0x22, '۶'),
+    (0x23, '۷'),
+    (0x24, '۸'),
+    (0x25, '۹'),
+    (0x26, '،'),
+    (0x27, '؍'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2a, '؎'),
+    (0x2b, '؏'),
+    (0x2c, 'ؐ'),
+    (0x2d, 'ؑ'),
+    (0x2e, 'ؒ'),
+    (0x2f, '\\'),
+    (0x30, 'ؓ'),
+    (0x31, 'ؔ'),
+    (0x32, '؛'),
+    (0x33, '؟'),
+    (0x34, 'ـ'),
+    (0x35, 'ْ'),
+    (0x36, '٘'),
+    (0x37, '٫'),
+    (0x38, '٬'),
+    (0x39, 'ٲ'),
+    (0x3a, 'ٳ'),
+    (0x3b, 'ۍ'),
+    (0x3c, '['),
+    (0x3d, '~'),
+    (0x3e, ']'),
+    (0x3f, '۔'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4a, 'J'),
+    (0x4b, 'K'),
+    (0x4c, 'L'),
+    (0x4d, 'M'),
+    (0x4e, 'N'),
+    (0x4f, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5a, 'Z'),
+    (0x65, '€'),
+];
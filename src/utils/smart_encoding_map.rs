@@ -1,279 +1,289 @@
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
+use crate::smart_encoding::SubstitutionCategory::{self, Dashes, Fractions, Other, Quotes, Spaces};
+
+/// Built-in smart encoding substitutions: original character, replacement and
+/// category
+pub const DEFAULT_SUBSTITUTIONS: &[(char, &str, SubstitutionCategory)] = &[
+    ('\u{00ab}', "\"", Quotes), // LEFT-POINTING DOUBLE ANGLE QUOTATION MARK
+    ('\u{00bb}', "\"", Quotes), // RIGHT-POINTING DOUBLE ANGLE QUOTATION MARK
+    ('\u{201c}', "\"", Quotes), // LEFT DOUBLE QUOTATION MARK
+    ('\u{201d}', "\"", Quotes), // RIGHT DOUBLE QUOTATION MARK
+    ('\u{02ba}', "\"", Quotes), // MODIFIER LETTER DOUBLE PRIME
+    ('\u{02ee}', "\"", Quotes), // MODIFIER LETTER DOUBLE APOSTROPHE
+    ('\u{201f}', "\"", Quotes), // DOUBLE HIGH-REVERSED-9 QUOTATION MARK
+    ('\u{201E}', "\"", Quotes), // DOUBLE LOW-9 QUOTATION MARK
+    ('\u{275d}', "\"", Quotes), // HEAVY DOUBLE TURNED COMMA QUOTATION MARK ORNAMENT
+    ('\u{275e}', "\"", Quotes), // HEAVY DOUBLE COMMA QUOTATION MARK ORNAMENT
+    ('\u{301d}', "\"", Quotes), // REVERSED DOUBLE PRIME QUOTATION MARK
+    ('\u{301e}', "\"", Quotes), // DOUBLE PRIME QUOTATION MARK
+    ('\u{ff02}', "\"", Quotes), // FULLWIDTH QUOTATION MARK
+    ('\u{2018}', "'", Quotes),  // LEFT SINGLE QUOTATION MARK
+    ('\u{2019}', "'", Quotes),  // RIGHT SINGLE QUOTATION MARK
+    ('\u{02BB}', "'", Quotes),  // MODIFIER LETTER TURNED COMMA
+    ('\u{02c8}', "'", Quotes),  // MODIFIER LETTER VERTICAL LINE
+    ('\u{02bc}', "'", Quotes),  // MODIFIER LETTER APOSTROPHE
+    ('\u{02bd}', "'", Quotes),  // MODIFIER LETTER REVERSED COMMA
+    ('\u{02b9}', "'", Quotes),  // MODIFIER LETTER PRIME
+    ('\u{201b}', "'", Quotes),  // SINGLE HIGH-REVERSED-9 QUOTATION MARK
+    ('\u{ff07}', "'", Quotes),  // FULLWIDTH APOSTROPHE
+    ('\u{00b4}', "'", Quotes),  // ACUTE ACCENT
+    ('\u{02ca}', "'", Quotes),  // MODIFIER LETTER ACUTE ACCENT
+    ('\u{0060}', "'", Quotes),  // GRAVE ACCENT
+    ('\u{02cb}', "'", Quotes),  // MODIFIER LETTER GRAVE ACCENT
+    ('\u{275b}', "'", Quotes),  // HEAVY SINGLE TURNED COMMA QUOTATION MARK ORNAMENT
+    ('\u{275c}', "'", Quotes),  // HEAVY SINGLE COMMA QUOTATION MARK ORNAMENT
+    ('\u{0313}', "'", Quotes),  // COMBINING COMMA ABOVE
+    ('\u{0314}', "'", Quotes),  // COMBINING REVERSED COMMA ABOVE
+    ('\u{fe10}', "'", Quotes),  // PRESENTATION FORM FOR VERTICAL COMMA
+    ('\u{fe11}', "'", Quotes),  // PRESENTATION FORM FOR VERTICAL IDEOGRAPHIC COMMA
+    ('\u{00F7}', "/", Other),   // DIVISION SIGN
+    ('\u{00bc}', "1/4", Fractions), // VULGAR FRACTION ONE QUARTER
+    ('\u{00bd}', "1/2", Fractions), // VULGAR FRACTION ONE HALF
+    ('\u{00be}', "3/4", Fractions), // VULGAR FRACTION THREE QUARTERS
+    ('\u{29f8}', "/", Other),   // BIG SOLIDUS
+    ('\u{0337}', "/", Other),   // COMBINING SHORT SOLIDUS OVERLAY
+    ('\u{0338}', "/", Other),   // COMBINING LONG SOLIDUS OVERLAY
+    ('\u{2044}', "/", Fractions), // FRACTION SLASH
+    ('\u{2215}', "/", Other),   // DIVISION SLASH
+    ('\u{ff0f}', "/", Other),   // FULLWIDTH SOLIDUS
+    ('\u{29f9}', "\\", Other),  // BIG REVERSE SOLIDUS
+    ('\u{29f5}', "\\", Other),  // REVERSE SOLIDUS OPERATOR
+    ('\u{20e5}', "\\", Other),  // COMBINING REVERSE SOLIDUS OVERLAY
+    ('\u{fe68}', "\\", Other),  // SMALL REVERSE SOLIDUS
+    ('\u{ff3c}', "\\", Other),  // FULLWIDTH REVERSE SOLIDUS
+    ('\u{0332}', "_", Other),   // COMBINING LOW LINE
+    ('\u{ff3f}', "_", Other),   // FULLWIDTH LOW LINE
+    ('\u{20d2}', "|", Other),   // COMBINING LONG VERTICAL LINE OVERLAY
+    ('\u{20d3}', "|", Other),   // COMBINING SHORT VERTICAL LINE OVERLAY
+    ('\u{2223}', "|", Other),   // DIVIDES
+    ('\u{ff5c}', "|", Other),   // FULLWIDTH VERTICAL LINE
+    ('\u{23b8}', "|", Other),   // LEFT VERTICAL BOX LINE
+    ('\u{23b9}', "|", Other),   // RIGHT VERTICAL BOX LINE
+    ('\u{23d0}', "|", Other),   // VERTICAL LINE EXTENSION
+    ('\u{239c}', "|", Other),   // LEFT PARENTHESIS EXTENSION
+    ('\u{239f}', "|", Other),   // RIGHT PARENTHESIS EXTENSION
+    ('\u{23bc}', "-", Dashes),  // HORIZONTAL SCAN LINE-7
+    ('\u{23bd}', "-", Dashes),  // HORIZONTAL SCAN LINE-9
+    ('\u{2015}', "-", Dashes),  // HORIZONTAL BAR
+    ('\u{fe63}', "-", Dashes),  // SMALL HYPHEN-MINUS
+    ('\u{ff0d}', "-", Dashes),  // FULLWIDTH HYPHEN-MINUS
+    ('\u{2010}', "-", Dashes),  // HYPHEN
+    ('\u{2043}', "-", Dashes),  // HYPHEN BULLET
+    ('\u{fe6b}', "@", Other),   // SMALL COMMERCIAL AT
+    ('\u{ff20}', "@", Other),   // FULLWIDTH COMMERCIAL AT
+    ('\u{fe69}', "$", Other),   // SMALL DOLLAR SIGN
+    ('\u{ff04}', "$", Other),   // FULLWIDTH DOLLAR SIGN
+    ('\u{01c3}', "!", Other),   // LATIN LETTER RETROFLEX CLICK
+    ('\u{fe15}', "!", Other),   // PRESENTATION FORM FOR VERTICAL EXLAMATION MARK
+    ('\u{fe57}', "!", Other),   // SMALL EXCLAMATION MARK
+    ('\u{ff01}', "!", Other),   // FULLWIDTH EXCLAMATION MARK
+    ('\u{fe5f}', "#", Other),   // SMALL NUMBER SIGN
+    ('\u{ff03}', "#", Other),   // FULLWIDTH NUMBER SIGN
+    ('\u{fe6a}', "%", Other),   // SMALL PERCENT SIGN
+    ('\u{ff05}', "%", Other),   // FULLWIDTH PERCENT SIGN
+    ('\u{fe60}', "&", Other),   // SMALL AMPERSAND
+    ('\u{ff06}', "&", Other),   // FULLWIDTH AMPERSAND
+    ('\u{201a}', ",", Quotes),  // SINGLE LOW-9 QUOTATION MARK
+    ('\u{0326}', ",", Other),   // COMBINING COMMA BELOW
+    ('\u{fe50}', ",", Other),   // SMALL COMMA
+    ('\u{fe51}', ",", Other),   // SMALL IDEOGRAPHIC COMMA
+    ('\u{ff0c}', ",", Other),   // FULLWIDTH COMMA
+    ('\u{ff64}', ",", Other),   // HALFWIDTH IDEOGRAPHIC COMMA
+    ('\u{2768}', "(", Other),   // MEDIUM LEFT PARENTHESIS ORNAMENT
+    ('\u{276a}', "(", Other),   // MEDIUM FLATTENED LEFT PARENTHESIS ORNAMENT
+    ('\u{fe59}', "(", Other),   // SMALL LEFT PARENTHESIS
+    ('\u{ff08}', "(", Other),   // FULLWIDTH LEFT PARENTHESIS
+    ('\u{27ee}', "(", Other),   // MATHEMATICAL LEFT FLATTENED PARENTHESIS
+    ('\u{2985}', "(", Other),   // LEFT WHITE PARENTHESIS
+    ('\u{2769}', ")", Other),   // MEDIUM RIGHT PARENTHESIS ORNAMENT
+    ('\u{276b}', ")", Other),   // MEDIUM FLATTENED RIGHT PARENTHESIS ORNAMENT
+    ('\u{fe5a}', ")", Other),   // SMALL RIGHT PARENTHESIS
+    ('\u{ff09}', ")", Other),   // FULLWIDTH RIGHT PARENTHESIS
+    ('\u{27ef}', ")", Other),   // MATHEMATICAL RIGHT FLATTENED PARENTHESIS
+    ('\u{2986}', ")", Other),   // RIGHT WHITE PARENTHESIS
+    ('\u{204e}', "*", Other),   // LOW ASTERISK
+    ('\u{2217}', "*", Other),   // ASTERISK OPERATOR
+    ('\u{229B}', "*", Other),   // CIRCLED ASTERISK OPERATOR
+    ('\u{2722}', "*", Other),   // FOUR TEARDROP-SPOKED ASTERISK
+    ('\u{2723}', "*", Other),   // FOUR BALLOON-SPOKED ASTERISK
+    ('\u{2724}', "*", Other),   // HEAVY FOUR BALLOON-SPOKED ASTERISK
+    ('\u{2725}', "*", Other),   // FOUR CLUB-SPOKED ASTERISK
+    ('\u{2731}', "*", Other),   // HEAVY ASTERISK
+    ('\u{2732}', "*", Other),   // OPEN CENTRE ASTERISK
+    ('\u{2733}', "*", Other),   // EIGHT SPOKED ASTERISK
+    ('\u{273a}', "*", Other),   // SIXTEEN POINTED ASTERISK
+    ('\u{273b}', "*", Other),   // TEARDROP-SPOKED ASTERISK
+    ('\u{273c}', "*", Other),   // OPEN CENTRE TEARDROP-SPOKED ASTERISK
+    ('\u{273d}', "*", Other),   // HEAVY TEARDROP-SPOKED ASTERISK
+    ('\u{2743}', "*", Other),   // HEAVY TEARDROP-SPOKED PINWHEEL ASTERISK
+    ('\u{2749}', "*", Other),   // BALLOON-SPOKED ASTERISK
+    ('\u{274a}', "*", Other),   // EIGHT TEARDROP-SPOKED PROPELLER ASTERISK
+    ('\u{274b}', "*", Other),   // HEAVY EIGHT TEARDROP-SPOKED PROPELLER ASTERISK
+    ('\u{29c6}', "*", Other),   // SQUARED ASTERISK
+    ('\u{fe61}', "*", Other),   // SMALL ASTERISK
+    ('\u{ff0a}', "*", Other),   // FULLWIDTH ASTERISK
+    ('\u{02d6}', "+", Other),   // MODIFIER LETTER PLUS SIGN
+    ('\u{fe62}', "+", Other),   // SMALL PLUS SIGN
+    ('\u{ff0b}', "+", Other),   // FULLWIDTH PLUS SIGN
+    ('\u{3002}', ".", Other),   // IDEOGRAPHIC FULL STOP
+    ('\u{fe52}', ".", Other),   // SMALL FULL STOP
+    ('\u{ff0e}', ".", Other),   // FULLWIDTH FULL STOP
+    ('\u{ff61}', ".", Other),   // HALFWIDTH IDEOGRAPHIC FULL STOP
+    ('\u{ff10}', "0", Other),   // FULLWIDTH DIGIT ZERO
+    ('\u{ff11}', "1", Other),   // FULLWIDTH DIGIT ONE
+    ('\u{ff12}', "2", Other),   // FULLWIDTH DIGIT TWO
+    ('\u{ff13}', "3", Other),   // FULLWIDTH DIGIT THREE
+    ('\u{ff14}', "4", Other),   // FULLWIDTH DIGIT FOUR
+    ('\u{ff15}', "5", Other),   // FULLWIDTH DIGIT FIVE
+    ('\u{ff16}', "6", Other),   // FULLWIDTH DIGIT SIX
+    ('\u{ff17}', "7", Other),   // FULLWIDTH DIGIT SEVEN
+    ('\u{ff18}', "8", Other),   // FULLWIDTH DIGIT EIGHT
+    ('\u{ff19}', "9", Other),   // FULLWIDTH DIGIT NINE
+    ('\u{02d0}', ":", Other),   // MODIFIER LETTER TRIANGULAR COLON
+    ('\u{02f8}', ":", Other),   // MODIFIER LETTER RAISED COLON
+    ('\u{2982}', ":", Other),   // Z NOTATION TYPE COLON
+    ('\u{a789}', ":", Other),   // MODIFIER LETTER COLON
+    ('\u{fe13}', ":", Other),   // PRESENTATION FORM FOR VERTICAL COLON
+    ('\u{ff1a}', ":", Other),   // FULLWIDTH COLON
+    ('\u{204f}', ";", Other),   // REVERSED SEMICOLON
+    ('\u{fe14}', ";", Other),   // PRESENTATION FORM FOR VERTICAL SEMICOLON
+    ('\u{fe54}', ";", Other),   // SMALL SEMICOLON
+    ('\u{ff1b}', ";", Other),   // FULLWIDTH SEMICOLON
+    ('\u{fe64}', "<", Other),   // SMALL LESS-THAN SIGN
+    ('\u{ff1c}', "<", Other),   // FULLWIDTH LESS-THAN SIGN
+    ('\u{0347}', "=", Other),   // COMBINING EQUALS SIGN BELOW
+    ('\u{a78a}', "=", Other),   // MODIFIER LETTER SHORT EQUALS SIGN
+    ('\u{fe66}', "=", Other),   // SMALL EQUALS SIGN
+    ('\u{ff1d}', "=", Other),   // FULLWIDTH EQUALS SIGN
+    ('\u{fe65}', ">", Other),   // SMALL GREATER-THAN SIGN
+    ('\u{ff1e}', ">", Other),   // FULLWIDTH GREATER-THAN SIGN
+    ('\u{fe16}', "?", Other),   // PRESENTATION FORM FOR VERTICAL QUESTION MARK
+    ('\u{fe56}', "?", Other),   // SMALL QUESTION MARK
+    ('\u{ff1f}', "?", Other),   // FULLWIDTH QUESTION MARK
+    ('\u{ff21}', "A", Other),   // FULLWIDTH LATIN CAPITAL LETTER A
+    ('\u{1d00}', "A", Other),   // LATIN LETTER SMALL CAPITAL A
+    ('\u{ff22}', "B", Other),   // FULLWIDTH LATIN CAPITAL LETTER B
+    ('\u{0299}', "B", Other),   // LATIN LETTER SMALL CAPITAL B
+    ('\u{ff23}', "C", Other),   // FULLWIDTH LATIN CAPITAL LETTER C
+    ('\u{1d04}', "C", Other),   // LATIN LETTER SMALL CAPITAL C
+    ('\u{ff24}', "D", Other),   // FULLWIDTH LATIN CAPITAL LETTER D
+    ('\u{1d05}', "D", Other),   // LATIN LETTER SMALL CAPITAL D
+    ('\u{ff25}', "E", Other),   // FULLWIDTH LATIN CAPITAL LETTER E
+    ('\u{1d07}', "E", Other),   // LATIN LETTER SMALL CAPITAL E
+    ('\u{ff26}', "F", Other),   // FULLWIDTH LATIN CAPITAL LETTER F
+    ('\u{a730}', "F", Other),   // LATIN LETTER SMALL CAPITAL F
+    ('\u{ff27}', "G", Other),   // FULLWIDTH LATIN CAPITAL LETTER G
+    ('\u{0262}', "G", Other),   // LATIN LETTER SMALL CAPITAL G
+    ('\u{ff28}', "H", Other),   // FULLWIDTH LATIN CAPITAL LETTER H
+    ('\u{029c}', "H", Other),   // LATIN LETTER SMALL CAPITAL H
+    ('\u{ff29}', "I", Other),   // FULLWIDTH LATIN CAPITAL LETTER I
+    ('\u{026a}', "I", Other),   // LATIN LETTER SMALL CAPITAL I
+    ('\u{ff2a}', "J", Other),   // FULLWIDTH LATIN CAPITAL LETTER J
+    ('\u{1d0a}', "J", Other),   // LATIN LETTER SMALL CAPITAL J
+    ('\u{ff2b}', "K", Other),   // FULLWIDTH LATIN CAPITAL LETTER K
+    ('\u{1d0b}', "K", Other),   // LATIN LETTER SMALL CAPITAL K
+    ('\u{ff2c}', "L", Other),   // FULLWIDTH LATIN CAPITAL LETTER L
+    ('\u{029f}', "L", Other),   // LATIN LETTER SMALL CAPITAL L
+    ('\u{ff2d}', "M", Other),   // FULLWIDTH LATIN CAPITAL LETTER M
+    ('\u{1d0d}', "M", Other),   // LATIN LETTER SMALL CAPITAL M
+    ('\u{ff2e}', "N", Other),   // FULLWIDTH LATIN CAPITAL LETTER N
+    ('\u{0274}', "N", Other),   // LATIN LETTER SMALL CAPITAL N
+    ('\u{ff2f}', "O", Other),   // FULLWIDTH LATIN CAPITAL LETTER O
+    ('\u{1d0f}', "O", Other),   // LATIN LETTER SMALL CAPITAL O
+    ('\u{ff30}', "P", Other),   // FULLWIDTH LATIN CAPITAL LETTER P
+    ('\u{1d18}', "P", Other),   // LATIN LETTER SMALL CAPITAL P
+    ('\u{ff31}', "Q", Other),   // FULLWIDTH LATIN CAPITAL LETTER Q
+    ('\u{ff32}', "R", Other),   // FULLWIDTH LATIN CAPITAL LETTER R
+    ('\u{0280}', "R", Other),   // LATIN LETTER SMALL CAPITAL R
+    ('\u{ff33}', "S", Other),   // FULLWIDTH LATIN CAPITAL LETTER S
+    ('\u{a731}', "S", Other),   // LATIN LETTER SMALL CAPITAL S
+    ('\u{ff34}', "T", Other),   // FULLWIDTH LATIN CAPITAL LETTER T
+    ('\u{1d1b}', "T", Other),   // LATIN LETTER SMALL CAPITAL T
+    ('\u{ff35}', "U", Other),   // FULLWIDTH LATIN CAPITAL LETTER U
+    ('\u{1d1c}', "U", Other),   // LATIN LETTER SMALL CAPITAL U
+    ('\u{ff36}', "V", Other),   // FULLWIDTH LATIN CAPITAL LETTER V
+    ('\u{1d20}', "V", Other),   // LATIN LETTER SMALL CAPITAL V
+    ('\u{ff37}', "W", Other),   // FULLWIDTH LATIN CAPITAL LETTER W
+    ('\u{1d21}', "W", Other),   // LATIN LETTER SMALL CAPITAL W
+    ('\u{ff38}', "X", Other),   // FULLWIDTH LATIN CAPITAL LETTER X
+    ('\u{ff39}', "Y", Other),   // FULLWIDTH LATIN CAPITAL LETTER Y
+    ('\u{028f}', "Y", Other),   // LATIN LETTER SMALL CAPITAL Y
+    ('\u{ff3a}', "Z", Other),   // FULLWIDTH LATIN CAPITAL LETTER Z
+    ('\u{1d22}', "Z", Other),   // LATIN LETTER SMALL CAPITAL Z
+    ('\u{02c6}', "^", Other),   // MODIFIER LETTER CIRCUMFLEX ACCENT
+    ('\u{0302}', "^", Other),   // COMBINING CIRCUMFLEX ACCENT
+    ('\u{ff3e}', "^", Other),   // FULLWIDTH CIRCUMFLEX ACCENT
+    ('\u{1dcd}', "^", Other),   // COMBINING DOUBLE CIRCUMFLEX ABOVE
+    ('\u{2774}', "{", Other),   // MEDIUM LEFT CURLY BRACKET ORNAMENT
+    ('\u{fe5b}', "{", Other),   // SMALL LEFT CURLY BRACKET
+    ('\u{ff5b}', "{", Other),   // FULLWIDTH LEFT CURLY BRACKET
+    ('\u{2775}', "}", Other),   // MEDIUM RIGHT CURLY BRACKET ORNAMENT
+    ('\u{fe5c}', "}", Other),   // SMALL RIGHT CURLY BRACKET
+    ('\u{ff5d}', "}", Other),   // FULLWIDTH RIGHT CURLY BRACKET
+    ('\u{ff3b}', "[", Other),   // FULLWIDTH LEFT SQUARE BRACKET
+    ('\u{ff3d}', "]", Other),   // FULLWIDTH RIGHT SQUARE BRACKET
+    ('\u{02dc}', "~", Other),   // SMALL TILDE
+    ('\u{02f7}', "~", Other),   // MODIFIER LETTER LOW TILDE
+    ('\u{0303}', "~", Other),   // COMBINING TILDE
+    ('\u{0330}', "~", Other),   // COMBINING TILDE BELOW
+    ('\u{0334}', "~", Other),   // COMBINING TILDE OVERLAY
+    ('\u{223c}', "~", Other),   // TILDE OPERATOR
+    ('\u{ff5e}', "~", Other),   // FULLWIDTH TILDE
+    ('\u{00a0}', " ", Spaces),  // NO-BREAK SPACE
+    ('\u{2000}', " ", Spaces),  // EN QUAD
+    ('\u{2002}', " ", Spaces),  // EN SPACE
+    ('\u{2003}', " ", Spaces),  // EM SPACE
+    ('\u{2004}', " ", Spaces),  // THREE-PER-EM SPACE
+    ('\u{2005}', " ", Spaces),  // FOUR-PER-EM SPACE
+    ('\u{2006}', " ", Spaces),  // SIX-PER-EM SPACE
+    ('\u{2007}', " ", Spaces),  // FIGURE SPACE
+    ('\u{2008}', " ", Spaces),  // PUNCTUATION SPACE
+    ('\u{2009}', " ", Spaces),  // THIN SPACE
+    ('\u{200a}', " ", Spaces),  // HAIR SPACE
+    ('\u{202f}', " ", Spaces),  // NARROW NO-BREAK SPACE
+    ('\u{205f}', " ", Spaces),  // MEDIUM MATHEMATICAL SPACE
+    ('\u{3000}', "　", Spaces), // IDEOGRAPHIC SPACE
+    ('\u{008d}', "\u{008d}", Other), // REVERSE LINE FEED (standard LF looks like \n, this looks like a space)
+    ('\u{009f}', "\u{009f}", Other), // <control>
+    ('\u{0080}', "\u{0080}", Other), // C1 CONTROL CODES
+    ('\u{0090}', "\u{0090}", Other), // DEVICE CONTROL STRING
+    ('\u{009b}', "\u{009b}", Other), // CONTROL SEQUENCE INTRODUCER
+    ('\u{0010}', "\u{0010}", Other), // ESCAPE, DATA LINK (not visible)
+    ('\u{0009}', "\t", Other), // TAB (7 spaces based on print statement in Python interpreter)
+    ('\u{0000}', "�", Other),  // NULL
+    ('\u{0003}', "\u{0003}", Other), // END OF TEXT
+    ('\u{0004}', "\u{0004}", Other), // END OF TRANSMISSION
+    ('\u{0017}', "\u{0017}", Other), // END OF TRANSMISSION BLOCK
+    ('\u{0019}', "\u{0019}", Other), // END OF MEDIUM
+    ('\u{0011}', "\u{0011}", Other), // DEVICE CONTROL ONE
+    ('\u{0012}', "\u{0012}", Other), // DEVICE CONTROL TWO
+    ('\u{0013}', "\u{0013}", Other), // DEVICE CONTROL THREE
+    ('\u{0014}', "\u{0014}", Other), // DEVICE CONTROL FOUR
+    ('\u{2060}', "\u{2060}", Spaces), // WORD JOINER
+    ('\u{2017}', "_", Other),  // DOUBLE LOW LINE
+    ('\u{2014}', "-", Dashes), // EM DASH
+    ('\u{2013}', "-", Dashes), // EN DASH
+    ('\u{2039}', ">", Quotes), // Single left-pointing angle quotation mark
+    ('\u{203a}', "<", Quotes), // Single right-pointing angle quotation mark
+    ('\u{203c}', "!!", Other), // Double exclamation mark
+    ('\u{2028}', " ", Spaces), // Whitespace: Line Separator
+    ('\u{2029}', " ", Spaces), // Whitespace: Paragraph Separator
+    ('\u{2026}', "...", Other), // HORIZONTAL ELLIPSIS
+    ('\u{2001}', " ", Spaces), // EM QUAD
+    ('\u{200b}', "\u{200b}", Spaces), // ZERO WIDTH SPACE
+    ('\u{3001}', ",", Other),  // IDEOGRAPHIC COMMA
+    ('\u{feff}', "\u{feff}", Spaces), // ZERO WIDTH NO-BREAK SPACE
+    ('\u{2022}', "-", Other),  // BULLET
+    ('\u{2122}', "(TM)", Other), // TRADE MARK SIGN
+    ('\u{00a9}', "(c)", Other), // COPYRIGHT SIGN
+    ('\u{00ae}', "(R)", Other), // REGISTERED SIGN
+];
+
 lazy_static! {
-    pub static ref SMART_ENCODING_MAP: HashMap<char, char> = {
-        let mut m = HashMap::new();
-        m.insert('\u{00ab}', '"'); // LEFT-POINTING DOUBLE ANGLE QUOTATION MARK
-        m.insert('\u{00bb}', '"'); // RIGHT-POINTING DOUBLE ANGLE QUOTATION MARK
-        m.insert('\u{201c}', '"'); // LEFT DOUBLE QUOTATION MARK
-        m.insert('\u{201d}', '"'); // RIGHT DOUBLE QUOTATION MARK
-        m.insert('\u{02ba}', '"'); // MODIFIER LETTER DOUBLE PRIME
-        m.insert('\u{02ee}', '"'); // MODIFIER LETTER DOUBLE APOSTROPHE
-        m.insert('\u{201f}', '"'); // DOUBLE HIGH-REVERSED-9 QUOTATION MARK
-        m.insert('\u{201E}', '"'); // DOUBLE LOW-9 QUOTATION MARK
-        m.insert('\u{275d}', '"'); // HEAVY DOUBLE TURNED COMMA QUOTATION MARK ORNAMENT
-        m.insert('\u{275e}', '"'); // HEAVY DOUBLE COMMA QUOTATION MARK ORNAMENT
-        m.insert('\u{301d}', '"'); // REVERSED DOUBLE PRIME QUOTATION MARK
-        m.insert('\u{301e}', '"'); // DOUBLE PRIME QUOTATION MARK
-        m.insert('\u{ff02}', '"'); // FULLWIDTH QUOTATION MARK
-        m.insert('\u{2018}', '\''); // LEFT SINGLE QUOTATION MARK
-        m.insert('\u{2019}', '\''); // RIGHT SINGLE QUOTATION MARK
-        m.insert('\u{02BB}', '\''); // MODIFIER LETTER TURNED COMMA
-        m.insert('\u{02c8}', '\''); // MODIFIER LETTER VERTICAL LINE
-        m.insert('\u{02bc}', '\''); // MODIFIER LETTER APOSTROPHE
-        m.insert('\u{02bd}', '\''); // MODIFIER LETTER REVERSED COMMA
-        m.insert('\u{02b9}', '\''); // MODIFIER LETTER PRIME
-        m.insert('\u{201b}', '\''); // SINGLE HIGH-REVERSED-9 QUOTATION MARK
-        m.insert('\u{ff07}', '\''); // FULLWIDTH APOSTROPHE
-        m.insert('\u{00b4}', '\''); // ACUTE ACCENT
-        m.insert('\u{02ca}', '\''); // MODIFIER LETTER ACUTE ACCENT
-        m.insert('\u{0060}', '\''); // GRAVE ACCENT
-        m.insert('\u{02cb}', '\''); // MODIFIER LETTER GRAVE ACCENT
-        m.insert('\u{275b}', '\''); // HEAVY SINGLE TURNED COMMA QUOTATION MARK ORNAMENT
-        m.insert('\u{275c}', '\''); // HEAVY SINGLE COMMA QUOTATION MARK ORNAMENT
-        m.insert('\u{0313}', '\''); // COMBINING COMMA ABOVE
-        m.insert('\u{0314}', '\''); // COMBINING REVERSED COMMA ABOVE
-        m.insert('\u{fe10}', '\''); // PRESENTATION FORM FOR VERTICAL COMMA
-        m.insert('\u{fe11}', '\''); // PRESENTATION FORM FOR VERTICAL IDEOGRAPHIC COMMA
-        m.insert('\u{00F7}', '/'); // DIVISION SIGN
-        m.insert('\u{00bc}', '¼'); // VULGAR FRACTION ONE QUARTER
-        m.insert('\u{00bd}', '½'); // VULGAR FRACTION ONE HALF
-        m.insert('\u{00be}', '¾'); // VULGAR FRACTION THREE QUARTERS
-        m.insert('\u{29f8}', '/'); // BIG SOLIDUS
-        m.insert('\u{0337}', '/'); // COMBINING SHORT SOLIDUS OVERLAY
-        m.insert('\u{0338}', '/'); // COMBINING LONG SOLIDUS OVERLAY
-        m.insert('\u{2044}', '/'); // FRACTION SLASH
-        m.insert('\u{2215}', '/'); // DIVISION SLASH
-        m.insert('\u{ff0f}', '/'); // FULLWIDTH SOLIDUS
-        m.insert('\u{29f9}', '\\'); // BIG REVERSE SOLIDUS
-        m.insert('\u{29f5}', '\\'); // REVERSE SOLIDUS OPERATOR
-        m.insert('\u{20e5}', '\\'); // COMBINING REVERSE SOLIDUS OVERLAY
-        m.insert('\u{fe68}', '\\'); // SMALL REVERSE SOLIDUS
-        m.insert('\u{ff3c}', '\\'); // FULLWIDTH REVERSE SOLIDUS
-        m.insert('\u{0332}', '_'); // COMBINING LOW LINE
-        m.insert('\u{ff3f}', '_'); // FULLWIDTH LOW LINE
-        m.insert('\u{20d2}', '|'); // COMBINING LONG VERTICAL LINE OVERLAY
-        m.insert('\u{20d3}', '|'); // COMBINING SHORT VERTICAL LINE OVERLAY
-        m.insert('\u{2223}', '|'); // DIVIDES
-        m.insert('\u{ff5c}', '|'); // FULLWIDTH VERTICAL LINE
-        m.insert('\u{23b8}', '|'); // LEFT VERTICAL BOX LINE
-        m.insert('\u{23b9}', '|'); // RIGHT VERTICAL BOX LINE
-        m.insert('\u{23d0}', '|'); // VERTICAL LINE EXTENSION
-        m.insert('\u{239c}', '|'); // LEFT PARENTHESIS EXTENSION
-        m.insert('\u{239f}', '|'); // RIGHT PARENTHESIS EXTENSION
-        m.insert('\u{23bc}', '-'); // HORIZONTAL SCAN LINE-7
-        m.insert('\u{23bd}', '-'); // HORIZONTAL SCAN LINE-9
-        m.insert('\u{2015}', '-'); // HORIZONTAL BAR
-        m.insert('\u{fe63}', '-'); // SMALL HYPHEN-MINUS
-        m.insert('\u{ff0d}', '-'); // FULLWIDTH HYPHEN-MINUS
-        m.insert('\u{2010}', '-'); // HYPHEN
-        m.insert('\u{2043}', '-'); // HYPHEN BULLET
-        m.insert('\u{fe6b}', '@'); // SMALL COMMERCIAL AT
-        m.insert('\u{ff20}', '@'); // FULLWIDTH COMMERCIAL AT
-        m.insert('\u{fe69}', '$'); // SMALL DOLLAR SIGN
-        m.insert('\u{ff04}', '$'); // FULLWIDTH DOLLAR SIGN
-        m.insert('\u{01c3}', '!'); // LATIN LETTER RETROFLEX CLICK
-        m.insert('\u{fe15}', '!'); // PRESENTATION FORM FOR VERTICAL EXLAMATION MARK
-        m.insert('\u{fe57}', '!'); // SMALL EXCLAMATION MARK
-        m.insert('\u{ff01}', '!'); // FULLWIDTH EXCLAMATION MARK
-        m.insert('\u{fe5f}', '#'); // SMALL NUMBER SIGN
-        m.insert('\u{ff03}', '#'); // FULLWIDTH NUMBER SIGN
-        m.insert('\u{fe6a}', '%'); // SMALL PERCENT SIGN
-        m.insert('\u{ff05}', '%'); // FULLWIDTH PERCENT SIGN
-        m.insert('\u{fe60}', '&'); // SMALL AMPERSAND
-        m.insert('\u{ff06}', '&'); // FULLWIDTH AMPERSAND
-        m.insert('\u{201a}', ','); // SINGLE LOW-9 QUOTATION MARK
-        m.insert('\u{0326}', ','); // COMBINING COMMA BELOW
-        m.insert('\u{fe50}', ','); // SMALL COMMA
-        m.insert('\u{fe51}', ','); // SMALL IDEOGRAPHIC COMMA
-        m.insert('\u{ff0c}', ','); // FULLWIDTH COMMA
-        m.insert('\u{ff64}', ','); // HALFWIDTH IDEOGRAPHIC COMMA
-        m.insert('\u{2768}', '('); // MEDIUM LEFT PARENTHESIS ORNAMENT
-        m.insert('\u{276a}', '('); // MEDIUM FLATTENED LEFT PARENTHESIS ORNAMENT
-        m.insert('\u{fe59}', '('); // SMALL LEFT PARENTHESIS
-        m.insert('\u{ff08}', '('); // FULLWIDTH LEFT PARENTHESIS
-        m.insert('\u{27ee}', '('); // MATHEMATICAL LEFT FLATTENED PARENTHESIS
-        m.insert('\u{2985}', '('); // LEFT WHITE PARENTHESIS
-        m.insert('\u{2769}', ')'); // MEDIUM RIGHT PARENTHESIS ORNAMENT
-        m.insert('\u{276b}', ')'); // MEDIUM FLATTENED RIGHT PARENTHESIS ORNAMENT
-        m.insert('\u{fe5a}', ')'); // SMALL RIGHT PARENTHESIS
-        m.insert('\u{ff09}', ')'); // FULLWIDTH RIGHT PARENTHESIS
-        m.insert('\u{27ef}', ')'); // MATHEMATICAL RIGHT FLATTENED PARENTHESIS
-        m.insert('\u{2986}', ')'); // RIGHT WHITE PARENTHESIS
-        m.insert('\u{204e}', '*'); // LOW ASTERISK
-        m.insert('\u{2217}', '*'); // ASTERISK OPERATOR
-        m.insert('\u{229B}', '*'); // CIRCLED ASTERISK OPERATOR
-        m.insert('\u{2722}', '*'); // FOUR TEARDROP-SPOKED ASTERISK
-        m.insert('\u{2723}', '*'); // FOUR BALLOON-SPOKED ASTERISK
-        m.insert('\u{2724}', '*'); // HEAVY FOUR BALLOON-SPOKED ASTERISK
-        m.insert('\u{2725}', '*'); // FOUR CLUB-SPOKED ASTERISK
-        m.insert('\u{2731}', '*'); // HEAVY ASTERISK
-        m.insert('\u{2732}', '*'); // OPEN CENTRE ASTERISK
-        m.insert('\u{2733}', '*'); // EIGHT SPOKED ASTERISK
-        m.insert('\u{273a}', '*'); // SIXTEEN POINTED ASTERISK
-        m.insert('\u{273b}', '*'); // TEARDROP-SPOKED ASTERISK
-        m.insert('\u{273c}', '*'); // OPEN CENTRE TEARDROP-SPOKED ASTERISK
-        m.insert('\u{273d}', '*'); // HEAVY TEARDROP-SPOKED ASTERISK
-        m.insert('\u{2743}', '*'); // HEAVY TEARDROP-SPOKED PINWHEEL ASTERISK
-        m.insert('\u{2749}', '*'); // BALLOON-SPOKED ASTERISK
-        m.insert('\u{274a}', '*'); // EIGHT TEARDROP-SPOKED PROPELLER ASTERISK
-        m.insert('\u{274b}', '*'); // HEAVY EIGHT TEARDROP-SPOKED PROPELLER ASTERISK
-        m.insert('\u{29c6}', '*'); // SQUARED ASTERISK
-        m.insert('\u{fe61}', '*'); // SMALL ASTERISK
-        m.insert('\u{ff0a}', '*'); // FULLWIDTH ASTERISK
-        m.insert('\u{02d6}', '+'); // MODIFIER LETTER PLUS SIGN
-        m.insert('\u{fe62}', '+'); // SMALL PLUS SIGN
-        m.insert('\u{ff0b}', '+'); // FULLWIDTH PLUS SIGN
-        m.insert('\u{3002}', '.'); // IDEOGRAPHIC FULL STOP
-        m.insert('\u{fe52}', '.'); // SMALL FULL STOP
-        m.insert('\u{ff0e}', '.'); // FULLWIDTH FULL STOP
-        m.insert('\u{ff61}', '.'); // HALFWIDTH IDEOGRAPHIC FULL STOP
-        m.insert('\u{ff10}', '0'); // FULLWIDTH DIGIT ZERO
-        m.insert('\u{ff11}', '1'); // FULLWIDTH DIGIT ONE
-        m.insert('\u{ff12}', '2'); // FULLWIDTH DIGIT TWO
-        m.insert('\u{ff13}', '3'); // FULLWIDTH DIGIT THREE
-        m.insert('\u{ff14}', '4'); // FULLWIDTH DIGIT FOUR
-        m.insert('\u{ff15}', '5'); // FULLWIDTH DIGIT FIVE
-        m.insert('\u{ff16}', '6'); // FULLWIDTH DIGIT SIX
-        m.insert('\u{ff17}', '7'); // FULLWIDTH DIGIT SEVEN
-        m.insert('\u{ff18}', '8'); // FULLWIDTH DIGIT EIGHT
-        m.insert('\u{ff19}', '9'); // FULLWIDTH DIGIT NINE
-        m.insert('\u{02d0}', ':'); // MODIFIER LETTER TRIANGULAR COLON
-        m.insert('\u{02f8}', ':'); // MODIFIER LETTER RAISED COLON
-        m.insert('\u{2982}', ':'); // Z NOTATION TYPE COLON
-        m.insert('\u{a789}', ':'); // MODIFIER LETTER COLON
-        m.insert('\u{fe13}', ':'); // PRESENTATION FORM FOR VERTICAL COLON
-        m.insert('\u{ff1a}', ':'); // FULLWIDTH COLON
-        m.insert('\u{204f}', ';'); // REVERSED SEMICOLON
-        m.insert('\u{fe14}', ';'); // PRESENTATION FORM FOR VERTICAL SEMICOLON
-        m.insert('\u{fe54}', ';'); // SMALL SEMICOLON
-        m.insert('\u{ff1b}', ';'); // FULLWIDTH SEMICOLON
-        m.insert('\u{fe64}', '<'); // SMALL LESS-THAN SIGN
-        m.insert('\u{ff1c}', '<'); // FULLWIDTH LESS-THAN SIGN
-        m.insert('\u{0347}', '='); // COMBINING EQUALS SIGN BELOW
-        m.insert('\u{a78a}', '='); // MODIFIER LETTER SHORT EQUALS SIGN
-        m.insert('\u{fe66}', '='); // SMALL EQUALS SIGN
-        m.insert('\u{ff1d}', '='); // FULLWIDTH EQUALS SIGN
-        m.insert('\u{fe65}', '>'); // SMALL GREATER-THAN SIGN
-        m.insert('\u{ff1e}', '>'); // FULLWIDTH GREATER-THAN SIGN
-        m.insert('\u{fe16}', '?'); // PRESENTATION FORM FOR VERTICAL QUESTION MARK
-        m.insert('\u{fe56}', '?'); // SMALL QUESTION MARK
-        m.insert('\u{ff1f}', '?'); // FULLWIDTH QUESTION MARK
-        m.insert('\u{ff21}', 'A'); // FULLWIDTH LATIN CAPITAL LETTER A
-        m.insert('\u{1d00}', 'A'); // LATIN LETTER SMALL CAPITAL A
-        m.insert('\u{ff22}', 'B'); // FULLWIDTH LATIN CAPITAL LETTER B
-        m.insert('\u{0299}', 'B'); // LATIN LETTER SMALL CAPITAL B
-        m.insert('\u{ff23}', 'C'); // FULLWIDTH LATIN CAPITAL LETTER C
-        m.insert('\u{1d04}', 'C'); // LATIN LETTER SMALL CAPITAL C
-        m.insert('\u{ff24}', 'D'); // FULLWIDTH LATIN CAPITAL LETTER D
-        m.insert('\u{1d05}', 'D'); // LATIN LETTER SMALL CAPITAL D
-        m.insert('\u{ff25}', 'E'); // FULLWIDTH LATIN CAPITAL LETTER E
-        m.insert('\u{1d07}', 'E'); // LATIN LETTER SMALL CAPITAL E
-        m.insert('\u{ff26}', 'F'); // FULLWIDTH LATIN CAPITAL LETTER F
-        m.insert('\u{a730}', 'F'); // LATIN LETTER SMALL CAPITAL F
-        m.insert('\u{ff27}', 'G'); // FULLWIDTH LATIN CAPITAL LETTER G
-        m.insert('\u{0262}', 'G'); // LATIN LETTER SMALL CAPITAL G
-        m.insert('\u{ff28}', 'H'); // FULLWIDTH LATIN CAPITAL LETTER H
-        m.insert('\u{029c}', 'H'); // LATIN LETTER SMALL CAPITAL H
-        m.insert('\u{ff29}', 'I'); // FULLWIDTH LATIN CAPITAL LETTER I
-        m.insert('\u{026a}', 'I'); // LATIN LETTER SMALL CAPITAL I
-        m.insert('\u{ff2a}', 'J'); // FULLWIDTH LATIN CAPITAL LETTER J
-        m.insert('\u{1d0a}', 'J'); // LATIN LETTER SMALL CAPITAL J
-        m.insert('\u{ff2b}', 'K'); // FULLWIDTH LATIN CAPITAL LETTER K
-        m.insert('\u{1d0b}', 'K'); // LATIN LETTER SMALL CAPITAL K
-        m.insert('\u{ff2c}', 'L'); // FULLWIDTH LATIN CAPITAL LETTER L
-        m.insert('\u{029f}', 'L'); // LATIN LETTER SMALL CAPITAL L
-        m.insert('\u{ff2d}', 'M'); // FULLWIDTH LATIN CAPITAL LETTER M
-        m.insert('\u{1d0d}', 'M'); // LATIN LETTER SMALL CAPITAL M
-        m.insert('\u{ff2e}', 'N'); // FULLWIDTH LATIN CAPITAL LETTER N
-        m.insert('\u{0274}', 'N'); // LATIN LETTER SMALL CAPITAL N
-        m.insert('\u{ff2f}', 'O'); // FULLWIDTH LATIN CAPITAL LETTER O
-        m.insert('\u{1d0f}', 'O'); // LATIN LETTER SMALL CAPITAL O
-        m.insert('\u{ff30}', 'P'); // FULLWIDTH LATIN CAPITAL LETTER P
-        m.insert('\u{1d18}', 'P'); // LATIN LETTER SMALL CAPITAL P
-        m.insert('\u{ff31}', 'Q'); // FULLWIDTH LATIN CAPITAL LETTER Q
-        m.insert('\u{ff32}', 'R'); // FULLWIDTH LATIN CAPITAL LETTER R
-        m.insert('\u{0280}', 'R'); // LATIN LETTER SMALL CAPITAL R
-        m.insert('\u{ff33}', 'S'); // FULLWIDTH LATIN CAPITAL LETTER S
-        m.insert('\u{a731}', 'S'); // LATIN LETTER SMALL CAPITAL S
-        m.insert('\u{ff34}', 'T'); // FULLWIDTH LATIN CAPITAL LETTER T
-        m.insert('\u{1d1b}', 'T'); // LATIN LETTER SMALL CAPITAL T
-        m.insert('\u{ff35}', 'U'); // FULLWIDTH LATIN CAPITAL LETTER U
-        m.insert('\u{1d1c}', 'U'); // LATIN LETTER SMALL CAPITAL U
-        m.insert('\u{ff36}', 'V'); // FULLWIDTH LATIN CAPITAL LETTER V
-        m.insert('\u{1d20}', 'V'); // LATIN LETTER SMALL CAPITAL V
-        m.insert('\u{ff37}', 'W'); // FULLWIDTH LATIN CAPITAL LETTER W
-        m.insert('\u{1d21}', 'W'); // LATIN LETTER SMALL CAPITAL W
-        m.insert('\u{ff38}', 'X'); // FULLWIDTH LATIN CAPITAL LETTER X
-        m.insert('\u{ff39}', 'Y'); // FULLWIDTH LATIN CAPITAL LETTER Y
-        m.insert('\u{028f}', 'Y'); // LATIN LETTER SMALL CAPITAL Y
-        m.insert('\u{ff3a}', 'Z'); // FULLWIDTH LATIN CAPITAL LETTER Z
-        m.insert('\u{1d22}', 'Z'); // LATIN LETTER SMALL CAPITAL Z
-        m.insert('\u{02c6}', '^'); // MODIFIER LETTER CIRCUMFLEX ACCENT
-        m.insert('\u{0302}', '^'); // COMBINING CIRCUMFLEX ACCENT
-        m.insert('\u{ff3e}', '^'); // FULLWIDTH CIRCUMFLEX ACCENT
-        m.insert('\u{1dcd}', '^'); // COMBINING DOUBLE CIRCUMFLEX ABOVE
-        m.insert('\u{2774}', '{'); // MEDIUM LEFT CURLY BRACKET ORNAMENT
-        m.insert('\u{fe5b}', '{'); // SMALL LEFT CURLY BRACKET
-        m.insert('\u{ff5b}', '{'); // FULLWIDTH LEFT CURLY BRACKET
-        m.insert('\u{2775}', '}'); // MEDIUM RIGHT CURLY BRACKET ORNAMENT
-        m.insert('\u{fe5c}', '}'); // SMALL RIGHT CURLY BRACKET
-        m.insert('\u{ff5d}', '}'); // FULLWIDTH RIGHT CURLY BRACKET
-        m.insert('\u{ff3b}', '['); // FULLWIDTH LEFT SQUARE BRACKET
-        m.insert('\u{ff3d}', ']'); // FULLWIDTH RIGHT SQUARE BRACKET
-        m.insert('\u{02dc}', '~'); // SMALL TILDE
-        m.insert('\u{02f7}', '~'); // MODIFIER LETTER LOW TILDE
-        m.insert('\u{0303}', '~'); // COMBINING TILDE
-        m.insert('\u{0330}', '~'); // COMBINING TILDE BELOW
-        m.insert('\u{0334}', '~'); // COMBINING TILDE OVERLAY
-        m.insert('\u{223c}', '~'); // TILDE OPERATOR
-        m.insert('\u{ff5e}', '~'); // FULLWIDTH TILDE
-        m.insert('\u{00a0}', ' '); // NO-BREAK SPACE
-        m.insert('\u{2000}', ' '); // EN QUAD
-        m.insert('\u{2002}', ' '); // EN SPACE
-        m.insert('\u{2003}', ' '); // EM SPACE
-        m.insert('\u{2004}', ' '); // THREE-PER-EM SPACE
-        m.insert('\u{2005}', ' '); // FOUR-PER-EM SPACE
-        m.insert('\u{2006}', ' '); // SIX-PER-EM SPACE
-        m.insert('\u{2007}', ' '); // FIGURE SPACE
-        m.insert('\u{2008}', ' '); // PUNCTUATION SPACE
-        m.insert('\u{2009}', ' '); // THIN SPACE
-        m.insert('\u{200a}', ' '); // HAIR SPACE
-        m.insert('\u{202f}', ' '); // NARROW NO-BREAK SPACE
-        m.insert('\u{205f}', ' '); // MEDIUM MATHEMATICAL SPACE
-        m.insert('\u{3000}', '　'); // IDEOGRAPHIC SPACE
-        m.insert('\u{008d}', ''); // REVERSE LINE FEED (standard LF looks like \n, this looks like a space)
-        m.insert('\u{009f}', ''); // <control>
-        m.insert('\u{0080}', ''); // C1 CONTROL CODES
-        m.insert('\u{0090}', ''); // DEVICE CONTROL STRING
-        m.insert('\u{009b}', ''); // CONTROL SEQUENCE INTRODUCER
-        m.insert('\u{0010}', ''); // ESCAPE, DATA LINK (not visible)
-        m.insert('\u{0009}', '\t'); // TAB (7 spaces based on print statement in Python interpreter)
-        m.insert('\u{0000}', '�'); // NULL
-        m.insert('\u{0003}', ''); // END OF TEXT
-        m.insert('\u{0004}', ''); // END OF TRANSMISSION
-        m.insert('\u{0017}', ''); // END OF TRANSMISSION BLOCK
-        m.insert('\u{0019}', ''); // END OF MEDIUM
-        m.insert('\u{0011}', ''); // DEVICE CONTROL ONE
-        m.insert('\u{0012}', ''); // DEVICE CONTROL TWO
-        m.insert('\u{0013}', ''); // DEVICE CONTROL THREE
-        m.insert('\u{0014}', ''); // DEVICE CONTROL FOUR
-        m.insert('\u{2060}', '⁠'); // WORD JOINER
-        m.insert('\u{2017}', '_'); // DOUBLE LOW LINE
-        m.insert('\u{2014}', '-'); // EM DASH
-        m.insert('\u{2013}', '-'); // EN DASH
-        m.insert('\u{2039}', '>'); // Single left-pointing angle quotation mark
-        m.insert('\u{203a}', '<'); // Single right-pointing angle quotation mark
-        m.insert('\u{203c}', '‼'); // Double exclamation mark
-        m.insert('\u{2028}', ' '); // Whitespace: Line Separator
-        m.insert('\u{2029}', ' '); // Whitespace: Paragraph Separator
-        m.insert('\u{2026}', '…'); // HORIZONTAL ELLIPSIS
-        m.insert('\u{2001}', ' '); // EM QUAD
-        m.insert('\u{200b}', '​'); // ZERO WIDTH SPACE
-        m.insert('\u{3001}', ','); // IDEOGRAPHIC COMMA
-        m.insert('\u{feff}', '﻿'); // ZERO WIDTH NO-BREAK SPACE
-        m.insert('\u{2022}', '-'); // BULLET
-        m
-    };
+    pub static ref SMART_ENCODING_MAP: HashMap<char, &'static str> = DEFAULT_SUBSTITUTIONS
+        .iter()
+        .map(|&(original, replacement, _)| (original, replacement))
+        .collect();
 }
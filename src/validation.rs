@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::encoded_char::Encoding;
+use crate::error::{Error, Sequence};
+use crate::national_language::LanguageShift;
+use crate::split_strategy::Atomicity;
+
+const ESCAPE: u16 = 0x1b;
+
+fn is_high_surrogate(code_unit: u16) -> bool {
+    (0xd800..0xdc00).contains(&code_unit)
+}
+
+fn is_low_surrogate(code_unit: u16) -> bool {
+    (0xdc00..0xe000).contains(&code_unit)
+}
+
+/// Checks that no boundary between `parts` cuts a GSM-7 escape sequence or a
+/// UTF-16 surrogate pair, nor a grapheme cluster unless `atomicity` allows it.
+/// `parts` holds the code units of each segment: septets for GSM-7, decoded
+/// through `language_shift`, or UTF-16 code units for UCS-2.
+pub fn validate_segmentation(
+    parts: &[Vec<u16>],
+    encoding: Encoding,
+    language_shift: LanguageShift,
+    atomicity: Atomicity,
+) -> Result<(), Error> {
+    match encoding {
+        Encoding::GSM7 => validate_escape_sequences(parts)?,
+        Encoding::UCS2 => validate_surrogate_pairs(parts)?,
+        Encoding::Auto => {
+            return Err(Error::InvalidOption(
+                "Segmentation can only be validated for GSM-7 or UCS-2".to_string(),
+            ))
+        }
+    }
+    if atomicity == Atomicity::GraphemeCluster {
+        let chars = match encoding {
+            Encoding::GSM7 => gsm7_chars(parts, language_shift),
+            _ => ucs2_chars(parts)?,
+        };
+        validate_grapheme_clusters(parts, &chars)?;
+    }
+    Ok(())
+}
+
+fn validate_escape_sequences(parts: &[Vec<u16>]) -> Result<(), Error> {
+    for (index, part) in parts.iter().enumerate() {
+        let mut position = 0;
+        while position < part.len() {
+            if part[position] == ESCAPE {
+                if position + 1 == part.len() {
+                    return Err(Error::SplitSequence {
+                        segment: index + 1,
+                        sequence: Sequence::EscapeSequence,
+                    });
+                }
+                position += 2;
+            } else {
+                position += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_surrogate_pairs(parts: &[Vec<u16>]) -> Result<(), Error> {
+    for (index, part) in parts.iter().enumerate() {
+        let ends_in_pair = part.last().copied().is_some_and(is_high_surrogate)
+            || parts
+                .get(index + 1)
+                .and_then(|next| next.first())
+                .copied()
+                .is_some_and(is_low_surrogate);
+        if ends_in_pair {
+            return Err(Error::SplitSequence {
+                segment: index + 1,
+                sequence: Sequence::SurrogatePair,
+            });
+        }
+    }
+    if parts
+        .first()
+        .and_then(|part| part.first())
+        .copied()
+        .is_some_and(is_low_surrogate)
+    {
+        return Err(Error::Decode(
+            "Segment 1 starts with an unpaired low surrogate".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// Each character with the number of code units it takes. Codes the tables
+// leave undefined stand for a replacement character of their own.
+fn gsm7_chars(parts: &[Vec<u16>], language_shift: LanguageShift) -> Vec<(char, usize)> {
+    let septets = parts.concat();
+    let mut chars = Vec::new();
+    let mut codes = septets.iter();
+
+    while let Some(&code) = codes.next() {
+        let (c, width) = match code {
+            ESCAPE => {
+                let extension_code = codes.next().copied().unwrap_or_default();
+                (
+                    language_shift.decode_extension_code(extension_code as u8),
+                    2,
+                )
+            }
+            _ => (language_shift.decode_code(code as u8), 1),
+        };
+        chars.push((c.unwrap_or(char::REPLACEMENT_CHARACTER), width));
+    }
+    chars
+}
+
+fn ucs2_chars(parts: &[Vec<u16>]) -> Result<Vec<(char, usize)>, Error> {
+    char::decode_utf16(parts.concat())
+        .map(|c| c.map(|c| (c, c.len_utf16())))
+        .collect::<Result<_, _>>()
+        .map_err(|_| Error::Decode("Segments do not hold valid UTF-16".to_string()))
+}
+
+// Line breaks count as two characters, like in `SegmentedMessage`, so a
+// boundary between "\r" and "\n" is allowed
+fn validate_grapheme_clusters(parts: &[Vec<u16>], chars: &[(char, usize)]) -> Result<(), Error> {
+    let message = chars.iter().map(|&(c, _)| c).collect::<String>();
+    let mut widths = chars.iter().map(|&(_, width)| width);
+
+    let mut boundaries = HashSet::from([0]);
+    let mut offset = 0;
+    for grapheme in message.graphemes(true) {
+        if grapheme == "\r\n" {
+            boundaries.insert(offset + 1);
+        }
+        offset += widths
+            .by_ref()
+            .take(grapheme.chars().count())
+            .sum::<usize>();
+        boundaries.insert(offset);
+    }
+
+    let mut offset = 0;
+    for (index, part) in parts.iter().enumerate() {
+        offset += part.len();
+        if !boundaries.contains(&offset) {
+            return Err(Error::SplitSequence {
+                segment: index + 1,
+                sequence: Sequence::GraphemeCluster,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::fmt;
+use std::ops::Range;
+
+use crate::segmented_message::LineBreakStyle;
+
+/// Invisible characters that still take up space in a segment
+pub const INVISIBLE_CHARACTERS: [char; 4] = [
+    '\u{00ad}', // SOFT HYPHEN
+    '\u{200b}', // ZERO WIDTH SPACE
+    '\u{2060}', // WORD JOINER
+    '\u{feff}', // ZERO WIDTH NO-BREAK SPACE (BOM)
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+}
+
+/// Part of the original message a warning refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub char_range: Range<usize>,
+    pub byte_range: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// A line break; `style` is `LF` or `CRLF`
+    LineBreak {
+        style: LineBreakStyle,
+        span: Span,
+    },
+    /// A character the GSM-7 alphabet can't represent, making the whole
+    /// message UCS-2
+    ForcesUcs2 {
+        grapheme: String,
+        span: Span,
+    },
+    /// A character smart encoding replaced with GSM-7 text
+    SmartEncodingReplacement {
+        original: char,
+        replacement: String,
+        span: Span,
+    },
+    InvisibleCharacter {
+        character: char,
+        span: Span,
+    },
+    /// Whitespace at the end of the message or of a line
+    TrailingWhitespace {
+        span: Span,
+    },
+    /// One more character would need another segment
+    NearSegmentBoundary {
+        segments: usize,
+    },
+    /// More segments than the carrier profile concatenates
+    TooManySegments {
+        segments: usize,
+        max_segments: usize,
+    },
+}
+
+impl Warning {
+    /// Stable identifier of the kind of warning
+    pub fn code(&self) -> &'static str {
+        match self {
+            Warning::LineBreak { .. } => "line-break",
+            Warning::ForcesUcs2 { .. } => "forces-ucs2",
+            Warning::SmartEncodingReplacement { .. } => "smart-encoding-replacement",
+            Warning::InvisibleCharacter { .. } => "invisible-character",
+            Warning::TrailingWhitespace { .. } => "trailing-whitespace",
+            Warning::NearSegmentBoundary { .. } => "near-segment-boundary",
+            Warning::TooManySegments { .. } => "too-many-segments",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Warning::LineBreak {
+                style: LineBreakStyle::LF,
+                ..
+            }
+            | Warning::SmartEncodingReplacement { .. }
+            | Warning::TrailingWhitespace { .. } => Severity::Info,
+            Warning::LineBreak { .. }
+            | Warning::ForcesUcs2 { .. }
+            | Warning::InvisibleCharacter { .. }
+            | Warning::NearSegmentBoundary { .. }
+            | Warning::TooManySegments { .. } => Severity::Warning,
+        }
+    }
+
+    /// Part of the message the warning refers to, absent for warnings about
+    /// the whole message
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Warning::LineBreak { span, .. }
+            | Warning::ForcesUcs2 { span, .. }
+            | Warning::SmartEncodingReplacement { span, .. }
+            | Warning::InvisibleCharacter { span, .. }
+            | Warning::TrailingWhitespace { span } => Some(span),
+            Warning::NearSegmentBoundary { .. } | Warning::TooManySegments { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::LineBreak {
+                style: LineBreakStyle::LF,
+                ..
+            } => write!(f, "Line break"),
+            Warning::LineBreak { .. } => write!(
+                f,
+                "CRLF line break, counted as two characters. The web page utility converts it to LF"
+            ),
+            Warning::ForcesUcs2 { grapheme, .. } => write!(
+                f,
+                "\"{}\" is not in the GSM-7 alphabet and makes the message UCS-2",
+                grapheme
+            ),
+            Warning::SmartEncodingReplacement {
+                original,
+                replacement,
+                ..
+            } => write!(f, "\"{}\" was replaced with \"{}\"", original, replacement),
+            Warning::InvisibleCharacter { character, .. } => write!(
+                f,
+                "Invisible character U+{:04X} takes up space",
+                *character as u32
+            ),
+            Warning::TrailingWhitespace { .. } => write!(f, "Trailing whitespace"),
+            Warning::NearSegmentBoundary { segments } => {
+                write!(f, "One more character would need segment {}", segments + 1)
+            }
+            Warning::TooManySegments {
+                segments,
+                max_segments,
+            } => write!(
+                f,
+                "The message needs {} segments, the carrier profile concatenates at most {}",
+                segments, max_segments
+            ),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+#![cfg(feature = "cli")]
+
+use pretty_assertions::assert_eq;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_calculate_message_segments"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.unwrap_or("").as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_text_output() {
+    let output = run(&["Hello world"], None);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("Message: \"Hello world\"\n"));
+    assert!(stdout.contains("Number of segments: 1\n"));
+    assert!(stdout.contains("Encoding used: GSM7\n"));
+}
+
+#[test]
+fn test_json_output_from_stdin_with_smart_encoding() {
+    let output = run(&["--smart", "--format", "json"], Some("It’s on\n"));
+    assert_eq!(output.status.code(), Some(0));
+
+    let summary: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(summary["message"], "It’s on");
+    assert_eq!(summary["encoding"], "GSM7");
+    assert_eq!(summary["segments"], 1);
+    assert_eq!(summary["warnings"][0]["code"], "smart-encoding-replacement");
+    assert_eq!(summary["warnings"][0]["severity"], "info");
+}
+
+#[test]
+fn test_csv_output() {
+    let output = run(
+        &["--format", "csv", "--encoding", "ucs2", "Hi, \"you\""],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "message,encoding,segments,characters,unicode_scalars,total_size_in_bits,\
+         message_size_in_bits,non_gsm_characters,warnings\n\
+         \"Hi, \"\"you\"\"\",UCS2,1,9,9,144,144,,\n"
+    );
+}
+
+#[test]
+fn test_yaml_output_from_file() {
+    let path = std::env::temp_dir().join("calculate_message_segments_test_message.txt");
+    std::fs::write(&path, "Café 😜\r\n").unwrap();
+    let output = run(
+        &["--file", path.to_str().unwrap(), "--format", "yaml"],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("message: Café 😜\n"));
+    assert!(stdout.contains("encoding: UCS2\n"));
+    assert!(stdout.contains("- 😜\n"));
+}
+
+#[test]
+fn test_exit_codes() {
+    let output = run(&["--encoding", "gsm7", "Hi 😜"], None);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("incompatible with GSM7"));
+
+    let path = std::env::temp_dir().join("calculate_message_segments_test_profile.toml");
+    std::fs::write(&path, "name = \"short\"\nmax_segments = 1\n").unwrap();
+    let message = "a".repeat(200);
+    let output = run(&["--profile", path.to_str().unwrap(), &message], None);
+    assert_eq!(output.status.code(), Some(4));
+
+    let output = run(&["--profile", "twilio", &message], None);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run(&["--profile", "no-such-profile", "Hi"], None);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run(&["--encoding", "utf8", "Hi"], None);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_batch_csv() {
+    let input = "id,text\n1,Hello “there”\n2,\"Hi 😜, you\"\n3\n";
+    let output = run(&["batch", "--smart", "--encoding", "gsm7"], Some(input));
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "id,text,encoding,segments,characters,non_gsm_characters,warnings,error\n\
+         1,Hello “there”,GSM7,1,13,,\
+         \"smart-encoding-replacement: \"\"“\"\" was replaced with \"\"\"\"\"\"; \
+         smart-encoding-replacement: \"\"”\"\" was replaced with \"\"\"\"\"\"\",\n\
+         2,\"Hi 😜, you\",,,,,,\"The string provided is incompatible with GSM7 encoding: \"\"😜\"\" at 3\"\n\
+         3,,,,,,,\"No \"\"text\"\" value\"\n"
+    );
+}
+
+#[test]
+fn test_batch_jsonl_keeps_row_order() {
+    let path = std::env::temp_dir().join("calculate_message_segments_test_batch.jsonl");
+    let input = (0..5000)
+        .map(|index| {
+            format!(
+                "{{\"id\":{},\"body\":\"{}\"}}\n",
+                index,
+                "a".repeat(index % 400)
+            )
+        })
+        .collect::<String>();
+    std::fs::write(&path, input).unwrap();
+
+    let output = run(
+        &[
+            "batch",
+            path.to_str().unwrap(),
+            "--column",
+            "body",
+            "--jobs",
+            "4",
+        ],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    let rows = stdout(&output)
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<serde_json::Value>>();
+    assert_eq!(rows.len(), 5000);
+    for (index, row) in rows.iter().enumerate() {
+        let characters = index % 400;
+        assert_eq!(row["id"], index);
+        assert_eq!(row["encoding"], "GSM7");
+        assert_eq!(row["characters"], characters);
+        assert_eq!(
+            row["segments"],
+            if characters <= 160 {
+                1
+            } else {
+                characters.div_ceil(153)
+            }
+        );
+    }
+}
+
+#[test]
+fn test_batch_invalid_input() {
+    let output = run(&["batch", "--column", "body"], Some("id,text\n1,Hi\n"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No \"body\" column"));
+
+    let output = run(
+        &["batch", "--input-format", "jsonl"],
+        Some("{\"text\":\"Hi\"}\n{bad\n"),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid JSON on line 2"));
+
+    let output = run(&["batch"], Some("text,segments\nHi,1\n"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("The CSV header already has a \"segments\" column"));
+
+    let output = run(
+        &["batch", "--input-format", "jsonl"],
+        Some("{\"text\":\"Hi\",\"error\":null}\n"),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Line 1 already has a \"error\" field")
+    );
+
+    let output = run(&["batch"], Some("id,text\n1,Hi\n2,Hi,extra\n"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Row on line 3 has 3 fields, but the header has 2"));
+}
+
+#[test]
+fn test_batch_jsonl_appends_to_rows() {
+    let output = run(
+        &["batch", "--input-format", "jsonl"],
+        Some("{\"text\":\"Hi\",\"id\":7}\n"),
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "{\"text\":\"Hi\",\"id\":7,\"encoding\":\"GSM7\",\"segments\":1,\"characters\":2,\
+         \"non_gsm_characters\":[],\"warnings\":[]}\n"
+    );
+}
+
+#[test]
+fn test_segments_view_without_a_terminal() {
+    let message = format!("Hi {{€}}\n{}", "a".repeat(160));
+    let output = run(
+        &[
+            "--format",
+            "segments",
+            "--profile",
+            "twilio",
+            "--smart",
+            &message,
+        ],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = stdout(&output);
+    let lines = stdout.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0]
+        .starts_with("Segment 1/2  [########################]  1119/1120 bits  UDH 05 00 03 "));
+    assert!(lines[0].ends_with(&format!(" 01  Hi {{€}}\\n{}", "a".repeat(143))));
+    assert!(lines[1]
+        .starts_with("Segment 2/2  [####....................]  167/1120 bits  UDH 05 00 03 "));
+    assert!(lines[1].ends_with(&format!(" 02  {}", "a".repeat(17))));
+    assert_eq!(
+        lines[2],
+        "GSM7, 2 segments: 953 bits free in the last segment (136 more characters)"
+    );
+    assert_eq!(
+        lines[3],
+        "Characters: 164 basic GSM-7, 3 GSM-7 extension (2 septets), 0 UCS-2"
+    );
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn test_segments_view_colors() {
+    let output = run(&["--format", "segments", "--color", "always", "€ 😜"], None);
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with(
+        "Segment 1/1  [██░░░░░░░░░░░░░░░░░░░░░░]  64/1120 bits  UDH \x1b[2m-\x1b[0m  "
+    ));
+    assert!(stdout.contains("\x1b[31m€\x1b[0m\x1b[31m \x1b[0m\x1b[31m😜\x1b[0m\n"));
+    assert!(stdout.ends_with(
+        "Characters: 0 basic GSM-7, \x1b[33m0 GSM-7 extension (2 septets)\x1b[0m, \x1b[31m3 UCS-2\x1b[0m\n"
+    ));
+
+    let output = run(&["--format", "segments", "--color", "always", "€ a"], None);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\x1b[33m€\x1b[0m a\n"));
+    assert!(stdout.ends_with(
+        "Characters: 2 basic GSM-7, \x1b[33m1 GSM-7 extension (2 septets)\x1b[0m, \x1b[31m0 UCS-2\x1b[0m\n"
+    ));
+}
+
+#[test]
+fn test_live_keystrokes_without_a_terminal() {
+    let output = run(&["live"], Some("Hi😜\u{7f}!"));
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "GSM7, 1 segment, 159 characters left in this segment\n\
+         GSM7, 1 segment, 158 characters left in this segment\n\
+         UCS2, 1 segment, 66 characters left in this segment, \"😜\" just switched the message to UCS-2\n\
+         GSM7, 1 segment, 158 characters left in this segment\n\
+         GSM7, 1 segment, 157 characters left in this segment\n"
+    );
+
+    let path = std::env::temp_dir().join("calculate_message_segments_test_no_euro.toml");
+    std::fs::write(&path, "name = \"no-euro\"\nrejected_characters = [\"€\"]\n").unwrap();
+    let output = run(&["live", "--profile", path.to_str().unwrap()], Some("€a"));
+    assert_eq!(
+        stdout(&output),
+        "UCS2, 1 segment, 69 characters left in this segment, \"€\" just switched the message to UCS-2\n\
+         UCS2, 1 segment, 68 characters left in this segment, UCS-2 because of \"€\"\n"
+    );
+
+    let path = std::env::temp_dir().join("calculate_message_segments_test_live_profile.toml");
+    std::fs::write(&path, "name = \"short\"\nmax_segments = 1\n").unwrap();
+    let output = run(
+        &["live", "--profile", path.to_str().unwrap()],
+        Some(&"a".repeat(161)),
+    );
+    assert_eq!(
+        stdout(&output).lines().last(),
+        Some("GSM7, 2 segments, 145 characters left in this segment, over the limit of 1 segment")
+    );
+}
@@ -9,8 +9,8 @@ mod basic_tests {
         body: &'static str,
         encoding: Encoding,
         segments: usize,
-        message_size: u16,
-        total_size: u16,
+        message_size: u32,
+        total_size: u32,
         characters: usize,
         unicode_scalars: usize,
     }
@@ -174,8 +174,8 @@ mod gsm7_escape_character_tests {
         message: &str,
         expected_encoding: Encoding,
         expected_segments: usize,
-        expected_message_size: u16,
-        expected_total_size: u16,
+        expected_message_size: u32,
+        expected_total_size: u32,
     ) {
         let segmented_message = SegmentedMessage::new(message, Encoding::Auto, false).unwrap();
 
@@ -381,3 +381,3309 @@ mod segments_analysis_tests {
         assert_eq!(last_segment.data.last().unwrap().raw(), "4");
     }
 }
+
+mod national_language_tests {
+    use super::*;
+    use message_segment_calculator::{LanguageShift, NationalLanguage};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_national_languages_are_opt_in() {
+        let test_message = "Günaydın, nasılsın?";
+        let segmented_message = SegmentedMessage::new(test_message, Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.get_encoding_name(), Encoding::UCS2);
+        assert!(segmented_message.language_shift.is_default());
+    }
+
+    #[test]
+    fn test_turkish_locking_shift() {
+        let test_message = "Günaydın, nasılsın? Çok güzel bir gün.";
+        let segmented_message = SegmentedMessage::with_national_languages(
+            test_message,
+            Encoding::Auto,
+            false,
+            &[NationalLanguage::Turkish],
+        )
+        .unwrap();
+        assert_eq!(segmented_message.get_encoding_name(), Encoding::GSM7);
+        assert_eq!(
+            segmented_message.language_shift,
+            LanguageShift::new(Some(NationalLanguage::Turkish), None)
+        );
+        assert_eq!(segmented_message.segments_count(), 1);
+        assert_eq!(segmented_message.message_size(), 38 * 7);
+        assert_eq!(segmented_message.total_size(), 32 + 38 * 7);
+    }
+
+    #[test]
+    fn test_spanish_single_shift() {
+        let test_message = "Cómo está usted? Muy bien.";
+        let segmented_message = SegmentedMessage::with_national_languages(
+            test_message,
+            Encoding::Auto,
+            false,
+            &[NationalLanguage::Spanish],
+        )
+        .unwrap();
+        assert_eq!(segmented_message.get_encoding_name(), Encoding::GSM7);
+        assert_eq!(
+            segmented_message.language_shift,
+            LanguageShift::new(None, Some(NationalLanguage::Spanish))
+        );
+        assert_eq!(segmented_message.number_of_characters, 28);
+        assert_eq!(segmented_message.total_size(), 32 + 28 * 7);
+    }
+
+    #[test]
+    fn test_hindi_tables() {
+        let test_message = "नमस्ते दुनिया";
+        let default_message = SegmentedMessage::new(test_message, Encoding::Auto, false).unwrap();
+        assert_eq!(default_message.get_encoding_name(), Encoding::UCS2);
+
+        let segmented_message = SegmentedMessage::with_national_languages(
+            test_message,
+            Encoding::Auto,
+            false,
+            &NationalLanguage::ALL,
+        )
+        .unwrap();
+        assert_eq!(segmented_message.get_encoding_name(), Encoding::GSM7);
+        assert_eq!(
+            segmented_message.language_shift,
+            LanguageShift::new(Some(NationalLanguage::Hindi), None)
+        );
+        assert_eq!(segmented_message.message_size(), 13 * 7);
+        assert_eq!(segmented_message.total_size(), 32 + 13 * 7);
+
+        // The danda and Devanagari digits are in the single shift table
+        let segmented_message = SegmentedMessage::with_national_languages(
+            "नमस्ते। १२३",
+            Encoding::Auto,
+            false,
+            &[NationalLanguage::Hindi],
+        )
+        .unwrap();
+        assert_eq!(
+            segmented_message.language_shift,
+            LanguageShift::new(Some(NationalLanguage::Hindi), Some(NationalLanguage::Hindi))
+        );
+        assert_eq!(segmented_message.message_size(), (6 + 4 * 2 + 1) * 7);
+        assert_eq!(
+            NationalLanguage::from_language_id(0x06),
+            Some(NationalLanguage::Hindi)
+        );
+    }
+
+    #[test]
+    fn test_cheapest_combination_is_selected() {
+        // '€' is an extension character in the default alphabet but a basic
+        // character in the Portuguese locking shift table
+        let test_message = "€".repeat(81);
+        let segmented_message = SegmentedMessage::with_national_languages(
+            &test_message,
+            Encoding::Auto,
+            false,
+            &[NationalLanguage::Spanish, NationalLanguage::Portuguese],
+        )
+        .unwrap();
+        assert_eq!(
+            segmented_message.language_shift,
+            LanguageShift::new(Some(NationalLanguage::Portuguese), None)
+        );
+        assert_eq!(segmented_message.segments_count(), 1);
+
+        let default_message = SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        assert_eq!(default_message.segments_count(), 2);
+    }
+
+    #[test]
+    fn test_shift_table_header_in_every_segment() {
+        let one_segment = "ı".repeat(155);
+        let segmented_message = SegmentedMessage::with_national_languages(
+            &one_segment,
+            Encoding::Auto,
+            false,
+            &[NationalLanguage::Turkish],
+        )
+        .unwrap();
+        assert_eq!(segmented_message.segments_count(), 1);
+        assert_eq!(segmented_message.total_size(), 32 + 155 * 7);
+
+        let two_segments = "ı".repeat(156);
+        let segmented_message = SegmentedMessage::with_national_languages(
+            &two_segments,
+            Encoding::Auto,
+            false,
+            &[NationalLanguage::Turkish],
+        )
+        .unwrap();
+        assert_eq!(segmented_message.segments_count(), 2);
+        assert_eq!(segmented_message.message_size(), 156 * 7);
+        assert_eq!(segmented_message.total_size(), 2 * 72 + 156 * 7);
+        for segment in &segmented_message.segments {
+            assert_eq!(segment.header_octets(), 9);
+        }
+    }
+
+    #[test]
+    fn test_gsm7_incompatible_with_national_languages() {
+        let test_message = "Günaydın 😀";
+        let result = SegmentedMessage::with_national_languages(
+            test_message,
+            Encoding::GSM7,
+            false,
+            &[NationalLanguage::Turkish],
+        );
+        assert!(result.is_err());
+    }
+}
+
+mod packing_tests {
+    use super::*;
+    use message_segment_calculator::{
+        decode_gsm7, decode_ucs2, fill_bits, pack_septets, unpack_septets, Error, LanguageShift,
+        NationalLanguage,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_pack_septets() {
+        let septets = "hellohello".bytes().collect::<Vec<u8>>();
+        assert_eq!(
+            pack_septets(&septets, 0),
+            vec![0xe8, 0x32, 0x9b, 0xfd, 0x46, 0x97, 0xd9, 0xec, 0x37]
+        );
+    }
+
+    #[test]
+    fn test_unpack_septets() {
+        let octets = [0xe8, 0x32, 0x9b, 0xfd, 0x46, 0x97, 0xd9, 0xec, 0x37];
+        let septets = unpack_septets(&octets, 10, 0).unwrap();
+        assert_eq!(septets, "hellohello".bytes().collect::<Vec<u8>>());
+        assert!(unpack_septets(&octets, 11, 0).is_err());
+    }
+
+    #[test]
+    fn test_fill_bits() {
+        assert_eq!(fill_bits(0), 0);
+        assert_eq!(fill_bits(6), 1);
+        assert_eq!(fill_bits(7), 0);
+        assert_eq!(fill_bits(4), 3);
+    }
+
+    #[test]
+    fn test_round_trip_with_fill_bits() {
+        let septets = "Hello world".bytes().collect::<Vec<u8>>();
+        for fill in 0..7 {
+            let octets = pack_septets(&septets, fill);
+            assert_eq!(octets[0] & ((1 << fill) - 1), 0);
+            assert_eq!(
+                unpack_septets(&octets, septets.len(), fill).unwrap(),
+                septets
+            );
+        }
+    }
+
+    #[test]
+    fn test_segmented_message_round_trip() {
+        let test_message = "Price: 10€ {approx} ".repeat(10);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.segments_count(), 2);
+
+        let decoded = segmented_message
+            .packed_user_data()
+            .unwrap()
+            .iter()
+            .zip(&segmented_message.segments)
+            .map(|(octets, segment)| {
+                decode_gsm7(
+                    octets,
+                    segment.septets().len(),
+                    segment.fill_bits(),
+                    LanguageShift::default(),
+                )
+                .unwrap()
+            })
+            .collect::<String>();
+        assert_eq!(decoded, test_message);
+    }
+
+    #[test]
+    fn test_national_language_round_trip() {
+        let test_message = "Günaydın, nasılsın? Şimdi {ğ}";
+        let segmented_message = SegmentedMessage::with_national_languages(
+            test_message,
+            Encoding::Auto,
+            false,
+            &[NationalLanguage::Turkish],
+        )
+        .unwrap();
+        let segment = &segmented_message.segments[0];
+        assert_eq!(segment.fill_bits(), fill_bits(segment.header_octets()));
+
+        let decoded = decode_gsm7(
+            &segment.packed_user_data(),
+            segment.septets().len(),
+            segment.fill_bits(),
+            segmented_message.language_shift,
+        )
+        .unwrap();
+        assert_eq!(decoded, test_message);
+    }
+
+    #[test]
+    fn test_undefined_extension_code_falls_back_to_basic_table() {
+        // 0x1b 0x41 is not defined in the extension table and is shown as 'A'
+        let octets = pack_septets(&[0x1b, 0x41, 0x1b, 0x65], 0);
+        assert_eq!(
+            decode_gsm7(&octets, 4, 0, LanguageShift::default()).unwrap(),
+            "A€"
+        );
+    }
+
+    #[test]
+    fn test_ucs2_messages_cannot_be_packed() {
+        let segmented_message = SegmentedMessage::new("😀", Encoding::Auto, false).unwrap();
+        assert!(segmented_message.packed_user_data().is_err());
+    }
+
+    #[test]
+    fn test_decode_ucs2() {
+        assert_eq!(
+            decode_ucs2(&[0x00, 0x48, 0xd8, 0x3d, 0xde, 0x00]).unwrap(),
+            "H😀"
+        );
+        assert!(matches!(
+            decode_ucs2(&[0x00, 0x48, 0x00]),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(decode_ucs2(&[0xd8, 0x3d]), Err(Error::Decode(_))));
+    }
+}
+
+mod user_data_header_tests {
+    use super::*;
+    use message_segment_calculator::{
+        Concatenation, InformationElement, LanguageShift, NationalLanguage, ReferenceWidth,
+        UserDataHeader,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_serialize_8_bit_concatenation() {
+        let mut header = UserDataHeader::new();
+        header.set_concatenation(Concatenation::new(ReferenceWidth::EightBit, 0x1a2b, 3, 2));
+        assert_eq!(header.to_bytes(), vec![0x05, 0x00, 0x03, 0x2b, 0x03, 0x02]);
+        assert_eq!(header.len(), 6);
+    }
+
+    #[test]
+    fn test_serialize_16_bit_concatenation() {
+        let mut header = UserDataHeader::new();
+        header.set_concatenation(Concatenation::new(ReferenceWidth::SixteenBit, 0x1a2b, 3, 2));
+        assert_eq!(
+            header.to_bytes(),
+            vec![0x06, 0x08, 0x04, 0x1a, 0x2b, 0x03, 0x02]
+        );
+        assert_eq!(header.len(), 7);
+    }
+
+    #[test]
+    fn test_empty_header() {
+        let header = UserDataHeader::new();
+        assert!(header.is_empty());
+        assert_eq!(header.len(), 0);
+        assert_eq!(header.to_bytes(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let bytes = [
+            0x08, 0x00, 0x03, 0x7f, 0x02, 0x01, 0x25, 0x01, 0x01, 0x41, 0x42,
+        ];
+        let header = UserDataHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(header.len(), 9);
+        assert_eq!(
+            header.concatenation(),
+            Some(Concatenation::new(ReferenceWidth::EightBit, 0x7f, 2, 1))
+        );
+        assert_eq!(
+            header.language_shift(),
+            LanguageShift::new(Some(NationalLanguage::Turkish), None)
+        );
+        assert_eq!(header.to_bytes(), bytes[..9].to_vec());
+    }
+
+    #[test]
+    fn test_parse_unknown_information_element() {
+        let header = UserDataHeader::from_bytes(&[0x04, 0x70, 0x02, 0xaa, 0xbb]).unwrap();
+        assert_eq!(
+            header.information_elements,
+            vec![InformationElement::new(0x70, vec![0xaa, 0xbb])]
+        );
+        assert_eq!(header.concatenation(), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_header() {
+        assert!(UserDataHeader::from_bytes(&[]).is_err());
+        assert!(UserDataHeader::from_bytes(&[0x05, 0x00, 0x03]).is_err());
+        assert!(UserDataHeader::from_bytes(&[0x03, 0x00, 0x03, 0x01]).is_err());
+    }
+
+    #[test]
+    fn test_segments_are_numbered() {
+        let test_message = "1".repeat(307);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_concatenation_reference(0x42);
+        assert_eq!(segmented_message.concatenation_reference(), Some(0x42));
+
+        for (index, segment) in segmented_message.segments.iter().enumerate() {
+            let header_octets = segment
+                .data
+                .iter()
+                .take(6)
+                .map(|c| c.size_in_bits())
+                .sum::<u16>();
+            assert_eq!(header_octets, 48);
+            assert_eq!(
+                segment.user_data_header.to_bytes(),
+                vec![0x05, 0x00, 0x03, 0x42, 0x03, index as u8 + 1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_single_segment_has_no_concatenation() {
+        let segmented_message = SegmentedMessage::new("Hello", Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.concatenation_reference(), None);
+        assert!(segmented_message.segments[0].user_data_header.is_empty());
+    }
+
+    #[test]
+    fn test_16_bit_reference_capacity() {
+        let test_message = "1".repeat(306);
+        let eight_bit = SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        assert_eq!(eight_bit.segments_count(), 2);
+        assert_eq!(eight_bit.segments[0].message_size_in_bits(), 153 * 7);
+
+        let sixteen_bit = eight_bit.with_reference_width(ReferenceWidth::SixteenBit);
+        assert_eq!(sixteen_bit.segments_count(), 3);
+        assert_eq!(sixteen_bit.segments[0].message_size_in_bits(), 152 * 7);
+        assert_eq!(sixteen_bit.segments[0].header_octets(), 7);
+        assert_eq!(
+            sixteen_bit.segments[2]
+                .user_data_header
+                .concatenation()
+                .unwrap()
+                .sequence,
+            3
+        );
+    }
+}
+
+mod pdu_tests {
+    use super::*;
+    use message_segment_calculator::pdu::{
+        decode_hex, encode_hex, Address, DataCoding, ReceivedPdu, SmsDeliver, SmsStatusReport,
+        SmsSubmit, TimeStamp, ValidityPeriod,
+    };
+    use message_segment_calculator::Error;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode_sms_submit() {
+        let segmented_message = SegmentedMessage::new("hellohello", Encoding::Auto, false).unwrap();
+        let mut pdus =
+            SmsSubmit::from_segmented_message(&segmented_message, "+46708251358", 0).unwrap();
+        assert_eq!(pdus.len(), 1);
+
+        pdus[0].validity_period = Some(ValidityPeriod::Relative(0xaa));
+        assert_eq!(
+            pdus[0].to_hex().unwrap(),
+            "11000B916407281553F80000AA0AE8329BFD4697D9EC37"
+        );
+    }
+
+    #[test]
+    fn test_decode_sms_submit() {
+        let pdu = SmsSubmit::from_hex("11000B916407281553F80000AA0AE8329BFD4697D9EC37").unwrap();
+        assert_eq!(pdu.destination_address.to_string(), "+46708251358");
+        assert_eq!(pdu.validity_period, Some(ValidityPeriod::Relative(0xaa)));
+        assert_eq!(
+            pdu.decode_user_data().unwrap().text.as_deref(),
+            Some("hellohello")
+        );
+    }
+
+    #[test]
+    fn test_decode_sms_deliver() {
+        let pdu = SmsDeliver::from_hex("040BC87238880900F10000993092516195800AE8329BFD4697D9EC37")
+            .unwrap();
+        assert_eq!(pdu.originating_address.value, "27838890001");
+        assert!(!pdu.more_messages_to_send);
+        assert_eq!(
+            pdu.service_centre_time_stamp,
+            TimeStamp {
+                year: 99,
+                month: 3,
+                day: 29,
+                hour: 15,
+                minute: 16,
+                second: 59,
+                time_zone: 8,
+            }
+        );
+        assert_eq!(pdu.text(), Some("hellohello"));
+        assert_eq!(pdu.user_data.user_data_header, None);
+    }
+
+    #[test]
+    fn test_decode_status_report() {
+        let hex = "062A0B916407281553F899309251619580993092516195800000";
+        let report = SmsStatusReport::from_hex(hex).unwrap();
+        assert_eq!(report.message_reference, 0x2a);
+        assert_eq!(report.recipient_address.to_string(), "+46708251358");
+        assert_eq!(report.discharge_time.second, 59);
+        assert!(report.is_delivered());
+
+        match ReceivedPdu::from_hex(hex).unwrap() {
+            ReceivedPdu::StatusReport(received) => assert_eq!(received, report),
+            other => panic!("Expected a status report, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_multipart_gsm7_round_trip() {
+        let test_message = "Concatenated [GSM-7] message ".repeat(8);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_concatenation_reference(0x17);
+        let pdus = SmsSubmit::from_segmented_message(&segmented_message, "5551234", 0x10).unwrap();
+        assert_eq!(pdus.len(), segmented_message.segments_count());
+
+        let mut decoded = String::new();
+        for (index, pdu) in pdus.iter().enumerate() {
+            assert_eq!(pdu.message_reference, 0x10 + index as u8);
+            let parsed = SmsSubmit::from_hex(&pdu.to_hex().unwrap()).unwrap();
+            assert_eq!(&parsed, pdu);
+
+            let user_data = parsed.decode_user_data().unwrap();
+            let concatenation = user_data.user_data_header.unwrap().concatenation().unwrap();
+            assert_eq!(concatenation.reference, 0x17);
+            assert_eq!(concatenation.sequence, index as u8 + 1);
+            decoded.push_str(&user_data.text.unwrap());
+        }
+        assert_eq!(decoded, test_message);
+    }
+
+    #[test]
+    fn test_deliver_with_user_data_header_and_ucs2() {
+        let test_message = "Привет! ".repeat(12);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.get_encoding_name(), Encoding::UCS2);
+        assert_eq!(segmented_message.segments_count(), 2);
+
+        let mut decoded = String::new();
+        for segment in &segmented_message.segments {
+            let mut pdu = vec![0x44];
+            pdu.extend(Address::new("+46708251358").to_bytes().unwrap());
+            pdu.extend([0x00, DataCoding::UCS2.data_coding_scheme()]);
+            pdu.extend([0x99, 0x30, 0x92, 0x51, 0x61, 0x95, 0x80]);
+            pdu.push(segment.user_data_length(Encoding::UCS2) as u8);
+            pdu.extend(segment.user_data(Encoding::UCS2));
+
+            let deliver = SmsDeliver::from_hex(&encode_hex(&pdu)).unwrap();
+            assert!(deliver.user_data.user_data_header.is_some());
+            decoded.push_str(deliver.text().unwrap());
+        }
+        assert_eq!(decoded, test_message);
+    }
+
+    #[test]
+    fn test_alphanumeric_address() {
+        let address = Address::new("Twilio");
+        assert!(address.is_alphanumeric());
+        let bytes = address.to_bytes().unwrap();
+        assert_eq!(Address::from_bytes(&bytes).unwrap(), address);
+    }
+
+    #[test]
+    fn test_address_length_limits() {
+        let address = Address::new(&"1".repeat(20));
+        let bytes = address.to_bytes().unwrap();
+        assert_eq!(bytes[0], 20);
+        assert_eq!(Address::from_bytes(&bytes).unwrap(), address);
+        assert_eq!(
+            Address::new(&format!("+{}", "1".repeat(21))).to_bytes(),
+            Err(Error::Encode(format!(
+                "Address \"{}\" has 21 digits, at most 20 fit",
+                "1".repeat(21)
+            )))
+        );
+
+        let address = Address::new("TwilioSMSAB");
+        let bytes = address.to_bytes().unwrap();
+        assert_eq!(bytes[0], 20);
+        assert_eq!(Address::from_bytes(&bytes).unwrap(), address);
+        assert_eq!(
+            Address::new("TwilioSMSABC").to_bytes(),
+            Err(Error::Encode(
+                "Address \"TwilioSMSABC\" needs 12 septets, at most 11 fit".to_string()
+            ))
+        );
+        assert!(Address::new("Twilio{SMS}").to_bytes().is_err());
+    }
+
+    #[test]
+    fn test_data_coding_scheme() {
+        assert_eq!(
+            DataCoding::from_data_coding_scheme(0x00).unwrap(),
+            DataCoding::GSM7
+        );
+        assert_eq!(
+            DataCoding::from_data_coding_scheme(0x08).unwrap(),
+            DataCoding::UCS2
+        );
+        assert_eq!(
+            DataCoding::from_data_coding_scheme(0xf4).unwrap(),
+            DataCoding::EightBit
+        );
+        assert!(DataCoding::from_data_coding_scheme(0x0c).is_err());
+    }
+
+    #[test]
+    fn test_truncated_pdu() {
+        assert!(SmsDeliver::from_hex("040BC8723888").is_err());
+        assert!(SmsSubmit::from_hex("1100").is_err());
+        assert!(ReceivedPdu::from_hex("").is_err());
+    }
+
+    #[test]
+    fn test_invalid_hex() {
+        assert_eq!(decode_hex("0A ff").unwrap(), vec![0x0a, 0xff]);
+        assert_eq!(
+            decode_hex("aé1"),
+            Err(Error::Decode("Invalid hex digit 'é'".to_string()))
+        );
+        assert!(decode_hex("0A0").is_err());
+    }
+
+    #[test]
+    fn test_deliver_with_smsc() {
+        let hex = "07911326040000F0040B911346610089F60000208062917314080CC8F71D14969741F977FD07";
+        assert!(ReceivedPdu::from_hex(hex).is_err());
+
+        let (service_centre, pdu) = SmsDeliver::from_hex_with_smsc(hex).unwrap();
+        assert_eq!(service_centre.unwrap().to_string(), "+31624000000");
+        assert_eq!(pdu.originating_address.to_string(), "+31641600986");
+        assert_eq!(pdu.text(), Some("How are you?"));
+
+        match ReceivedPdu::from_hex_with_smsc(hex).unwrap() {
+            (_, ReceivedPdu::Deliver(received)) => assert_eq!(received, pdu),
+            other => panic!("Expected an SMS-DELIVER, got {:?}", other),
+        }
+
+        let (service_centre, _) =
+            ReceivedPdu::from_hex_with_smsc(&format!("00{}", &hex[16..])).unwrap();
+        assert_eq!(service_centre, None);
+    }
+
+    #[test]
+    fn test_submit_with_smsc() {
+        let segmented_message = SegmentedMessage::new("hellohello", Encoding::Auto, false).unwrap();
+        let pdu = SmsSubmit::from_segmented_message(&segmented_message, "+46708251358", 0)
+            .unwrap()
+            .remove(0);
+        let tpdu = pdu.to_hex().unwrap();
+        assert_eq!(pdu.to_hex_with_smsc(None).unwrap(), format!("00{}", tpdu));
+
+        let service_centre = Address::new("+31624000000");
+        let hex = pdu.to_hex_with_smsc(Some(&service_centre)).unwrap();
+        assert_eq!(hex, format!("07911326040000F0{}", tpdu));
+        assert_eq!(
+            SmsSubmit::from_hex_with_smsc(&hex).unwrap(),
+            (Some(service_centre), pdu.clone())
+        );
+        assert!(pdu.to_hex_with_smsc(Some(&Address::new("Twilio"))).is_err());
+    }
+}
+
+mod smpp_tests {
+    use super::*;
+    use message_segment_calculator::smpp::{
+        ConcatenationMethod, DataSm, DeliverSm, SmppAddress, SubmitSm, Tlv, DATA_CODING_DEFAULT,
+        DATA_CODING_UCS2, ESM_CLASS_UDHI, TAG_MESSAGE_PAYLOAD,
+    };
+    use message_segment_calculator::Error;
+    use pretty_assertions::assert_eq;
+
+    fn round_trip(bodies: &[SubmitSm]) -> String {
+        bodies
+            .iter()
+            .map(|body| {
+                let parsed = SubmitSm::from_bytes(&body.to_bytes().unwrap()).unwrap();
+                assert_eq!(&parsed, body);
+                parsed.decode_message().unwrap().text.unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_submit_sm_body() {
+        let segmented_message = SegmentedMessage::new("Hi", Encoding::Auto, false).unwrap();
+        let bodies = SubmitSm::from_segmented_message(
+            &segmented_message,
+            "12345",
+            "+15551234567",
+            ConcatenationMethod::UserDataHeader,
+        )
+        .unwrap();
+        assert_eq!(bodies.len(), 1);
+
+        let mut expected = vec![0x00, 0x00, 0x01];
+        expected.extend(b"12345\0");
+        expected.extend([0x01, 0x01]);
+        expected.extend(b"15551234567\0");
+        expected.extend([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        expected.extend([0x02, b'H', b'i']);
+        assert_eq!(bodies[0].to_bytes().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_user_data_header_concatenation() {
+        let test_message = "SMPP {concatenation} test ".repeat(10);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_concatenation_reference(0x33);
+        let bodies = SubmitSm::from_segmented_message(
+            &segmented_message,
+            "Twilio",
+            "15551234567",
+            ConcatenationMethod::UserDataHeader,
+        )
+        .unwrap();
+        assert_eq!(bodies.len(), segmented_message.segments_count());
+
+        for (index, body) in bodies.iter().enumerate() {
+            assert_eq!(body.esm_class & ESM_CLASS_UDHI, ESM_CLASS_UDHI);
+            assert_eq!(body.data_coding, DATA_CODING_DEFAULT);
+            assert_eq!(
+                body.short_message[..6],
+                [0x05, 0x00, 0x03, 0x33, bodies.len() as u8, index as u8 + 1]
+            );
+            assert!(body.tlvs.is_empty());
+        }
+        assert_eq!(round_trip(&bodies), test_message);
+    }
+
+    #[test]
+    fn test_sar_concatenation() {
+        let test_message = "Сообщение из нескольких частей. ".repeat(4);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_concatenation_reference(0x44);
+        let bodies = SubmitSm::from_segmented_message(
+            &segmented_message,
+            "Twilio",
+            "15551234567",
+            ConcatenationMethod::SarTlv,
+        )
+        .unwrap();
+        assert_eq!(bodies.len(), 2);
+
+        for (index, body) in bodies.iter().enumerate() {
+            assert_eq!(body.esm_class, 0);
+            assert_eq!(body.data_coding, DATA_CODING_UCS2);
+            let decoded = SubmitSm::from_bytes(&body.to_bytes().unwrap())
+                .unwrap()
+                .decode_message()
+                .unwrap();
+            assert_eq!(decoded.user_data_header, None);
+            assert_eq!(decoded.sar, Some((0x44, 2, index as u8 + 1)));
+        }
+        assert_eq!(round_trip(&bodies), test_message);
+    }
+
+    #[test]
+    fn test_message_payload() {
+        let test_message = "1234567890".repeat(40);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        let bodies = SubmitSm::from_segmented_message(
+            &segmented_message,
+            "12345",
+            "15551234567",
+            ConcatenationMethod::MessagePayload,
+        )
+        .unwrap();
+        assert_eq!(bodies.len(), 1);
+        assert!(bodies[0].short_message.is_empty());
+        assert_eq!(bodies[0].tlvs[0].tag, TAG_MESSAGE_PAYLOAD);
+        assert_eq!(bodies[0].tlvs[0].value.len(), 400);
+        assert_eq!(round_trip(&bodies), test_message);
+    }
+
+    #[test]
+    fn test_data_sm() {
+        let test_message = "data_sm carries {everything} in message_payload".repeat(5);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        let body =
+            DataSm::from_segmented_message(&segmented_message, "12345", "15551234567").unwrap();
+
+        let parsed = DataSm::from_bytes(&body.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed, body);
+        assert_eq!(parsed.decode_message().unwrap().text.unwrap(), test_message);
+    }
+
+    #[test]
+    fn test_tlv_too_long() {
+        let segmented_message = SegmentedMessage::new("Hello", Encoding::Auto, false).unwrap();
+        let mut body =
+            DataSm::from_segmented_message(&segmented_message, "12345", "15551234567").unwrap();
+        body.tlvs = vec![Tlv::new(TAG_MESSAGE_PAYLOAD, vec![0x41; 65535])];
+        assert!(body.to_bytes().is_ok());
+
+        body.tlvs = vec![Tlv::new(TAG_MESSAGE_PAYLOAD, vec![0x41; 65536])];
+        assert_eq!(
+            body.to_bytes(),
+            Err(Error::Encode(
+                "TLV 0x0424 has 65536 octets, at most 65535 fit".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_smpp_address() {
+        assert_eq!(
+            SmppAddress::new("+15551234567"),
+            SmppAddress {
+                ton: 0x01,
+                npi: 0x01,
+                address: "15551234567".to_string(),
+            }
+        );
+        assert_eq!(
+            (SmppAddress::new("12345").ton, SmppAddress::new("12345").npi),
+            (0x00, 0x01)
+        );
+        assert_eq!(
+            (
+                SmppAddress::new("Twilio").ton,
+                SmppAddress::new("Twilio").npi
+            ),
+            (0x05, 0x00)
+        );
+    }
+
+    #[test]
+    fn test_parse_deliver_sm() {
+        let mut bytes = vec![0x00, 0x01, 0x01];
+        bytes.extend(b"15551234567\0");
+        bytes.extend([0x00, 0x00]);
+        bytes.extend(b"12345\0");
+        bytes.extend([0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        let short_message = [0x05, 0x00, 0x03, 0x01, 0x02, 0x02, b'O', b'K', 0x1b, 0x65];
+        bytes.push(short_message.len() as u8);
+        bytes.extend(short_message);
+
+        let deliver = DeliverSm::from_bytes(&bytes).unwrap();
+        assert_eq!(deliver.source.address, "15551234567");
+        let decoded = deliver.decode_message().unwrap();
+        assert_eq!(decoded.text.as_deref(), Some("OK€"));
+        assert_eq!(
+            decoded
+                .user_data_header
+                .unwrap()
+                .concatenation()
+                .unwrap()
+                .sequence,
+            2
+        );
+    }
+
+    #[test]
+    fn test_truncated_body() {
+        assert!(DeliverSm::from_bytes(&[0x00, 0x01]).is_err());
+        assert!(DataSm::from_bytes(&[]).is_err());
+    }
+}
+
+mod reassembly_tests {
+    use super::*;
+    use message_segment_calculator::pdu::{encode_hex, Address, SmsDeliver};
+    use message_segment_calculator::{
+        Error, ReassembledMessage, Reassembler, ReassemblyStatus, ReceivedSegment, ReferenceWidth,
+    };
+    use pretty_assertions::assert_eq;
+    use std::time::{Duration, Instant};
+
+    fn received_segments(message: &str, originator: &str, reference: u16) -> Vec<ReceivedSegment> {
+        received_segments_with_width(message, originator, reference, ReferenceWidth::EightBit)
+    }
+
+    fn received_segments_with_width(
+        message: &str,
+        originator: &str,
+        reference: u16,
+        reference_width: ReferenceWidth,
+    ) -> Vec<ReceivedSegment> {
+        let segmented_message = SegmentedMessage::new(message, Encoding::Auto, false)
+            .unwrap()
+            .with_reference_width(reference_width)
+            .with_concatenation_reference(reference);
+        let encoding = segmented_message.get_encoding_name();
+
+        segmented_message
+            .segments
+            .iter()
+            .map(|segment| {
+                let user_data = segment.user_data(encoding);
+                let payload = match encoding {
+                    Encoding::GSM7 => segment.septets(),
+                    _ => user_data[segment.header_octets()..].to_vec(),
+                };
+                ReceivedSegment::new(
+                    originator,
+                    &user_data[..segment.header_octets()],
+                    payload,
+                    encoding,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_single_part_is_complete() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        let segment = received_segments("Hello", "+15551234567", 1).remove(0);
+        assert_eq!(
+            reassembler.push(segment).unwrap(),
+            ReassemblyStatus::Complete(ReassembledMessage {
+                originator: "+15551234567".to_string(),
+                reference: None,
+                total: 1,
+                encoding: Encoding::GSM7,
+                text: "Hello".to_string(),
+            })
+        );
+        assert_eq!(reassembler.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_undecodable_group_is_returned() {
+        let mut segments = received_segments(&"😜".repeat(80), "+15551234567", 4);
+        assert_eq!(segments.len(), 3);
+        // End the message with an unpaired high surrogate
+        let last = segments[2].payload.len();
+        segments[2].payload[last - 2..].copy_from_slice(&[0xd8, 0x3d]);
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        for segment in &segments[..2] {
+            reassembler.push(segment.clone()).unwrap();
+        }
+        match reassembler.push(segments[2].clone()).unwrap() {
+            ReassemblyStatus::Undecodable { parts, error } => {
+                assert_eq!(parts, segments);
+                assert!(matches!(error, Error::Decode(_)));
+            }
+            other => panic!("Expected an undecodable message, got {:?}", other),
+        }
+        assert_eq!(reassembler.pending_count(), 0);
+
+        // A part sent again starts a new group
+        assert_eq!(
+            reassembler.push(segments[0].clone()).unwrap(),
+            ReassemblyStatus::Pending {
+                received: 1,
+                missing: vec![2, 3],
+            }
+        );
+        assert_eq!(
+            reassembler.missing_parts("+15551234567", ReferenceWidth::EightBit, 4, 3),
+            Some(vec![2, 3])
+        );
+    }
+
+    #[test]
+    fn test_mismatched_parts_are_rejected() {
+        let gsm7 = received_segments(&"a".repeat(200), "+15551234567", 5);
+        let ucs2 = received_segments(&"😜".repeat(40), "+15551234567", 5);
+        assert_eq!((gsm7.len(), ucs2.len()), (2, 2));
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        reassembler.push(gsm7[0].clone()).unwrap();
+        assert!(matches!(
+            reassembler.push(ucs2[1].clone()),
+            Err(Error::Decode(_))
+        ));
+        assert_eq!(
+            reassembler.missing_parts("+15551234567", ReferenceWidth::EightBit, 5, 2),
+            Some(vec![2])
+        );
+    }
+
+    #[test]
+    fn test_reference_widths_are_separate_groups() {
+        let message = "a".repeat(200);
+        let eight_bit = received_segments(&message, "+15551234567", 9);
+        let sixteen_bit =
+            received_segments_with_width(&message, "+15551234567", 9, ReferenceWidth::SixteenBit);
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        reassembler.push(eight_bit[0].clone()).unwrap();
+        assert_eq!(
+            reassembler.push(sixteen_bit[1].clone()).unwrap(),
+            ReassemblyStatus::Pending {
+                received: 1,
+                missing: vec![1],
+            }
+        );
+        assert_eq!(reassembler.pending_count(), 2);
+    }
+
+    #[test]
+    fn test_out_of_order_and_duplicate_parts() {
+        let test_message = "1234567890".repeat(40);
+        let mut segments = received_segments(&test_message, "+15551234567", 9);
+        assert_eq!(segments.len(), 3);
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        let third = segments.pop().unwrap();
+        let second = segments.pop().unwrap();
+        let first = segments.pop().unwrap();
+
+        assert_eq!(
+            reassembler.push(third.clone()).unwrap(),
+            ReassemblyStatus::Pending {
+                received: 1,
+                missing: vec![1, 2],
+            }
+        );
+        assert_eq!(
+            reassembler.push(first).unwrap(),
+            ReassemblyStatus::Pending {
+                received: 2,
+                missing: vec![2],
+            }
+        );
+        assert_eq!(
+            reassembler.push(third).unwrap(),
+            ReassemblyStatus::Duplicate { missing: vec![2] }
+        );
+        assert_eq!(
+            reassembler.missing_parts("+15551234567", ReferenceWidth::EightBit, 9, 3),
+            Some(vec![2])
+        );
+
+        match reassembler.push(second).unwrap() {
+            ReassemblyStatus::Complete(message) => {
+                assert_eq!(message.reference, Some(9));
+                assert_eq!(message.text, test_message);
+            }
+            status => panic!("Expected a complete message, got {:?}", status),
+        }
+        assert_eq!(reassembler.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_groups_by_originator_and_reference() {
+        let test_message = "abcdefghij".repeat(20);
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        let alice = received_segments(&test_message, "+15550000001", 5);
+        let bob = received_segments(&test_message, "+15550000002", 5);
+        let other_reference = received_segments(&test_message, "+15550000001", 6);
+
+        reassembler.push(alice[0].clone()).unwrap();
+        reassembler.push(bob[1].clone()).unwrap();
+        reassembler.push(other_reference[0].clone()).unwrap();
+        assert_eq!(reassembler.pending_count(), 3);
+
+        assert!(matches!(
+            reassembler.push(alice[1].clone()).unwrap(),
+            ReassemblyStatus::Complete(_)
+        ));
+        assert_eq!(reassembler.pending_count(), 2);
+    }
+
+    #[test]
+    fn test_ucs2_surrogate_pair_split_across_parts() {
+        let high = "😀".encode_utf16().next().unwrap().to_be_bytes().to_vec();
+        let low = "😀".encode_utf16().nth(1).unwrap().to_be_bytes().to_vec();
+        let first = ReceivedSegment::new(
+            "Twilio",
+            &[0x05, 0x00, 0x03, 0x01, 0x02, 0x01],
+            high,
+            Encoding::UCS2,
+        )
+        .unwrap();
+        let second = ReceivedSegment::new(
+            "Twilio",
+            &[0x05, 0x00, 0x03, 0x01, 0x02, 0x02],
+            low,
+            Encoding::UCS2,
+        )
+        .unwrap();
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        reassembler.push(first).unwrap();
+        match reassembler.push(second).unwrap() {
+            ReassemblyStatus::Complete(message) => assert_eq!(message.text, "😀"),
+            status => panic!("Expected a complete message, got {:?}", status),
+        }
+    }
+
+    #[test]
+    fn test_stale_groups_expire() {
+        let test_message = "1234567890".repeat(40);
+        let segments = received_segments(&test_message, "+15551234567", 3);
+        let mut reassembler = Reassembler::new(Duration::from_secs(30));
+        let start = Instant::now();
+
+        reassembler.push_at(segments[1].clone(), start).unwrap();
+        assert!(reassembler
+            .expire_at(start + Duration::from_secs(29))
+            .is_empty());
+
+        let expired = reassembler.expire_at(start + Duration::from_secs(30));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].reference, 3);
+        assert_eq!(expired[0].received, vec![2]);
+        assert_eq!(expired[0].missing, vec![1, 3]);
+        assert_eq!(reassembler.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_sequence_number() {
+        let segment = ReceivedSegment::new(
+            "Twilio",
+            &[0x05, 0x00, 0x03, 0x01, 0x02, 0x03],
+            vec![0x41],
+            Encoding::GSM7,
+        )
+        .unwrap();
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        assert!(reassembler.push(segment).is_err());
+    }
+
+    #[test]
+    fn test_reassemble_sms_deliver() {
+        let test_message = "Inbound {multipart} message ".repeat(8);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_concatenation_reference(0x21);
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        let mut status = None;
+
+        for segment in segmented_message.segments.iter().rev() {
+            let mut pdu = vec![0x44];
+            pdu.extend(Address::new("+46708251358").to_bytes().unwrap());
+            pdu.extend([0x00, 0x00, 0x99, 0x30, 0x92, 0x51, 0x61, 0x95, 0x80]);
+            pdu.push(segment.user_data_length(Encoding::GSM7) as u8);
+            pdu.extend(segment.user_data(Encoding::GSM7));
+
+            let deliver = SmsDeliver::from_hex(&encode_hex(&pdu)).unwrap();
+            let received = ReceivedSegment::from_sms_deliver(&deliver).unwrap();
+            status = Some(reassembler.push(received).unwrap());
+        }
+
+        match status.unwrap() {
+            ReassemblyStatus::Complete(message) => {
+                assert_eq!(message.originator, "+46708251358");
+                assert_eq!(message.text, test_message);
+            }
+            status => panic!("Expected a complete message, got {:?}", status),
+        }
+    }
+}
+
+mod segment_texts_tests {
+    use super::*;
+    use message_segment_calculator::SegmentText;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_single_segment_text() {
+        let segmented_message = SegmentedMessage::new("Hello", Encoding::Auto, false).unwrap();
+        assert_eq!(
+            segmented_message.segment_texts(),
+            vec![SegmentText {
+                text: "Hello".to_string(),
+                byte_range: 0..5,
+                char_range: 0..5,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_gsm7_segment_texts() {
+        let test_message = "1234567890".repeat(31);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        let texts = segmented_message.segment_texts();
+
+        assert_eq!(texts.len(), 3);
+        assert_eq!(texts[0].char_range, 0..153);
+        assert_eq!(texts[1].char_range, 153..306);
+        assert_eq!(texts[2].char_range, 306..310);
+        assert_eq!(texts[2].text, "7890");
+        assert_eq!(
+            texts.iter().map(|t| t.text.as_str()).collect::<String>(),
+            test_message
+        );
+    }
+
+    #[test]
+    fn test_ucs2_segment_text_byte_ranges() {
+        let test_message = format!("😜{}", "á".repeat(70));
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        let texts = segmented_message.segment_texts();
+
+        assert_eq!(texts.len(), 2);
+        for text in &texts {
+            assert_eq!(&test_message[text.byte_range.clone()], text.text);
+        }
+        assert_eq!(texts[0].char_range, 0..66);
+        assert_eq!(texts[0].byte_range, 0..4 + 65 * 2);
+        assert_eq!(texts[1].char_range, 66..71);
+    }
+
+    #[test]
+    fn test_smart_encoding_ranges_point_to_original() {
+        let test_message = format!("\u{201c}{}\u{201d}", "a".repeat(160));
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, true).unwrap();
+        let texts = segmented_message.segment_texts();
+
+        assert_eq!(texts.len(), 2);
+        assert!(texts[0].text.starts_with('"'));
+        assert_eq!(texts[1].char_range.end, 162);
+        assert_eq!(texts[1].byte_range.end, test_message.len());
+        assert_eq!(
+            &test_message[texts[1].byte_range.clone()],
+            format!("{}\u{201d}", "a".repeat(8))
+        );
+    }
+}
+
+mod split_strategy_tests {
+    use super::*;
+    use message_segment_calculator::{ReferenceWidth, SplitStrategy};
+    use pretty_assertions::assert_eq;
+
+    fn texts(segmented_message: &SegmentedMessage) -> Vec<String> {
+        segmented_message
+            .segment_texts()
+            .into_iter()
+            .map(|t| t.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_character_split_is_default() {
+        let test_message = "word ".repeat(40);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+
+        assert_eq!(
+            segmented_message.options.split_strategy,
+            SplitStrategy::Character
+        );
+        assert_eq!(texts(&segmented_message)[0].chars().count(), 153);
+        assert_eq!(segmented_message.split_overhead_in_bits(), 0);
+    }
+
+    #[test]
+    fn test_word_split_backs_off_to_whitespace() {
+        // 153 characters end in the middle of the 31st "word "
+        let test_message = "word ".repeat(40);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_split_strategy(SplitStrategy::Word { max_backoff: 10 });
+        let texts = texts(&segmented_message);
+
+        assert_eq!(texts.len(), 2);
+        assert_eq!(texts[0], "word ".repeat(30));
+        assert_eq!(texts.concat(), test_message);
+        assert_eq!(segmented_message.split_overhead_in_bits(), 3 * 7);
+    }
+
+    #[test]
+    fn test_word_split_respects_max_backoff() {
+        let test_message = format!("{} {}", "a".repeat(100), "b".repeat(100));
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_split_strategy(SplitStrategy::Word { max_backoff: 20 });
+
+        assert_eq!(texts(&segmented_message)[0].chars().count(), 153);
+        assert_eq!(segmented_message.split_overhead_in_bits(), 0);
+    }
+
+    #[test]
+    fn test_word_split_keeps_urls_whole() {
+        let url = "https://example.com/some/long/path";
+        let test_message = format!("{} {}", "a ".repeat(70), url);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_split_strategy(SplitStrategy::Word { max_backoff: 40 });
+        let texts = texts(&segmented_message);
+
+        assert_eq!(texts.len(), 2);
+        assert_eq!(texts[1], url);
+    }
+
+    #[test]
+    fn test_sentence_split_prefers_sentence_boundary() {
+        let first_sentence = format!("{}.", "word ".repeat(28).trim_end());
+        let test_message = format!("{} {}", first_sentence, "More words here. ".repeat(6));
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_split_strategy(SplitStrategy::Sentence { max_backoff: 30 });
+        let texts = texts(&segmented_message);
+
+        assert_eq!(texts[0], format!("{} ", first_sentence));
+        assert_eq!(texts.concat(), test_message);
+    }
+
+    #[test]
+    fn test_sentence_split_falls_back_to_words() {
+        let test_message = "word ".repeat(40);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_split_strategy(SplitStrategy::Sentence { max_backoff: 10 });
+
+        assert_eq!(texts(&segmented_message)[0], "word ".repeat(30));
+    }
+
+    #[test]
+    fn test_split_strategy_survives_reference_width_change() {
+        let test_message = "word ".repeat(40);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_split_strategy(SplitStrategy::Word { max_backoff: 10 })
+            .with_reference_width(ReferenceWidth::SixteenBit);
+
+        assert_eq!(texts(&segmented_message)[0], "word ".repeat(30));
+    }
+}
+
+mod atomicity_tests {
+    use super::*;
+    use message_segment_calculator::{
+        validate_segmentation, Atomicity, Error, LanguageShift, NationalLanguage, Sequence,
+    };
+    use pretty_assertions::assert_eq;
+
+    const FAMILY: &str = "👨\u{200d}👩\u{200d}👧";
+
+    fn texts(segmented_message: &SegmentedMessage) -> Vec<String> {
+        segmented_message
+            .segment_texts()
+            .into_iter()
+            .map(|t| t.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_escape_sequence_moves_to_next_segment() {
+        // 152 septets leave one free septet, too few for the escaped euro sign
+        let test_message = format!("{}€{}", "a".repeat(152), "b".repeat(10));
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+
+        assert_eq!(segmented_message.segments[0].septets().len(), 152);
+        assert_eq!(segmented_message.segments[1].septets()[..2], [0x1b, 0x65]);
+        assert_eq!(segmented_message.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_escape_sequence_fills_segment_exactly() {
+        let test_message = format!("{}€{}", "a".repeat(151), "b".repeat(10));
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+
+        assert_eq!(segmented_message.segments[0].septets().len(), 153);
+        assert_eq!(texts(&segmented_message)[1], "b".repeat(10));
+        assert_eq!(segmented_message.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_escape_sequences_at_every_offset() {
+        for prefix in 140..160 {
+            let test_message = format!("{}{}", "a".repeat(prefix), "€".repeat(12));
+            let segmented_message =
+                SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+
+            assert_eq!(segmented_message.validate(), Ok(()), "prefix {}", prefix);
+            assert_eq!(texts(&segmented_message).concat(), test_message);
+        }
+    }
+
+    #[test]
+    fn test_surrogate_pair_moves_to_next_segment() {
+        // 66 code units leave one free code unit, too few for the surrogate pair
+        let test_message = format!("{}😜{}", "a".repeat(66), "b".repeat(10));
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+
+        assert_eq!(
+            texts(&segmented_message),
+            vec!["a".repeat(66), format!("😜{}", "b".repeat(10))]
+        );
+        assert_eq!(segmented_message.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_surrogate_pairs_at_every_offset() {
+        for prefix in 60..70 {
+            let test_message = format!("{}{}", "a".repeat(prefix), "😜".repeat(40));
+            let segmented_message =
+                SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+
+            assert_eq!(segmented_message.validate(), Ok(()), "prefix {}", prefix);
+            assert_eq!(texts(&segmented_message).concat(), test_message);
+        }
+    }
+
+    #[test]
+    fn test_grapheme_clusters_are_kept_whole_by_default() {
+        let test_message = format!("{}{}b", "a".repeat(62), FAMILY);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+
+        assert_eq!(
+            segmented_message.options.atomicity,
+            Atomicity::GraphemeCluster
+        );
+        assert_eq!(
+            texts(&segmented_message),
+            vec!["a".repeat(62), format!("{}b", FAMILY)]
+        );
+        assert_eq!(segmented_message.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_code_point_atomicity_splits_grapheme_clusters() {
+        let test_message = format!("{}{}b", "a".repeat(62), FAMILY);
+        let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+            .unwrap()
+            .with_atomicity(Atomicity::CodePoint);
+
+        assert_eq!(
+            texts(&segmented_message),
+            vec![
+                format!("{}👨\u{200d}👩", "a".repeat(62)),
+                "\u{200d}👧b".to_string()
+            ]
+        );
+        assert_eq!(segmented_message.validate(), Ok(()));
+
+        let mut strict = segmented_message.clone();
+        strict.options.atomicity = Atomicity::GraphemeCluster;
+        assert_eq!(
+            strict.validate(),
+            Err(Error::SplitSequence {
+                segment: 1,
+                sequence: Sequence::GraphemeCluster,
+            })
+        );
+    }
+
+    #[test]
+    fn test_code_point_atomicity_keeps_surrogate_pairs() {
+        for prefix in 55..68 {
+            let test_message = format!("{}{}", "a".repeat(prefix), FAMILY.repeat(10));
+            let segmented_message = SegmentedMessage::new(&test_message, Encoding::Auto, false)
+                .unwrap()
+                .with_atomicity(Atomicity::CodePoint);
+
+            assert_eq!(segmented_message.validate(), Ok(()), "prefix {}", prefix);
+            assert_eq!(texts(&segmented_message).concat(), test_message);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_split_escape_sequence() {
+        let parts = vec![vec![0x61, 0x1b], vec![0x65]];
+        assert_eq!(
+            validate_segmentation(
+                &parts,
+                Encoding::GSM7,
+                LanguageShift::default(),
+                Atomicity::GraphemeCluster
+            ),
+            Err(Error::SplitSequence {
+                segment: 1,
+                sequence: Sequence::EscapeSequence,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_accepts_escaped_escape() {
+        let parts = vec![vec![0x1b, 0x1b], vec![0x61]];
+        assert_eq!(
+            validate_segmentation(
+                &parts,
+                Encoding::GSM7,
+                LanguageShift::default(),
+                Atomicity::GraphemeCluster
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_split_gsm7_conjunct() {
+        let hindi = LanguageShift::new(Some(NationalLanguage::Hindi), None);
+        let code = |c| NationalLanguage::Hindi.locking_shift_code(c).unwrap() as u16;
+        let parts = vec![vec![code('क')], vec![code('्'), code('ष'), code('ि')]];
+        assert_eq!(
+            validate_segmentation(&parts, Encoding::GSM7, hindi, Atomicity::GraphemeCluster),
+            Err(Error::SplitSequence {
+                segment: 1,
+                sequence: Sequence::GraphemeCluster,
+            })
+        );
+        assert_eq!(
+            validate_segmentation(&parts, Encoding::GSM7, hindi, Atomicity::CodePoint),
+            Ok(())
+        );
+
+        let parts = vec![vec![code('क'), code('ि')], vec![code('क')]];
+        assert_eq!(
+            validate_segmentation(&parts, Encoding::GSM7, hindi, Atomicity::GraphemeCluster),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_split_surrogate_pair() {
+        let parts = vec![vec![0x61, 0xd83d], vec![0xde1c]];
+        assert_eq!(
+            validate_segmentation(
+                &parts,
+                Encoding::UCS2,
+                LanguageShift::default(),
+                Atomicity::CodePoint
+            ),
+            Err(Error::SplitSequence {
+                segment: 1,
+                sequence: Sequence::SurrogatePair,
+            })
+        );
+
+        let parts = vec![vec![0x61], vec![0xde1c]];
+        assert_eq!(
+            validate_segmentation(
+                &parts,
+                Encoding::UCS2,
+                LanguageShift::default(),
+                Atomicity::CodePoint
+            ),
+            Err(Error::SplitSequence {
+                segment: 1,
+                sequence: Sequence::SurrogatePair,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_allows_split_line_break() {
+        let parts = vec![vec![0x61, 0x0d], vec![0x0a, 0x62]];
+        assert_eq!(
+            validate_segmentation(
+                &parts,
+                Encoding::UCS2,
+                LanguageShift::default(),
+                Atomicity::GraphemeCluster
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_split_combining_sequence() {
+        let parts = vec![vec![0x65], vec![0x0301]];
+        assert_eq!(
+            validate_segmentation(
+                &parts,
+                Encoding::UCS2,
+                LanguageShift::default(),
+                Atomicity::GraphemeCluster
+            ),
+            Err(Error::SplitSequence {
+                segment: 1,
+                sequence: Sequence::GraphemeCluster,
+            })
+        );
+        assert_eq!(
+            validate_segmentation(
+                &parts,
+                Encoding::UCS2,
+                LanguageShift::default(),
+                Atomicity::CodePoint
+            ),
+            Ok(())
+        );
+    }
+}
+
+mod error_tests {
+    use super::*;
+    use message_segment_calculator::pdu::SmsSubmit;
+    use message_segment_calculator::{Error, IncompatibleCharacter, NationalLanguage, Warning};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_incompatible_characters_are_reported_with_positions() {
+        let result = SegmentedMessage::new("Hi 😜 ok 🎉", Encoding::GSM7, false);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::IncompatibleCharacters {
+                encoding: Encoding::GSM7,
+                characters: vec![
+                    IncompatibleCharacter {
+                        grapheme: "😜".to_string(),
+                        char_index: 3,
+                        byte_range: 3..7,
+                    },
+                    IncompatibleCharacter {
+                        grapheme: "🎉".to_string(),
+                        char_index: 8,
+                        byte_range: 11..15,
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_incompatible_character_positions_refer_to_original_message() {
+        let result = SegmentedMessage::new("\u{201c}Hi\u{201d} 😜", Encoding::GSM7, true);
+
+        match result.unwrap_err() {
+            Error::IncompatibleCharacters { characters, .. } => {
+                assert_eq!(characters.len(), 1);
+                assert_eq!(characters[0].char_index, 5);
+                assert_eq!(characters[0].byte_range, 9..13);
+            }
+            error => panic!("Unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_national_language_characters_are_not_reported() {
+        let result = SegmentedMessage::with_national_languages(
+            "ş 😜",
+            Encoding::GSM7,
+            false,
+            &[NationalLanguage::Turkish],
+        );
+
+        match result.unwrap_err() {
+            Error::IncompatibleCharacters { characters, .. } => {
+                assert_eq!(characters.len(), 1);
+                assert_eq!(characters[0].grapheme, "😜");
+            }
+            error => panic!("Unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_message_too_long() {
+        // Long messages are still counted, but can't be sent
+        let test_message = "a".repeat(153 * 299 + 1);
+        let segmented_message =
+            SegmentedMessage::new(&test_message, Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.segments_count(), 300);
+        assert_eq!(
+            segmented_message.warnings,
+            vec![Warning::TooManySegments {
+                segments: 300,
+                max_segments: 255,
+            }]
+        );
+        assert_eq!(
+            segmented_message.check_segment_limit(),
+            Err(Error::MessageTooLong {
+                segments: 300,
+                max_segments: 255,
+            })
+        );
+        assert_eq!(
+            SmsSubmit::from_segmented_message(&segmented_message, "+46708251358", 0).unwrap_err(),
+            Error::MessageTooLong {
+                segments: 300,
+                max_segments: 255,
+            }
+        );
+
+        let segmented_message =
+            SegmentedMessage::new(&"a".repeat(153 * 255), Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.check_segment_limit(), Ok(()));
+        assert_eq!(
+            segmented_message.warnings,
+            vec![Warning::NearSegmentBoundary { segments: 255 }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_option_and_decode_errors() {
+        let segmented_message = SegmentedMessage::new("😜", Encoding::Auto, false).unwrap();
+        assert!(matches!(
+            segmented_message.packed_user_data(),
+            Err(Error::InvalidOption(_))
+        ));
+        assert!(matches!(SmsSubmit::from_hex("1100"), Err(Error::Decode(_))));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let error = SegmentedMessage::new("a😜", Encoding::GSM7, false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The string provided is incompatible with GSM7 encoding: \"😜\" at 1"
+        );
+
+        let error: Box<dyn std::error::Error> = Box::new(Error::MessageTooLong {
+            segments: 300,
+            max_segments: 255,
+        });
+        assert_eq!(
+            error.to_string(),
+            "The message needs 300 segments, more than the 255 allowed"
+        );
+    }
+}
+
+mod warning_tests {
+    use super::*;
+    use message_segment_calculator::{LineBreakStyle, ReferenceWidth, Severity, Span, Warning};
+    use pretty_assertions::assert_eq;
+
+    fn span(char_range: std::ops::Range<usize>, byte_range: std::ops::Range<usize>) -> Span {
+        Span {
+            char_range,
+            byte_range,
+        }
+    }
+
+    #[test]
+    fn test_no_warnings() {
+        let segmented_message =
+            SegmentedMessage::new("Hello world", Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.warnings, vec![]);
+    }
+
+    #[test]
+    fn test_line_break_warnings() {
+        let segmented_message = SegmentedMessage::new("a\nb\r\nc", Encoding::Auto, false).unwrap();
+
+        assert_eq!(
+            segmented_message.warnings,
+            vec![
+                Warning::LineBreak {
+                    style: LineBreakStyle::LF,
+                    span: span(1..2, 1..2),
+                },
+                Warning::LineBreak {
+                    style: LineBreakStyle::CRLF,
+                    span: span(3..5, 3..5),
+                },
+            ]
+        );
+        assert_eq!(segmented_message.warnings[0].severity(), Severity::Info);
+        assert_eq!(segmented_message.warnings[1].severity(), Severity::Warning);
+        assert_eq!(segmented_message.warnings[1].code(), "line-break");
+    }
+
+    #[test]
+    fn test_forces_ucs2_warnings() {
+        let segmented_message = SegmentedMessage::new("Hi 😜", Encoding::Auto, false).unwrap();
+
+        assert_eq!(
+            segmented_message.warnings,
+            vec![Warning::ForcesUcs2 {
+                grapheme: "😜".to_string(),
+                span: span(3..4, 3..7),
+            }]
+        );
+        assert_eq!(
+            segmented_message.warnings[0].to_string(),
+            "\"😜\" is not in the GSM-7 alphabet and makes the message UCS-2"
+        );
+
+        let forced = SegmentedMessage::new("Hi 😜", Encoding::UCS2, false).unwrap();
+        assert_eq!(forced.warnings, vec![]);
+    }
+
+    #[test]
+    fn test_smart_encoding_replacement_warnings() {
+        let segmented_message =
+            SegmentedMessage::new("\u{201c}Hi\u{201d}", Encoding::Auto, true).unwrap();
+
+        assert_eq!(
+            segmented_message.warnings,
+            vec![
+                Warning::SmartEncodingReplacement {
+                    original: '\u{201c}',
+                    replacement: "\"".to_string(),
+                    span: span(0..1, 0..3),
+                },
+                Warning::SmartEncodingReplacement {
+                    original: '\u{201d}',
+                    replacement: "\"".to_string(),
+                    span: span(3..4, 5..8),
+                },
+            ]
+        );
+        assert_eq!(
+            segmented_message.warnings[0].code(),
+            "smart-encoding-replacement"
+        );
+    }
+
+    #[test]
+    fn test_invisible_character_warnings() {
+        let segmented_message =
+            SegmentedMessage::new("\u{feff}a\u{200b}b\u{00ad}", Encoding::Auto, false).unwrap();
+        let invisible = segmented_message
+            .warnings
+            .iter()
+            .filter(|w| w.code() == "invisible-character")
+            .collect::<Vec<&Warning>>();
+
+        assert_eq!(
+            invisible,
+            vec![
+                &Warning::InvisibleCharacter {
+                    character: '\u{feff}',
+                    span: span(0..1, 0..3),
+                },
+                &Warning::InvisibleCharacter {
+                    character: '\u{200b}',
+                    span: span(2..3, 4..7),
+                },
+                &Warning::InvisibleCharacter {
+                    character: '\u{00ad}',
+                    span: span(4..5, 8..10),
+                },
+            ]
+        );
+        assert_eq!(
+            invisible[0].to_string(),
+            "Invisible character U+FEFF takes up space"
+        );
+    }
+
+    #[test]
+    fn test_trailing_whitespace_warnings() {
+        let segmented_message =
+            SegmentedMessage::new("line  \nnext \t", Encoding::Auto, false).unwrap();
+        let trailing = segmented_message
+            .warnings
+            .iter()
+            .filter_map(|w| match w {
+                Warning::TrailingWhitespace { span } => Some(span.char_range.clone()),
+                _ => None,
+            })
+            .collect::<Vec<std::ops::Range<usize>>>();
+
+        assert_eq!(trailing, vec![4..6, 11..13]);
+    }
+
+    #[test]
+    fn test_near_segment_boundary_warnings() {
+        let full = SegmentedMessage::new(&"a".repeat(160), Encoding::Auto, false).unwrap();
+        assert_eq!(
+            full.warnings,
+            vec![Warning::NearSegmentBoundary { segments: 1 }]
+        );
+        assert_eq!(full.warnings[0].span(), None);
+        assert_eq!(
+            full.warnings[0].to_string(),
+            "One more character would need segment 2"
+        );
+
+        let not_full = SegmentedMessage::new(&"a".repeat(159), Encoding::Auto, false).unwrap();
+        assert_eq!(not_full.warnings, vec![]);
+
+        let ucs2_full = SegmentedMessage::new(&"😜".repeat(35), Encoding::Auto, false).unwrap();
+        assert!(ucs2_full
+            .warnings
+            .contains(&Warning::NearSegmentBoundary { segments: 1 }));
+    }
+
+    #[test]
+    fn test_near_segment_boundary_follows_rebuilds() {
+        // 306 characters fill two segments with an 8-bit reference
+        let segmented_message =
+            SegmentedMessage::new(&"a".repeat(306), Encoding::Auto, false).unwrap();
+        assert_eq!(
+            segmented_message.warnings,
+            vec![Warning::NearSegmentBoundary { segments: 2 }]
+        );
+
+        let segmented_message = segmented_message.with_reference_width(ReferenceWidth::SixteenBit);
+        assert_eq!(segmented_message.segments_count(), 3);
+        assert_eq!(segmented_message.warnings, vec![]);
+    }
+}
+
+mod segmentation_options_tests {
+    use super::*;
+    use message_segment_calculator::{
+        Atomicity, Error, LanguageShift, LineBreakNormalization, LineBreakStyle, NationalLanguage,
+        ReferenceWidth, SegmentCapacity, SegmentationOptions, Span, SplitStrategy, Warning,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_options() {
+        let options = SegmentationOptions::new();
+        assert_eq!(options.encoding, Encoding::Auto);
+        assert!(!options.smart_encoding);
+        assert_eq!(options.national_languages, vec![]);
+        assert_eq!(options.reference_width, ReferenceWidth::EightBit);
+        assert_eq!(options.concatenation_reference, None);
+        assert_eq!(options.segment_capacity, SegmentCapacity::GSM);
+        assert_eq!(
+            options.line_break_normalization,
+            LineBreakNormalization::Keep
+        );
+        assert_eq!(options.split_strategy, SplitStrategy::Character);
+        assert_eq!(options.atomicity, Atomicity::GraphemeCluster);
+    }
+
+    #[test]
+    fn test_with_options_matches_new() {
+        let message = "Hello “world” 😜 ".repeat(10);
+        let options = SegmentationOptions::new()
+            .smart_encoding(true)
+            .concatenation_reference(7);
+        let with_options = SegmentedMessage::with_options(&message, &options).unwrap();
+        let new = SegmentedMessage::new(&message, Encoding::Auto, true)
+            .unwrap()
+            .with_concatenation_reference(7);
+
+        assert_eq!(with_options.segments_count(), new.segments_count());
+        assert_eq!(with_options.total_size(), new.total_size());
+        assert_eq!(with_options.segment_texts(), new.segment_texts());
+        assert_eq!(with_options.warnings, new.warnings);
+        assert_eq!(with_options.options, new.options);
+    }
+
+    #[test]
+    fn test_segment_size() {
+        let message = "a".repeat(100);
+        let default =
+            SegmentedMessage::with_options(&message, &SegmentationOptions::new()).unwrap();
+        assert_eq!(default.segments_count(), 1);
+
+        // 70 octets hold 80 septets, or 73 next to a concatenation header
+        let options = SegmentationOptions::new().segment_capacity(SegmentCapacity::from_octets(70));
+        let smaller = SegmentedMessage::with_options(&message, &options).unwrap();
+        assert_eq!(smaller.segments_count(), 2);
+        assert_eq!(smaller.segment_texts()[0].char_range, 0..73);
+        assert!(smaller
+            .segments
+            .iter()
+            .all(|segment| segment.free_size_in_bits() >= 0));
+    }
+
+    #[test]
+    fn test_invalid_segment_size() {
+        for bits in [100, 600_000] {
+            let options =
+                SegmentationOptions::new().segment_capacity(SegmentCapacity::from_bits(bits));
+            assert!(matches!(
+                SegmentedMessage::with_options("Hello", &options),
+                Err(Error::InvalidOption(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_lf_normalization() {
+        let message = "ab \r\ncd\r\n";
+        let options =
+            SegmentationOptions::new().line_break_normalization(LineBreakNormalization::Lf);
+        let segmented_message = SegmentedMessage::with_options(message, &options).unwrap();
+
+        assert_eq!(segmented_message.number_of_characters, 7);
+        assert_eq!(segmented_message.line_break_style, Some(LineBreakStyle::LF));
+        assert_eq!(segmented_message.original_message, message);
+
+        let texts = segmented_message.segment_texts();
+        assert_eq!(texts[0].text, "ab \ncd\n");
+        assert_eq!(texts[0].char_range, 0..9);
+        assert_eq!(texts[0].byte_range, 0..9);
+
+        assert_eq!(
+            segmented_message.warnings,
+            vec![
+                Warning::LineBreak {
+                    style: LineBreakStyle::LF,
+                    span: Span {
+                        char_range: 3..5,
+                        byte_range: 3..5,
+                    },
+                },
+                Warning::LineBreak {
+                    style: LineBreakStyle::LF,
+                    span: Span {
+                        char_range: 7..9,
+                        byte_range: 7..9,
+                    },
+                },
+                Warning::TrailingWhitespace {
+                    span: Span {
+                        char_range: 2..3,
+                        byte_range: 2..3,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crlf_normalization() {
+        let message = "ab\ncd\r\n";
+        let options =
+            SegmentationOptions::new().line_break_normalization(LineBreakNormalization::CrLf);
+        let segmented_message = SegmentedMessage::with_options(message, &options).unwrap();
+
+        assert_eq!(segmented_message.number_of_characters, 8);
+
+        let texts = segmented_message.segment_texts();
+        assert_eq!(texts[0].text, "ab\r\ncd\r\n");
+        assert_eq!(texts[0].char_range, 0..7);
+        assert_eq!(
+            segmented_message.warnings[0],
+            Warning::LineBreak {
+                style: LineBreakStyle::CRLF,
+                span: Span {
+                    char_range: 2..3,
+                    byte_range: 2..3,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalization_keeps_ranges_across_segments() {
+        let message = "e\r\n".repeat(100);
+        let options =
+            SegmentationOptions::new().line_break_normalization(LineBreakNormalization::Lf);
+        let segmented_message = SegmentedMessage::with_options(&message, &options).unwrap();
+        assert_eq!(segmented_message.segments_count(), 2);
+
+        let texts = segmented_message.segment_texts();
+        // 153 septets end on an "e", right before a CRLF
+        assert_eq!(texts[0].char_range, 0..229);
+        assert_eq!(texts[1].char_range, 229..300);
+        assert_eq!(texts[1].byte_range, 229..300);
+    }
+
+    #[test]
+    fn test_concatenation_reference_option() {
+        let message = "a".repeat(200);
+        let options = SegmentationOptions::new()
+            .reference_width(ReferenceWidth::SixteenBit)
+            .concatenation_reference(0x1234);
+        let segmented_message = SegmentedMessage::with_options(&message, &options).unwrap();
+        assert_eq!(segmented_message.concatenation_reference(), Some(0x1234));
+        assert_eq!(
+            segmented_message.segments[0].reference_width,
+            ReferenceWidth::SixteenBit
+        );
+
+        let rebuilt =
+            segmented_message.with_split_strategy(SplitStrategy::Word { max_backoff: 10 });
+        assert_eq!(rebuilt.concatenation_reference(), Some(0x1234));
+    }
+
+    #[test]
+    fn test_national_languages_option() {
+        let options = SegmentationOptions::new().national_languages(&[NationalLanguage::Turkish]);
+        let segmented_message =
+            SegmentedMessage::with_options("Günaydın, nasılsın?", &options).unwrap();
+        assert_eq!(segmented_message.get_encoding_name(), Encoding::GSM7);
+        assert_eq!(
+            segmented_message.language_shift,
+            LanguageShift::new(Some(NationalLanguage::Turkish), None)
+        );
+    }
+}
+
+mod segment_capacity_tests {
+    use super::*;
+    use message_segment_calculator::pdu::SmsSubmit;
+    use message_segment_calculator::{Error, Segment, SegmentCapacity, SegmentationOptions};
+    use pretty_assertions::assert_eq;
+
+    fn with_capacity(message: &str, capacity: SegmentCapacity) -> SegmentedMessage {
+        SegmentedMessage::with_options(
+            message,
+            &SegmentationOptions::new().segment_capacity(capacity),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_capacity_units() {
+        assert_eq!(SegmentCapacity::GSM.bits(), 1120);
+        assert_eq!(SegmentCapacity::from_octets(140), SegmentCapacity::GSM);
+        assert_eq!(SegmentCapacity::from_bits(1000).octets(), 125);
+        assert_eq!(SegmentCapacity::default(), SegmentCapacity::GSM);
+        assert_eq!(SegmentCapacity::GSM.to_string(), "140 octets");
+        assert_eq!(SegmentCapacity::from_bits(1001).to_string(), "1001 bits");
+    }
+
+    #[test]
+    fn test_free_size_follows_capacity() {
+        let segment = Segment::new(false).with_capacity(SegmentCapacity::from_octets(10));
+        assert_eq!(segment.free_size_in_bits(), 80);
+
+        let segment = Segment::new(true).with_capacity(SegmentCapacity::from_octets(10));
+        assert_eq!(segment.free_size_in_bits(), 80 - 48);
+    }
+
+    #[test]
+    fn test_capacity_in_bits() {
+        // 1000 bits hold 142 septets on their own, 136 next to a header
+        let segmented_message = with_capacity(&"a".repeat(142), SegmentCapacity::from_bits(1000));
+        assert_eq!(segmented_message.segments_count(), 1);
+
+        let segmented_message = with_capacity(&"a".repeat(143), SegmentCapacity::from_bits(1000));
+        assert_eq!(segmented_message.segments_count(), 2);
+        assert_eq!(segmented_message.segment_texts()[0].char_range, 0..136);
+        assert!(segmented_message
+            .segments
+            .iter()
+            .all(|segment| segment.size_in_bits() <= 1000));
+    }
+
+    #[test]
+    fn test_smpp_short_message_capacity() {
+        let segmented_message =
+            with_capacity(&"a".repeat(290), SegmentCapacity::SMPP_SHORT_MESSAGE);
+        assert_eq!(segmented_message.segments_count(), 1);
+
+        let segmented_message =
+            with_capacity(&"😜".repeat(70), SegmentCapacity::SMPP_SHORT_MESSAGE);
+        // 127 UCS-2 code units fit, 124 next to a header
+        assert_eq!(segmented_message.segments_count(), 2);
+        assert_eq!(segmented_message.segment_texts()[0].char_range, 0..62);
+    }
+
+    #[test]
+    fn test_message_payload_capacity() {
+        let message = "a".repeat(10_000);
+        let segmented_message = with_capacity(&message, SegmentCapacity::SMPP_MESSAGE_PAYLOAD);
+        assert_eq!(segmented_message.segments_count(), 1);
+        assert!(!segmented_message.segments[0].has_user_data_header);
+        assert_eq!(segmented_message.total_size(), 70_000);
+        assert_eq!(segmented_message.message_size(), 70_000);
+
+        let gsm = SegmentedMessage::new(&message, Encoding::Auto, false).unwrap();
+        assert_eq!(gsm.segments_count(), 66);
+    }
+
+    #[test]
+    fn test_tpdu_rejects_large_segments() {
+        let segmented_message =
+            with_capacity(&"a".repeat(200), SegmentCapacity::SMPP_SHORT_MESSAGE);
+        assert!(matches!(
+            SmsSubmit::from_segmented_message(&segmented_message, "+46708251358", 0),
+            Err(Error::Encode(_))
+        ));
+    }
+}
+
+mod carrier_profile_tests {
+    use super::*;
+    use message_segment_calculator::{
+        CarrierProfile, Delivery, Error, ReferenceWidth, SegmentCapacity, SegmentationOptions,
+        Warning,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn with_profile(message: &str, profile: &CarrierProfile) -> Result<SegmentedMessage, Error> {
+        SegmentedMessage::with_options(
+            message,
+            &SegmentationOptions::new().carrier_profile(profile),
+        )
+    }
+
+    #[test]
+    fn test_built_in_profiles() {
+        assert_eq!(CarrierProfile::default(), CarrierProfile::generic_3gpp());
+        assert_eq!(
+            CarrierProfile::built_in()
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["3gpp", "twilio", "cdma"]
+        );
+        assert_eq!(
+            CarrierProfile::named("Twilio"),
+            Some(CarrierProfile::twilio())
+        );
+        assert_eq!(CarrierProfile::named("unknown"), None);
+    }
+
+    #[test]
+    fn test_cdma_profile() {
+        let cdma = CarrierProfile::cdma();
+        assert_eq!(CarrierProfile::named("CDMA"), Some(cdma.clone()));
+
+        let single = with_profile(&"a".repeat(160), &cdma).unwrap();
+        assert_eq!(single.get_encoding_name(), Encoding::GSM7);
+        assert_eq!(single.segments_count(), 1);
+        let concatenated = with_profile(&"a".repeat(161), &cdma).unwrap();
+        assert_eq!(concatenated.segments_count(), 2);
+
+        // ASCII extension characters stay 7-bit, the others aren't ASCII
+        let ascii = with_profile("{[~]}", &cdma).unwrap();
+        assert_eq!(ascii.get_encoding_name(), Encoding::GSM7);
+        for message in ["Price: 5€", "Café", "Straße"] {
+            let unicode = with_profile(message, &cdma).unwrap();
+            assert_eq!(unicode.get_encoding_name(), Encoding::UCS2, "{}", message);
+        }
+
+        assert_eq!(
+            with_profile(&"a".repeat(153 * 10 + 1), &cdma)
+                .unwrap()
+                .check_segment_limit(),
+            Err(Error::MessageTooLong {
+                segments: 11,
+                max_segments: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn test_twilio_reserved_bits() {
+        let message = "a".repeat(200);
+        let twilio = with_profile(&message, &CarrierProfile::twilio()).unwrap();
+        assert!(twilio.segments.iter().all(|s| s.has_twilio_reserved_bits));
+
+        let generic = with_profile(&message, &CarrierProfile::generic_3gpp()).unwrap();
+        assert!(generic.segments.iter().all(|s| !s.has_twilio_reserved_bits));
+        assert_eq!(twilio.total_size(), generic.total_size());
+
+        let single = with_profile("Hello", &CarrierProfile::twilio()).unwrap();
+        assert!(!single.segments[0].has_twilio_reserved_bits);
+
+        // Messages segmented without a profile keep the Twilio behavior
+        let default = SegmentedMessage::new(&message, Encoding::Auto, false).unwrap();
+        assert!(default.segments.iter().all(|s| s.has_twilio_reserved_bits));
+    }
+
+    fn no_euro() -> CarrierProfile {
+        CarrierProfile {
+            name: "no-euro".to_string(),
+            rejected_characters: vec!['€'],
+            ..CarrierProfile::generic_3gpp()
+        }
+    }
+
+    #[test]
+    fn test_rejected_characters() {
+        let rejected = with_profile("Price: 5€", &no_euro()).unwrap();
+        assert_eq!(rejected.get_encoding_name(), Encoding::UCS2);
+
+        let generic = with_profile("Price: 5€", &CarrierProfile::generic_3gpp()).unwrap();
+        assert_eq!(generic.get_encoding_name(), Encoding::GSM7);
+
+        let result = SegmentedMessage::with_options(
+            "Price: 5€",
+            &SegmentationOptions::new()
+                .encoding(Encoding::GSM7)
+                .carrier_profile(&no_euro()),
+        );
+        match result {
+            Err(Error::IncompatibleCharacters { characters, .. }) => {
+                assert_eq!(characters.len(), 1);
+                assert_eq!(characters[0].grapheme, "€");
+                assert_eq!(characters[0].char_index, 8);
+            }
+            other => panic!("Expected incompatible characters, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_max_segments() {
+        let profile = CarrierProfile {
+            max_segments: 3,
+            ..CarrierProfile::generic_3gpp()
+        };
+        let fitting = with_profile(&"a".repeat(459), &profile).unwrap();
+        assert_eq!(fitting.check_segment_limit(), Ok(()));
+        assert_eq!(
+            fitting.warnings,
+            vec![Warning::NearSegmentBoundary { segments: 3 }]
+        );
+
+        let too_long = with_profile(&"a".repeat(460), &profile).unwrap();
+        assert_eq!(
+            too_long.warnings,
+            vec![Warning::TooManySegments {
+                segments: 4,
+                max_segments: 3
+            }]
+        );
+        assert_eq!(
+            too_long.check_segment_limit(),
+            Err(Error::MessageTooLong {
+                segments: 4,
+                max_segments: 3
+            })
+        );
+
+        let invalid = CarrierProfile {
+            max_segments: 0,
+            ..CarrierProfile::generic_3gpp()
+        };
+        assert!(matches!(
+            with_profile("Hello", &invalid),
+            Err(Error::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_mms_conversion() {
+        let profile = CarrierProfile {
+            mms_conversion_threshold: Some(2),
+            ..CarrierProfile::generic_3gpp()
+        };
+        let two = with_profile(&"a".repeat(306), &profile).unwrap();
+        assert_eq!(two.delivery(), Delivery::Sms { segments: 2 });
+
+        let three = with_profile(&"a".repeat(307), &profile).unwrap();
+        assert_eq!(three.delivery(), Delivery::Mms { segments: 3 });
+
+        let generic = SegmentedMessage::new(&"a".repeat(307), Encoding::Auto, false).unwrap();
+        assert_eq!(generic.delivery(), Delivery::Sms { segments: 3 });
+    }
+
+    #[test]
+    fn test_profile_from_toml() {
+        let profile = CarrierProfile::from_toml(
+            r#"
+            name = "us-carrier"
+            segment_capacity = { octets = 134 }
+            reference_width = "sixteen-bit"
+            max_segments = 8
+            mms_conversion_threshold = 6
+            rejected_characters = ["€", "|"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            profile,
+            CarrierProfile {
+                name: "us-carrier".to_string(),
+                segment_capacity: SegmentCapacity::from_octets(134),
+                reference_width: ReferenceWidth::SixteenBit,
+                max_segments: 8,
+                mms_conversion_threshold: Some(6),
+                rejected_characters: vec!['€', '|'],
+                twilio_reserved_bits: false,
+            }
+        );
+
+        let segmented_message = with_profile(&"a".repeat(200), &profile).unwrap();
+        assert_eq!(
+            segmented_message.segments[0].capacity,
+            SegmentCapacity::from_octets(134)
+        );
+        assert_eq!(
+            segmented_message.segments[0].reference_width,
+            ReferenceWidth::SixteenBit
+        );
+    }
+
+    #[test]
+    fn test_profile_from_json() {
+        let profile =
+            CarrierProfile::from_json(r#"{"name": "short", "segment_capacity": {"bits": 1000}}"#)
+                .unwrap();
+        assert_eq!(profile.segment_capacity, SegmentCapacity::from_bits(1000));
+        assert_eq!(profile.max_segments, 255);
+
+        let json = serde_json::to_string(&no_euro()).unwrap();
+        assert_eq!(CarrierProfile::from_json(&json).unwrap(), no_euro());
+    }
+
+    #[test]
+    fn test_invalid_profiles() {
+        assert!(matches!(
+            CarrierProfile::from_toml("max_segments = \"many\""),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(
+            CarrierProfile::from_json(r#"{"unknown_field": 1}"#),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(
+            CarrierProfile::from_toml("max_segments = 300"),
+            Err(Error::InvalidOption(_))
+        ));
+    }
+}
+
+mod pricing_tests {
+    use super::*;
+    use message_segment_calculator::pricing::{Decimal, RateTable};
+    use message_segment_calculator::{CarrierProfile, Delivery, Error, SegmentationOptions};
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+
+    const RATES: &str = "destination,encoding,price_per_segment,mms_price
+US,GSM7,0.0079,0.0200
+US,UCS2,0.0083,0.0200
+310,,0.0075,
+*,,0.05,
+";
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_sms_cost() {
+        let rates = RateTable::from_csv(RATES, "USD").unwrap();
+        assert_eq!(rates.rates.len(), 4);
+        assert_eq!(rates.rates[2].encoding, None);
+        assert_eq!(rates.rates[2].mms_price, None);
+
+        let gsm = SegmentedMessage::new(&"a".repeat(200), Encoding::Auto, false).unwrap();
+        let cost = rates.cost(&gsm, "US").unwrap();
+        assert_eq!(cost.encoding, Encoding::GSM7);
+        assert_eq!(cost.delivery, Delivery::Sms { segments: 2 });
+        assert_eq!(cost.unit_price, decimal("0.0079"));
+        assert_eq!(cost.total, decimal("0.0158"));
+        assert_eq!(cost.currency, "USD");
+
+        let ucs2 = SegmentedMessage::new("Hi 😜", Encoding::Auto, false).unwrap();
+        assert_eq!(rates.cost(&ucs2, "us").unwrap().total, decimal("0.0083"));
+        assert_eq!(rates.cost(&ucs2, "310").unwrap().total, decimal("0.0075"));
+        assert_eq!(rates.cost(&ucs2, "FR").unwrap().total, decimal("0.05"));
+    }
+
+    #[test]
+    fn test_mms_cost() {
+        let rates = RateTable::from_csv(RATES, "USD").unwrap();
+        let profile = CarrierProfile {
+            mms_conversion_threshold: Some(1),
+            ..CarrierProfile::generic_3gpp()
+        };
+        let segmented_message = SegmentedMessage::with_options(
+            &"a".repeat(200),
+            &SegmentationOptions::new().carrier_profile(&profile),
+        )
+        .unwrap();
+
+        let cost = rates.cost(&segmented_message, "US").unwrap();
+        assert_eq!(cost.delivery, Delivery::Mms { segments: 2 });
+        assert_eq!(cost.total, decimal("0.0200"));
+
+        let error = rates.cost(&segmented_message, "310").unwrap_err();
+        assert_eq!(
+            error,
+            Error::NoRate {
+                destination: "310".to_string(),
+                encoding: Encoding::GSM7,
+                mms: true,
+            }
+        );
+        assert_eq!(error.to_string(), "No MMS rate for destination 310");
+    }
+
+    #[test]
+    fn test_missing_rate() {
+        let rates = RateTable::from_csv(
+            "destination,encoding,price_per_segment,mms_price\nUS,GSM7,0.0079,\n",
+            "USD",
+        )
+        .unwrap();
+        let ucs2 = SegmentedMessage::new("Hi 😜", Encoding::Auto, false).unwrap();
+        let error = rates.cost(&ucs2, "US").unwrap_err();
+        assert_eq!(error.to_string(), "No UCS2 rate for destination US");
+    }
+
+    #[test]
+    fn test_json_rates_are_exact() {
+        let rates = RateTable::from_json(
+            r#"{
+                "currency": "EUR",
+                "rates": [
+                    {"destination": "DE", "encoding": "GSM7", "price_per_segment": 0.1, "mms_price": null},
+                    {"destination": "DE", "encoding": "UCS2", "price_per_segment": "0.2", "mms_price": null},
+                    {"destination": "FR", "encoding": null, "price_per_segment": "0.1234567890123456789", "mms_price": null},
+                    {"destination": "IT", "encoding": null, "price_per_segment": 0.0123456789012345678901, "mms_price": 25e-3}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let segmented_message =
+            SegmentedMessage::new(&"a".repeat(400), Encoding::Auto, false).unwrap();
+
+        let cost = rates.cost(&segmented_message, "DE").unwrap();
+        assert_eq!(cost.total, decimal("0.3"));
+        assert_eq!(cost.total.to_string(), "0.3");
+        assert_eq!(
+            rates.cost(&segmented_message, "FR").unwrap().unit_price,
+            decimal("0.1234567890123456789")
+        );
+
+        // Long JSON numbers aren't rounded to a float
+        let cost = rates.cost(&segmented_message, "IT").unwrap();
+        assert_eq!(cost.unit_price.to_string(), "0.0123456789012345678901");
+        assert_eq!(cost.total, decimal("0.0370370367037037036703"));
+        assert_eq!(rates.rates[3].mms_price, Some(decimal("0.025")));
+
+        assert!(RateTable::from_json(
+            r#"{"currency": "EUR", "rates": [{"destination": "*", "encoding": null, "price_per_segment": true, "mms_price": null}]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_batch_cost() {
+        let rates = RateTable::from_csv(RATES, "USD").unwrap();
+        let segmented_message =
+            SegmentedMessage::new(&"a".repeat(200), Encoding::Auto, false).unwrap();
+
+        let batch = rates
+            .batch_cost(&segmented_message, &["US", "US", "FR"])
+            .unwrap();
+        assert_eq!(batch.breakdowns.len(), 3);
+        assert_eq!(batch.total, decimal("0.1316"));
+        assert_eq!(batch.currency, "USD");
+        assert_eq!(
+            batch.by_destination().into_iter().collect::<Vec<_>>(),
+            vec![
+                ("FR".to_string(), decimal("0.10")),
+                ("US".to_string(), decimal("0.0316")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_rate_tables() {
+        assert!(matches!(
+            RateTable::from_csv(
+                "destination,encoding,price_per_segment,mms_price\nUS,GSM7,cheap,\n",
+                "USD"
+            ),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(
+            RateTable::from_json(r#"{"currency": "USD"}"#),
+            Err(Error::Decode(_))
+        ));
+    }
+}
+
+mod gsm7_charset_tests {
+    use super::*;
+    use message_segment_calculator::{decode_gsm7, LanguageShift};
+    use pretty_assertions::assert_eq;
+
+    // 3GPP TS 23.038 default alphabet by code; 0x1B is the escape
+    const DEFAULT_ALPHABET: [(u8, char); 127] = [
+        (0x00, '@'),
+        (0x01, '£'),
+        (0x02, '$'),
+        (0x03, '¥'),
+        (0x04, 'è'),
+        (0x05, 'é'),
+        (0x06, 'ù'),
+        (0x07, 'ì'),
+        (0x08, 'ò'),
+        (0x09, 'Ç'),
+        (0x0a, '\n'),
+        (0x0b, 'Ø'),
+        (0x0c, 'ø'),
+        (0x0d, '\r'),
+        (0x0e, 'Å'),
+        (0x0f, 'å'),
+        (0x10, 'Δ'),
+        (0x11, '_'),
+        (0x12, 'Φ'),
+        (0x13, 'Γ'),
+        (0x14, 'Λ'),
+        (0x15, 'Ω'),
+        (0x16, 'Π'),
+        (0x17, 'Ψ'),
+        (0x18, 'Σ'),
+        (0x19, 'Θ'),
+        (0x1a, 'Ξ'),
+        (0x1c, 'Æ'),
+        (0x1d, 'æ'),
+        (0x1e, 'ß'),
+        (0x1f, 'É'),
+        (0x20, ' '),
+        (0x21, '!'),
+        (0x22, '"'),
+        (0x23, '#'),
+        (0x24, '¤'),
+        (0x25, '%'),
+        (0x26, '&'),
+        (0x27, '\''),
+        (0x28, '('),
+        (0x29, ')'),
+        (0x2a, '*'),
+        (0x2b, '+'),
+        (0x2c, ','),
+        (0x2d, '-'),
+        (0x2e, '.'),
+        (0x2f, '/'),
+        (0x30, '0'),
+        (0x31, '1'),
+        (0x32, '2'),
+        (0x33, '3'),
+        (0x34, '4'),
+        (0x35, '5'),
+        (0x36, '6'),
+        (0x37, '7'),
+        (0x38, '8'),
+        (0x39, '9'),
+        (0x3a, ':'),
+        (0x3b, ';'),
+        (0x3c, '<'),
+        (0x3d, '='),
+        (0x3e, '>'),
+        (0x3f, '?'),
+        (0x40, '¡'),
+        (0x41, 'A'),
+        (0x42, 'B'),
+        (0x43, 'C'),
+        (0x44, 'D'),
+        (0x45, 'E'),
+        (0x46, 'F'),
+        (0x47, 'G'),
+        (0x48, 'H'),
+        (0x49, 'I'),
+        (0x4a, 'J'),
+        (0x4b, 'K'),
+        (0x4c, 'L'),
+        (0x4d, 'M'),
+        (0x4e, 'N'),
+        (0x4f, 'O'),
+        (0x50, 'P'),
+        (0x51, 'Q'),
+        (0x52, 'R'),
+        (0x53, 'S'),
+        (0x54, 'T'),
+        (0x55, 'U'),
+        (0x56, 'V'),
+        (0x57, 'W'),
+        (0x58, 'X'),
+        (0x59, 'Y'),
+        (0x5a, 'Z'),
+        (0x5b, 'Ä'),
+        (0x5c, 'Ö'),
+        (0x5d, 'Ñ'),
+        (0x5e, 'Ü'),
+        (0x5f, '§'),
+        (0x60, '¿'),
+        (0x61, 'a'),
+        (0x62, 'b'),
+        (0x63, 'c'),
+        (0x64, 'd'),
+        (0x65, 'e'),
+        (0x66, 'f'),
+        (0x67, 'g'),
+        (0x68, 'h'),
+        (0x69, 'i'),
+        (0x6a, 'j'),
+        (0x6b, 'k'),
+        (0x6c, 'l'),
+        (0x6d, 'm'),
+        (0x6e, 'n'),
+        (0x6f, 'o'),
+        (0x70, 'p'),
+        (0x71, 'q'),
+        (0x72, 'r'),
+        (0x73, 's'),
+        (0x74, 't'),
+        (0x75, 'u'),
+        (0x76, 'v'),
+        (0x77, 'w'),
+        (0x78, 'x'),
+        (0x79, 'y'),
+        (0x7a, 'z'),
+        (0x7b, 'ä'),
+        (0x7c, 'ö'),
+        (0x7d, 'ñ'),
+        (0x7e, 'ü'),
+        (0x7f, 'à'),
+    ];
+
+    // Codes following an escape
+    const EXTENSION_TABLE: [(u8, char); 10] = [
+        (0x0a, '\u{000c}'),
+        (0x14, '^'),
+        (0x28, '{'),
+        (0x29, '}'),
+        (0x2f, '\\'),
+        (0x3c, '['),
+        (0x3d, '~'),
+        (0x3e, ']'),
+        (0x40, '|'),
+        (0x65, '€'),
+    ];
+
+    #[test]
+    fn test_default_alphabet() {
+        let language_shift = LanguageShift::default();
+        for &(code, c) in DEFAULT_ALPHABET.iter() {
+            assert_eq!(language_shift.encode_char(c), Some(vec![code]), "{:?}", c);
+            assert_eq!(language_shift.decode_code(code), Some(c), "0x{:02x}", code);
+
+            let segmented_message =
+                SegmentedMessage::new(&c.to_string(), Encoding::Auto, false).unwrap();
+            assert_eq!(
+                segmented_message.get_encoding_name(),
+                Encoding::GSM7,
+                "{:?}",
+                c
+            );
+            assert_eq!(segmented_message.message_size(), 7, "{:?}", c);
+        }
+        assert_eq!(language_shift.decode_code(0x1b), None);
+    }
+
+    #[test]
+    fn test_extension_table() {
+        let language_shift = LanguageShift::default();
+        for &(code, c) in EXTENSION_TABLE.iter() {
+            assert_eq!(
+                language_shift.encode_char(c),
+                Some(vec![0x1b, code]),
+                "{:?}",
+                c
+            );
+            assert_eq!(
+                language_shift.decode_extension_code(code),
+                Some(c),
+                "0x{:02x}",
+                code
+            );
+
+            let segmented_message =
+                SegmentedMessage::new(&c.to_string(), Encoding::Auto, false).unwrap();
+            assert_eq!(
+                segmented_message.get_encoding_name(),
+                Encoding::GSM7,
+                "{:?}",
+                c
+            );
+            assert_eq!(segmented_message.message_size(), 14, "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_letters_stay_gsm7() {
+        let message = "Café Ärger Niño Straße ΣΩΔ";
+        let segmented_message = SegmentedMessage::new(message, Encoding::Auto, false).unwrap();
+        assert_eq!(segmented_message.get_encoding_name(), Encoding::GSM7);
+        assert_eq!(segmented_message.get_non_gsm_characters().len(), 0);
+
+        let packed = segmented_message.packed_user_data().unwrap();
+        assert_eq!(
+            decode_gsm7(
+                &packed[0],
+                message.chars().count(),
+                0,
+                LanguageShift::default()
+            )
+            .unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn test_characters_outside_the_alphabet() {
+        for c in ['á', 'ç', 'Ő', 'α', '`', '\t'] {
+            assert_eq!(LanguageShift::default().encode_char(c), None, "{:?}", c);
+            let segmented_message =
+                SegmentedMessage::new(&c.to_string(), Encoding::Auto, false).unwrap();
+            assert_eq!(
+                segmented_message.get_encoding_name(),
+                Encoding::UCS2,
+                "{:?}",
+                c
+            );
+        }
+    }
+}
+
+mod charset_tests {
+    use message_segment_calculator::{
+        decode_septets, Charset, GsmCode, LanguageShift, NationalLanguage,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_lookup_in_both_directions() {
+        let charset = Charset::default();
+        assert_eq!(charset.code('é'), Some(GsmCode::Basic(0x05)));
+        assert_eq!(charset.code('€'), Some(GsmCode::Extension(0x65)));
+        assert_eq!(charset.code('ç'), None);
+        assert_eq!(charset.char(GsmCode::Basic(0x05)), Some('é'));
+        assert_eq!(charset.char(GsmCode::Extension(0x65)), Some('€'));
+        assert_eq!(charset.char(GsmCode::Basic(0x1b)), None);
+        assert_eq!(GsmCode::Extension(0x65).septets(), vec![0x1b, 0x65]);
+        assert_eq!(charset.encode_char('€'), Some(vec![0x1b, 0x65]));
+    }
+
+    #[test]
+    fn test_undefined_extension_codes_fall_back_to_basic_table() {
+        let charset = Charset::default();
+        assert_eq!(charset.extension_table().char(0x41), None);
+        assert_eq!(charset.decode_extension_code(0x41), Some('A'));
+        assert_eq!(charset.decode_extension_code(0x1b), None);
+
+        let turkish = Charset::new(LanguageShift::new(
+            Some(NationalLanguage::Turkish),
+            Some(NationalLanguage::Turkish),
+        ));
+        assert_eq!(turkish.decode_extension_code(0x47), Some('Ğ'));
+        // 0x60 is only defined in the Turkish locking shift table
+        assert_eq!(turkish.decode_extension_code(0x60), Some('ç'));
+
+        assert_eq!(
+            decode_septets(&[0x48, 0x1b, 0x69, 0x1b, 0x65], LanguageShift::default()).unwrap(),
+            "Hi€"
+        );
+    }
+
+    #[test]
+    fn test_every_table_round_trips() {
+        for language_shift in LanguageShift::candidates(&NationalLanguage::ALL) {
+            let charset = language_shift.charset();
+            assert_eq!(charset.language_shift(), language_shift);
+
+            for (code, c) in charset.basic_table().entries() {
+                assert_eq!(charset.code(c), Some(GsmCode::Basic(code)), "{:?}", c);
+                assert_eq!(charset.decode_code(code), Some(c));
+            }
+            for (code, c) in charset.extension_table().entries() {
+                assert_eq!(charset.decode_extension_code(code), Some(c));
+                assert_eq!(charset.char(charset.code(c).unwrap()), Some(c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_languages_without_a_locking_shift_table() {
+        let spanish = Charset::new(LanguageShift::new(
+            Some(NationalLanguage::Spanish),
+            Some(NationalLanguage::Spanish),
+        ));
+        assert_eq!(spanish.decode_code(0x05), Some('é'));
+        assert_eq!(spanish.code('á'), Some(GsmCode::Extension(0x61)));
+        assert_eq!(Charset::default().basic_table().entries().count(), 127);
+        assert_eq!(Charset::default().extension_table().entries().count(), 10);
+    }
+}
+
+mod smart_encoding_tests {
+    use super::*;
+    use message_segment_calculator::{
+        AppliedSubstitution, Error, SegmentationOptions, SmartEncodingMap, Span,
+        SubstitutionCategory, Warning,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn replacements(message: &SegmentedMessage) -> Vec<(char, &str)> {
+        message
+            .warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                Warning::SmartEncodingReplacement {
+                    original,
+                    replacement,
+                    ..
+                } => Some((*original, replacement.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_built_in_categories() {
+        let map = SmartEncodingMap::default();
+        assert_eq!(map, SmartEncodingMap::built_in());
+        assert_eq!(
+            map.substitution('\u{201c}').unwrap().category,
+            SubstitutionCategory::Quotes
+        );
+        assert_eq!(
+            map.substitution('\u{2014}').unwrap().category,
+            SubstitutionCategory::Dashes
+        );
+        assert_eq!(
+            map.substitution('\u{00a0}').unwrap().category,
+            SubstitutionCategory::Spaces
+        );
+        assert_eq!(
+            map.substitution('\u{2044}').unwrap().category,
+            SubstitutionCategory::Fractions
+        );
+        assert_eq!(map.get('\u{3002}'), Some("."));
+        assert_eq!(map.get('a'), None);
+        assert_eq!(
+            map.substitutions().count(),
+            message_segment_calculator::smart_encoding_map::SMART_ENCODING_MAP.len()
+        );
+    }
+
+    #[test]
+    fn test_curly_quotes_preserved_and_dashes_flattened() {
+        let map = SmartEncodingMap::default().with_category(SubstitutionCategory::Quotes, false);
+        let options = SegmentationOptions::new().smart_encoding_map(map);
+        assert!(options.smart_encoding);
+
+        let message = SegmentedMessage::with_options("“Sale” — today", &options).unwrap();
+        assert_eq!(message.get_encoding_name(), Encoding::UCS2);
+        assert_eq!(replacements(&message), vec![('—', "-")]);
+
+        let message = SegmentedMessage::with_options("Sale — today", &options).unwrap();
+        assert_eq!(message.get_encoding_name(), Encoding::GSM7);
+    }
+
+    #[test]
+    fn test_override_extend_and_remove() {
+        let mut map = SmartEncodingMap::default()
+            .with_substitution('—', "~", SubstitutionCategory::Dashes)
+            .with_substitution('✓', "v", SubstitutionCategory::Other)
+            .without_substitution('\u{2026}');
+        assert_eq!(map.get('—'), Some("~"));
+        assert_eq!(map.get('✓'), Some("v"));
+        assert_eq!(map.get('\u{2026}'), None);
+
+        map.disable_category(SubstitutionCategory::Other);
+        assert!(!map.is_category_enabled(SubstitutionCategory::Other));
+        assert_eq!(map.get('✓'), None);
+        assert_eq!(map.apply("Done ✓ —"), "Done ✓ ~");
+        map.enable_category(SubstitutionCategory::Other);
+
+        let options = SegmentationOptions::new().smart_encoding_map(map.clone());
+        let message = SegmentedMessage::with_options("Done ✓ — …", &options).unwrap();
+        assert_eq!(replacements(&message), vec![('✓', "v"), ('—', "~")]);
+        assert_eq!(message.get_encoding_name(), Encoding::UCS2);
+
+        // The map is only used with smart encoding on
+        let options = SegmentationOptions::new()
+            .smart_encoding_map(map)
+            .smart_encoding(false);
+        let message = SegmentedMessage::with_options("Done ✓", &options).unwrap();
+        assert_eq!(replacements(&message), vec![]);
+    }
+
+    #[test]
+    fn test_multi_char_replacements() {
+        let message = SegmentedMessage::new("Wait… ½ off™ ©", Encoding::Auto, true).unwrap();
+        assert_eq!(message.get_encoding_name(), Encoding::GSM7);
+        assert_eq!(message.number_of_characters, 23);
+        assert_eq!(message.message_size(), 23 * 7);
+        assert_eq!(
+            replacements(&message),
+            vec![('…', "..."), ('½', "1/2"), ('™', "(TM)"), ('©', "(c)")]
+        );
+        assert_eq!(
+            message.warnings[0].span(),
+            Some(&Span {
+                char_range: 4..5,
+                byte_range: 4..7,
+            })
+        );
+
+        let map =
+            SmartEncodingMap::empty().with_substitution('ß', "ss", SubstitutionCategory::Other);
+        assert_eq!(map.apply("Straße"), "Strasse");
+    }
+
+    #[test]
+    fn test_multi_char_replacement_across_segments() {
+        // The three dots are the 153rd to 155th chars, so the first segment
+        // ends within them and the original ellipsis goes to the second one
+        let message = format!("{}…{}", "a".repeat(152), "b".repeat(10));
+        let segmented_message = SegmentedMessage::new(&message, Encoding::Auto, true).unwrap();
+        assert_eq!(segmented_message.segments_count(), 2);
+
+        let texts = segmented_message.segment_texts();
+        assert_eq!(texts[0].char_range, 0..152);
+        assert_eq!(texts[1].char_range, 152..163);
+        assert_eq!(texts[0].text, format!("{}.", "a".repeat(152)));
+        assert_eq!(texts[1].text, format!("..{}", "b".repeat(10)));
+        assert_eq!(
+            &message[texts[1].byte_range.clone()],
+            format!("…{}", "b".repeat(10))
+        );
+        assert_eq!(texts[1].byte_range, 152..165);
+    }
+
+    #[test]
+    fn test_substitution_report() {
+        let message = format!("“Sale” — {}…", "a".repeat(70));
+        let segmented_message = SegmentedMessage::new(&message, Encoding::Auto, true).unwrap();
+        let report = segmented_message.substitution_report();
+
+        assert_eq!(report.original_message, message);
+        assert_eq!(
+            report.transformed_message,
+            format!("\"Sale\" - {}...", "a".repeat(70))
+        );
+        assert_eq!(
+            report.substitutions,
+            vec![
+                AppliedSubstitution {
+                    original: '“',
+                    replacement: "\"".to_string(),
+                    span: Span {
+                        char_range: 0..1,
+                        byte_range: 0..3,
+                    },
+                    transformed_char_range: 0..1,
+                },
+                AppliedSubstitution {
+                    original: '”',
+                    replacement: "\"".to_string(),
+                    span: Span {
+                        char_range: 5..6,
+                        byte_range: 7..10,
+                    },
+                    transformed_char_range: 5..6,
+                },
+                AppliedSubstitution {
+                    original: '—',
+                    replacement: "-".to_string(),
+                    span: Span {
+                        char_range: 7..8,
+                        byte_range: 11..14,
+                    },
+                    transformed_char_range: 7..8,
+                },
+                AppliedSubstitution {
+                    original: '…',
+                    replacement: "...".to_string(),
+                    span: Span {
+                        char_range: 79..80,
+                        byte_range: 85..88,
+                    },
+                    transformed_char_range: 79..82,
+                },
+            ]
+        );
+        assert_eq!(report.encoding, Encoding::GSM7);
+        assert_eq!(report.segments, 1);
+        assert_eq!(report.original_encoding, Some(Encoding::UCS2));
+        assert_eq!(report.original_segments, Some(2));
+        assert_eq!(report.saved_segments(), Some(1));
+        // The comparison doesn't touch the message
+        assert_eq!(
+            segmented_message.transformed_message(),
+            report.transformed_message
+        );
+    }
+
+    #[test]
+    fn test_substitution_report_without_smart_encoding() {
+        let segmented_message =
+            SegmentedMessage::new("“Sale” — today", Encoding::Auto, false).unwrap();
+        let report = segmented_message.substitution_report();
+        assert_eq!(report.transformed_message, report.original_message);
+        assert_eq!(report.substitutions, vec![]);
+        assert_eq!(report.saved_segments(), Some(0));
+    }
+
+    #[test]
+    fn test_substitution_report_when_gsm7_needs_substitutions() {
+        let segmented_message = SegmentedMessage::new("It’s on", Encoding::GSM7, true).unwrap();
+        let report = segmented_message.substitution_report();
+        assert_eq!(report.transformed_message, "It's on");
+        assert_eq!(report.original_encoding, None);
+        assert_eq!(report.original_segments, None);
+        assert_eq!(report.saved_segments(), None);
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let map = SmartEncodingMap::from_toml(
+            r#"
+            disabled_categories = ["quotes"]
+            removed = ["…"]
+
+            [[substitutions]]
+            original = "✓"
+            replacement = "v"
+
+            [[substitutions]]
+            original = "—"
+            replacement = "~"
+            category = "dashes"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(map.get('\u{201c}'), None);
+        assert_eq!(map.get('\u{2026}'), None);
+        assert_eq!(map.get('–'), Some("-"));
+        assert_eq!(map.get('—'), Some("~"));
+        assert_eq!(
+            map.substitution('✓').unwrap().category,
+            SubstitutionCategory::Other
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        let map = SmartEncodingMap::from_json(
+            r#"{
+                "extends_default": false,
+                "substitutions": [
+                    {"original": "—", "replacement": "-", "category": "dashes"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(map.substitutions().count(), 1);
+        assert_eq!(map.get('—'), Some("-"));
+        assert_eq!(map.get('\u{201c}'), None);
+
+        assert_eq!(
+            SmartEncodingMap::from_json("{}").unwrap(),
+            SmartEncodingMap::default()
+        );
+        assert!(matches!(
+            SmartEncodingMap::from_json(r#"{"disabled_categories": ["emoji"]}"#),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(
+            SmartEncodingMap::from_toml(
+                "[[substitutions]]\noriginal = \"ab\"\nreplacement = \"-\""
+            ),
+            Err(Error::Decode(_))
+        ));
+    }
+}
+
+mod message_editor_tests {
+    use message_segment_calculator::{
+        EditorSummary, Encoding, Error, MessageEditor, NationalLanguage, SegmentationOptions,
+        SegmentedMessage,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn expected_summary(message: &str, options: &SegmentationOptions) -> EditorSummary {
+        let segmented_message = SegmentedMessage::with_options(message, options).unwrap();
+        let encoding = segmented_message.get_encoding_name();
+        let free_bits = segmented_message
+            .segments
+            .last()
+            .unwrap()
+            .free_size_in_bits() as usize;
+        EditorSummary {
+            encoding,
+            segments: segmented_message.segments_count(),
+            remaining_characters: free_bits / if encoding == Encoding::UCS2 { 16 } else { 7 },
+        }
+    }
+
+    // Types the message a char at a time, then deletes it, checking every
+    // step against a message segmented from scratch
+    fn check_typing(message: &str, options: &SegmentationOptions) {
+        let mut editor = MessageEditor::new(options).unwrap();
+        let mut typed = String::new();
+        for c in message.chars() {
+            typed.push(c);
+            assert_eq!(
+                editor.push(c),
+                expected_summary(&typed, options),
+                "{:?}",
+                typed
+            );
+        }
+        assert_eq!(editor.text(), message);
+
+        while typed.pop().is_some() {
+            let summary = editor.pop().unwrap();
+            assert_eq!(editor.text(), typed);
+            assert_eq!(summary, expected_summary(&typed, options), "{:?}", typed);
+        }
+        assert_eq!(editor.pop(), None);
+        assert!(editor.is_empty());
+    }
+
+    #[test]
+    fn test_typing_gsm7() {
+        let options = SegmentationOptions::new();
+        check_typing(&"Hello world, {see} €5 ".repeat(20), &options);
+    }
+
+    #[test]
+    fn test_typing_ucs2_switch() {
+        let options = SegmentationOptions::new();
+        check_typing(
+            &format!("{}😜{}", "a".repeat(150), "b".repeat(150)),
+            &options,
+        );
+        check_typing(&"Café 👍🏽 e\u{301} 🇺🇸🇬🇧 👨‍👩‍👧\r\n".repeat(8), &options);
+    }
+
+    #[test]
+    fn test_typing_with_smart_encoding() {
+        let options = SegmentationOptions::new().smart_encoding(true);
+        check_typing(&"“Sale” — ½ off… ".repeat(12), &options);
+    }
+
+    #[test]
+    fn test_forced_encodings() {
+        check_typing(
+            &"Hi {there} ".repeat(30),
+            &SegmentationOptions::new().encoding(Encoding::UCS2),
+        );
+        check_typing(
+            &"Hi [there] ".repeat(30),
+            &SegmentationOptions::new().encoding(Encoding::GSM7),
+        );
+    }
+
+    #[test]
+    fn test_push_str_and_switch_back() {
+        let mut editor = MessageEditor::new(&SegmentationOptions::new()).unwrap();
+        assert_eq!(
+            editor.push_str(&"a".repeat(160)),
+            EditorSummary {
+                encoding: Encoding::GSM7,
+                segments: 1,
+                remaining_characters: 0,
+            }
+        );
+        assert_eq!(
+            editor.push('😜'),
+            EditorSummary {
+                encoding: Encoding::UCS2,
+                segments: 3,
+                remaining_characters: 39,
+            }
+        );
+        assert_eq!(editor.non_gsm_characters(), vec!["😜"]);
+
+        assert_eq!(
+            editor.pop(),
+            Some(EditorSummary {
+                encoding: Encoding::GSM7,
+                segments: 1,
+                remaining_characters: 0,
+            })
+        );
+        assert_eq!(
+            editor.segmented_message().unwrap().original_message,
+            "a".repeat(160)
+        );
+    }
+
+    #[test]
+    fn test_insert_and_delete() {
+        let options = SegmentationOptions::new().smart_encoding(true);
+        let mut editor = MessageEditor::new(&options).unwrap();
+        let mut expected = "Hello world… ".repeat(14).chars().collect::<Vec<char>>();
+        editor.push_str(&expected.iter().collect::<String>());
+
+        let edits: [(usize, &str, usize); 6] = [
+            (0, "😜", 0),
+            (5, "e\u{301}", 2),
+            (100, "👍", 0),
+            (101, "\u{1f3fd}", 0),
+            (expected.len() - 3, "“x”", 3),
+            (40, "", 60),
+        ];
+        for (position, inserted, deleted) in edits {
+            let summary = editor
+                .delete(position..position + deleted)
+                .and_then(|_| editor.insert(position, inserted))
+                .unwrap();
+            expected.splice(position..position + deleted, inserted.chars());
+            let expected = expected.iter().collect::<String>();
+            assert_eq!(editor.text(), expected);
+            assert_eq!(
+                summary,
+                expected_summary(&expected, &options),
+                "{:?}",
+                expected
+            );
+        }
+
+        // Deleting the last UCS-2 character switches back to GSM-7
+        assert_eq!(
+            editor.non_gsm_characters(),
+            vec!["😜", "e\u{301}", "👍\u{1f3fd}"]
+        );
+        for (first, len) in [('😜', 1), ('\u{301}', 1), ('👍', 2)] {
+            let position = editor.text().chars().position(|c| c == first).unwrap();
+            let summary = editor.delete(position..position + len).unwrap();
+            assert_eq!(summary, expected_summary(editor.text(), &options));
+        }
+        assert_eq!(editor.encoding(), Encoding::GSM7);
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        let mut editor = MessageEditor::new(&SegmentationOptions::new()).unwrap();
+        editor.push_str("Hé😜");
+        assert_eq!(editor.len(), 3);
+        assert!(matches!(
+            editor.insert(4, "a"),
+            Err(Error::InvalidOption(_))
+        ));
+        assert!(matches!(editor.delete(2..4), Err(Error::InvalidOption(_))));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 2..1;
+        assert!(matches!(
+            editor.delete(reversed),
+            Err(Error::InvalidOption(_))
+        ));
+        assert_eq!(editor.text(), "Hé😜");
+        assert_eq!(editor.insert(3, "!").unwrap().encoding, Encoding::UCS2);
+    }
+
+    #[test]
+    fn test_long_message() {
+        let mut editor = MessageEditor::new(&SegmentationOptions::new()).unwrap();
+        for _ in 0..100_000 {
+            editor.push('a');
+        }
+        assert_eq!(
+            editor.summary(),
+            EditorSummary {
+                encoding: Encoding::GSM7,
+                segments: 654,
+                remaining_characters: 62,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unsupported_options() {
+        assert!(matches!(
+            MessageEditor::new(
+                &SegmentationOptions::new().national_languages(&[NationalLanguage::Turkish])
+            ),
+            Err(Error::InvalidOption(_))
+        ));
+    }
+}